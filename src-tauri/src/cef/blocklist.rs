@@ -0,0 +1,117 @@
+/// Navigation blocklist for the AI Browser
+///
+/// Blocks navigation to listed hosts and their subdomains (focus mode).
+
+use super::url_host;
+use crate::error::AppError;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// Host patterns the AI browser refuses to navigate to
+pub struct NavigationBlocklist {
+    hosts: Mutex<HashSet<String>>,
+}
+
+/// Canonical form of a host pattern: lowercase, without `*.`/`.` prefix or trailing dot
+fn normalize_pattern(pattern: &str) -> String {
+    let pattern = pattern.trim().to_ascii_lowercase();
+    let pattern = pattern.strip_prefix("*.").unwrap_or(&pattern);
+    pattern.trim_start_matches('.').trim_end_matches('.').to_string()
+}
+
+impl NavigationBlocklist {
+    /// Create an empty blocklist
+    pub fn new() -> Self {
+        NavigationBlocklist {
+            hosts: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Block a host pattern (e.g. `example.com` also blocks `www.example.com`)
+    /// Returns false if the pattern was already blocked
+    pub fn add(&self, pattern: &str) -> Result<bool, AppError> {
+        let pattern = normalize_pattern(pattern);
+        if pattern.is_empty() {
+            return Err(AppError::InvalidPath("Host pattern cannot be empty".into()));
+        }
+
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(hosts.insert(pattern))
+    }
+
+    /// Unblock a host pattern
+    /// Returns false if the pattern was not blocked
+    pub fn remove(&self, pattern: &str) -> bool {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        hosts.remove(&normalize_pattern(pattern))
+    }
+
+    /// List blocked host patterns, sorted
+    pub fn list(&self) -> Vec<String> {
+        let hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let mut list: Vec<String> = hosts.iter().cloned().collect();
+        list.sort();
+        list
+    }
+
+    /// Check whether a URL's host or any of its parent domains is blocked
+    pub fn is_blocked(&self, url: &str) -> bool {
+        let Some(host) = url_host(url) else {
+            return false;
+        };
+
+        let hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        if hosts.is_empty() {
+            return false;
+        }
+
+        let mut candidate = host.as_str();
+        loop {
+            if hosts.contains(candidate) {
+                return true;
+            }
+            match candidate.split_once('.') {
+                Some((_, parent)) => candidate = parent,
+                None => return false,
+            }
+        }
+    }
+}
+
+impl Default for NavigationBlocklist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_host_and_subdomains() {
+        let blocklist = NavigationBlocklist::new();
+        blocklist.add("example.com").unwrap();
+
+        assert!(blocklist.is_blocked("https://example.com"));
+        assert!(blocklist.is_blocked("https://www.example.com/path"));
+        assert!(blocklist.is_blocked("http://a.b.EXAMPLE.com:8080/?q=1"));
+        assert!(!blocklist.is_blocked("https://notexample.com"));
+        assert!(!blocklist.is_blocked("https://example.com.evil.org"));
+        assert!(!blocklist.is_blocked("https://google.com"));
+    }
+
+    #[test]
+    fn test_add_and_remove() {
+        let blocklist = NavigationBlocklist::new();
+
+        assert!(blocklist.add("*.Example.com").unwrap());
+        assert!(!blocklist.add("example.com").unwrap());
+        assert!(blocklist.add("  ").is_err());
+        assert_eq!(blocklist.list(), vec!["example.com".to_string()]);
+
+        assert!(blocklist.remove("example.com"));
+        assert!(!blocklist.remove("example.com"));
+        assert!(!blocklist.is_blocked("https://www.example.com"));
+    }
+}
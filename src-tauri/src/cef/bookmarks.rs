@@ -0,0 +1,355 @@
+/// Bookmark management for the AI Browser
+///
+/// Bookmarks are kept in memory and persisted to a JSON file under the app data dir.
+
+use crate::error::AppError;
+use serde::{Serialize, Deserialize};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// A saved bookmark
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    pub created_at: u64,
+    /// Folder path such as `Work/Research`; `None` for the top level
+    #[serde(default)]
+    pub folder: Option<String>,
+}
+
+/// Bookmarks grouped into nested folders
+/// Folders only exist while they contain bookmarks, so emptied folders disappear on their own
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct BookmarkTree {
+    /// Folder name (empty for the root)
+    pub name: String,
+    /// Full folder path (empty for the root)
+    pub path: String,
+    /// Subfolders, sorted by name
+    pub folders: Vec<BookmarkTree>,
+    /// Bookmarks directly in this folder, in insertion order
+    pub bookmarks: Vec<Bookmark>,
+}
+
+impl BookmarkTree {
+    fn insert(&mut self, segments: &[&str], bookmark: Bookmark) {
+        let Some((first, rest)) = segments.split_first() else {
+            self.bookmarks.push(bookmark);
+            return;
+        };
+
+        let index = match self.folders.iter().position(|f| f.name == *first) {
+            Some(index) => index,
+            None => {
+                let path = if self.path.is_empty() { first.to_string() } else { format!("{}/{}", self.path, first) };
+                self.folders.push(BookmarkTree { name: first.to_string(), path, ..Default::default() });
+                self.folders.len() - 1
+            }
+        };
+        self.folders[index].insert(rest, bookmark);
+    }
+
+    fn sort(&mut self) {
+        self.folders.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        for folder in &mut self.folders {
+            folder.sort();
+        }
+    }
+}
+
+/// Clean up a folder path: trim segments and drop empty ones (`" Work//Research/ "` -> `Work/Research`)
+/// Returns `None` for the top level
+pub fn normalize_folder(folder: &str) -> Option<String> {
+    let segments: Vec<&str> = folder.split('/').map(str::trim).filter(|s| !s.is_empty()).collect();
+    if segments.is_empty() { None } else { Some(segments.join("/")) }
+}
+
+/// Bookmark store
+pub struct BookmarkStore {
+    bookmarks: Mutex<Vec<Bookmark>>,
+}
+
+impl BookmarkStore {
+    /// Create an empty bookmark store
+    pub fn new() -> Self {
+        BookmarkStore {
+            bookmarks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Load bookmarks from a JSON file
+    /// A missing or corrupt file yields an empty store
+    pub fn load(path: &Path) -> Self {
+        let bookmarks = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<Vec<Bookmark>>(&json).ok())
+            .unwrap_or_default();
+
+        BookmarkStore {
+            bookmarks: Mutex::new(bookmarks),
+        }
+    }
+
+    /// Save bookmarks to a JSON file
+    pub fn save(&self, path: &Path) -> Result<(), AppError> {
+        let bookmarks = self.bookmarks.lock().unwrap_or_else(|e| e.into_inner());
+
+        let json = serde_json::to_string_pretty(&*bookmarks)
+            .map_err(|e| AppError::InvalidPath(format!("Failed to serialize bookmarks: {}", e)))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, json)?;
+
+        Ok(())
+    }
+
+    /// Add a bookmark
+    /// Bookmarking an already bookmarked URL updates its title instead of adding a duplicate
+    pub fn add(&self, url: String, title: String) -> Result<Bookmark, AppError> {
+        let mut bookmarks = self.bookmarks.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(existing) = bookmarks.iter_mut().find(|b| b.url == url) {
+            existing.title = title;
+            return Ok(existing.clone());
+        }
+
+        let bookmark = Bookmark {
+            id: uuid::Uuid::new_v4().to_string(),
+            url,
+            title,
+            created_at: super::system_clock(),
+            folder: None,
+        };
+        bookmarks.push(bookmark.clone());
+
+        Ok(bookmark)
+    }
+
+    /// Remove a bookmark by id
+    /// Returns false if no bookmark has that id
+    pub fn remove(&self, id: &str) -> Result<bool, AppError> {
+        let mut bookmarks = self.bookmarks.lock().unwrap_or_else(|e| e.into_inner());
+
+        let len_before = bookmarks.len();
+        bookmarks.retain(|b| b.id != id);
+        Ok(bookmarks.len() != len_before)
+    }
+
+    /// List all bookmarks in insertion order
+    pub fn list(&self) -> Result<Vec<Bookmark>, AppError> {
+        let bookmarks = self.bookmarks.lock().unwrap_or_else(|e| e.into_inner());
+
+        Ok(bookmarks.clone())
+    }
+
+    /// Group all bookmarks into their folders
+    pub fn tree(&self) -> Result<BookmarkTree, AppError> {
+        let bookmarks = self.bookmarks.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut tree = BookmarkTree::default();
+        for bookmark in bookmarks.iter() {
+            let folder = bookmark.folder.as_deref().and_then(normalize_folder).unwrap_or_default();
+            let segments: Vec<&str> = folder.split('/').filter(|s| !s.is_empty()).collect();
+            tree.insert(&segments, bookmark.clone());
+        }
+        tree.sort();
+
+        Ok(tree)
+    }
+
+    /// Move a bookmark into a folder (`None` or an empty path for the top level)
+    /// Returns false if no bookmark has that id
+    pub fn move_to_folder(&self, id: &str, folder: Option<&str>) -> Result<bool, AppError> {
+        let mut bookmarks = self.bookmarks.lock().unwrap_or_else(|e| e.into_inner());
+
+        match bookmarks.iter_mut().find(|b| b.id == id) {
+            Some(bookmark) => {
+                bookmark.folder = folder.and_then(normalize_folder);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Rename a folder, moving every bookmark in it and its subfolders along
+    /// Returns the number of bookmarks moved
+    pub fn rename_folder(&self, old: &str, new: &str) -> Result<usize, AppError> {
+        let Some(old) = normalize_folder(old) else {
+            return Err(AppError::InvalidPath("folder cannot be empty".into()));
+        };
+        let new = normalize_folder(new);
+        let mut bookmarks = self.bookmarks.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut moved = 0;
+        for bookmark in bookmarks.iter_mut() {
+            let Some(folder) = bookmark.folder.as_deref().and_then(normalize_folder) else {
+                continue;
+            };
+            let rest = if folder == old {
+                ""
+            } else if let Some(rest) = folder.strip_prefix(&format!("{}/", old)) {
+                rest
+            } else {
+                continue;
+            };
+
+            bookmark.folder = match &new {
+                Some(new) => normalize_folder(&format!("{}/{}", new, rest)),
+                None => normalize_folder(rest),
+            };
+            moved += 1;
+        }
+
+        Ok(moved)
+    }
+
+    /// The bookmarks an import would leave, without changing the store
+    /// Replaces the current ones, or merges them in skipping URLs that are already bookmarked;
+    /// apply the result with `replace_all`
+    pub fn stage_import(&self, imported: Vec<Bookmark>, merge: bool) -> Result<Vec<Bookmark>, AppError> {
+        if !merge {
+            return Ok(imported);
+        }
+
+        let mut staged = self.list()?;
+        for bookmark in imported {
+            if !staged.iter().any(|b| b.url == bookmark.url) {
+                staged.push(bookmark);
+            }
+        }
+
+        Ok(staged)
+    }
+
+    /// Replace all bookmarks
+    pub fn replace_all(&self, bookmarks: Vec<Bookmark>) -> Result<(), AppError> {
+        let mut current = self.bookmarks.lock().unwrap_or_else(|e| e.into_inner());
+
+        *current = bookmarks;
+        Ok(())
+    }
+
+    /// Number of bookmarks
+    pub fn count(&self) -> Result<usize, AppError> {
+        let bookmarks = self.bookmarks.lock().unwrap_or_else(|e| e.into_inner());
+
+        Ok(bookmarks.len())
+    }
+
+    /// Check whether a URL is bookmarked
+    pub fn is_bookmarked(&self, url: &str) -> Result<bool, AppError> {
+        let bookmarks = self.bookmarks.lock().unwrap_or_else(|e| e.into_inner());
+
+        Ok(bookmarks.iter().any(|b| b.url == url))
+    }
+}
+
+impl Default for BookmarkStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_list() {
+        let store = BookmarkStore::new();
+        let bookmark = store.add("https://example.com".to_string(), "Example".to_string()).unwrap();
+
+        assert!(!bookmark.id.is_empty());
+        assert_eq!(store.list().unwrap(), vec![bookmark]);
+        assert!(store.is_bookmarked("https://example.com").unwrap());
+        assert!(!store.is_bookmarked("https://google.com").unwrap());
+    }
+
+    #[test]
+    fn test_add_dedupes_on_url() {
+        let store = BookmarkStore::new();
+        let first = store.add("https://example.com".to_string(), "Example".to_string()).unwrap();
+        let second = store.add("https://example.com".to_string(), "Example Domain".to_string()).unwrap();
+
+        assert_eq!(first.id, second.id);
+        let bookmarks = store.list().unwrap();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].title, "Example Domain");
+    }
+
+    #[test]
+    fn test_remove() {
+        let store = BookmarkStore::new();
+        let bookmark = store.add("https://example.com".to_string(), "Example".to_string()).unwrap();
+
+        assert!(store.remove(&bookmark.id).unwrap());
+        assert!(!store.remove(&bookmark.id).unwrap());
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bookmarks.json");
+
+        let store = BookmarkStore::new();
+        store.add("https://example.com".to_string(), "Example".to_string()).unwrap();
+        store.add("https://google.com".to_string(), "Google".to_string()).unwrap();
+        store.save(&path).unwrap();
+
+        let loaded = BookmarkStore::load(&path);
+        assert_eq!(loaded.list().unwrap(), store.list().unwrap());
+
+        let missing = BookmarkStore::load(&dir.path().join("missing.json"));
+        assert!(missing.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_move_between_folders() {
+        let store = BookmarkStore::new();
+        let rust = store.add("https://rust-lang.org".to_string(), "Rust".to_string()).unwrap();
+        let tauri = store.add("https://tauri.app".to_string(), "Tauri".to_string()).unwrap();
+
+        assert!(store.move_to_folder(&rust.id, Some("Work/Research")).unwrap());
+        assert!(store.move_to_folder(&tauri.id, Some(" Work / Research/")).unwrap());
+        assert!(!store.move_to_folder("missing", Some("Work")).unwrap());
+
+        let tree = store.tree().unwrap();
+        assert!(tree.bookmarks.is_empty());
+        assert_eq!(tree.folders.len(), 1);
+        let research = &tree.folders[0].folders[0];
+        assert_eq!(research.path, "Work/Research");
+        assert_eq!(research.bookmarks.len(), 2);
+
+        // Moving both out leaves no empty folders behind
+        store.move_to_folder(&rust.id, Some("Personal")).unwrap();
+        store.move_to_folder(&tauri.id, None).unwrap();
+        let tree = store.tree().unwrap();
+        let folders: Vec<&str> = tree.folders.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(folders, vec!["Personal"]);
+        assert_eq!(tree.bookmarks[0].id, tauri.id);
+    }
+
+    #[test]
+    fn test_rename_folder_cascades() {
+        let store = BookmarkStore::new();
+        let a = store.add("https://a.com".to_string(), "A".to_string()).unwrap();
+        let b = store.add("https://b.com".to_string(), "B".to_string()).unwrap();
+        let c = store.add("https://c.com".to_string(), "C".to_string()).unwrap();
+        store.move_to_folder(&a.id, Some("Work")).unwrap();
+        store.move_to_folder(&b.id, Some("Work/Research")).unwrap();
+        store.move_to_folder(&c.id, Some("Workshop")).unwrap();
+
+        assert_eq!(store.rename_folder("Work", "Archive/2024").unwrap(), 2);
+
+        let folder_of = |id: &str| store.list().unwrap().into_iter().find(|bm| bm.id == id).unwrap().folder;
+        assert_eq!(folder_of(a.id.as_str()), Some("Archive/2024".to_string()));
+        assert_eq!(folder_of(b.id.as_str()), Some("Archive/2024/Research".to_string()));
+        assert_eq!(folder_of(c.id.as_str()), Some("Workshop".to_string()));
+        assert!(store.rename_folder("", "Anything").is_err());
+    }
+}
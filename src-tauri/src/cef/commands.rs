@@ -3,85 +3,350 @@
 /// Tauri commands for CEF browser operations
 
 use crate::error::AppError;
-use tauri::{AppHandle, Emitter};
+use super::{internal_page_name, BrowserBounds, BrowserConfig, CefBrowserInfo, CefBrowserManager, CefInstancePool, FindState, HistorySearchHit, HistoryView, LoadError, NavigationTarget, TabSummary};
+use super::bookmarks::{Bookmark, BookmarkStore, BookmarkTree};
+use super::blocklist::NavigationBlocklist;
+use super::debounce::{BoundsDebouncer, BOUNDS_FLUSH_INTERVAL};
+use super::downloads::{DownloadItem, DownloadManager, DownloadState};
+use super::pending::PendingResults;
+use super::reader::extract_readable;
+use super::registry::TabRegistry;
+use super::session;
+use log::{debug, info, warn};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 use serde::{Serialize, Deserialize};
 
+/// Reject an empty tab id, logging the rejection
+fn require_tab_id(command: &str, tab_id: &str) -> Result<(), AppError> {
+    if tab_id.is_empty() {
+        warn!("[CEF] {} rejected: tab_id cannot be empty", command);
+        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
+    }
+    Ok(())
+}
+
+/// Location of the persisted browser session in the app data dir
+fn session_file_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let app_dir = app.path().app_data_dir()
+        .map_err(|e| AppError::InvalidPath(format!("Failed to get app dir: {}", e)))?;
+    Ok(app_dir.join("cef-session.json"))
+}
+
+/// Location of the persisted bookmarks in the app data dir
+pub fn bookmarks_file_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let app_dir = app.path().app_data_dir()
+        .map_err(|e| AppError::InvalidPath(format!("Failed to get app dir: {}", e)))?;
+    Ok(app_dir.join("cef-bookmarks.json"))
+}
+
+/// Fail with `AppError::NotFound` unless a browser is registered for `tab_id`
+fn ensure_tab_exists(manager: &CefBrowserManager, tab_id: &str) -> Result<(), AppError> {
+    if !manager.contains(tab_id)? {
+        return Err(AppError::NotFound(format!("tab {}", tab_id)));
+    }
+    Ok(())
+}
+
+/// Validate, register and announce a new browser tab
+#[allow(clippy::too_many_arguments)]
+fn create_browser(
+    app: &AppHandle,
+    manager: &CefBrowserManager,
+    config: &BrowserConfig,
+    tab_id: String,
+    url: String,
+    x: Option<f64>,
+    y: Option<f64>,
+    width: Option<f64>,
+    height: Option<f64>,
+    is_private: bool,
+) -> Result<(), AppError> {
+    // Validate URL; an empty one opens the home page
+    let url = config.resolve_url(&url)?;
+
+    // Validate tab_id
+    require_tab_id("create_browser", &tab_id)?;
+
+    // Validate dimensions, with missing ones taken from the configured defaults
+    let BrowserBounds { x, y, width, height } = config.resolve_bounds(x, y, width, height)?;
+
+    // TODO: Implement actual CEF browser creation
+    // This is a placeholder that will be replaced with actual CEF integration
+    
+    debug!(
+        "[CEF] create_cef_browser: tab_id={} url={} pos=({}, {}) size={}x{} private={}",
+        tab_id, url, x, y, width, height, is_private
+    );
+
+    if is_private {
+        manager.register_browser_private(tab_id.clone(), url.clone())?;
+    } else {
+        manager.register_browser(tab_id.clone(), url.clone())?;
+    }
+
+    // Emit event to frontend
+    let _ = app.emit("cef:browser-created", CreateCefBrowserPayload {
+        tab_id: tab_id.clone(),
+        url: url.clone(),
+    });
+    if let Some(payload) = internal_page_payload(&tab_id, &url, manager.now_ms()) {
+        let _ = app.emit("cef:show-internal-page", payload);
+    }
+
+    Ok(())
+}
+
+/// Payload asking the frontend to render an internal page (`about:*`) for a tab, or None
+/// if the URL is not an internal page
+fn internal_page_payload(tab_id: &str, url: &str, timestamp: u64) -> Option<ShowInternalPagePayload> {
+    internal_page_name(url).map(|page| ShowInternalPagePayload {
+        tab_id: tab_id.to_string(),
+        page,
+        timestamp,
+    })
+}
+
 /// Create a new CEF browser instance
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `config` - Browser configuration state
 /// * `tab_id` - Unique identifier for the browser tab
-/// * `url` - Initial URL to load
-/// * `x` - X position in logical pixels
-/// * `y` - Y position in logical pixels
-/// * `width` - Width in logical pixels
-/// * `height` - Height in logical pixels
+/// * `url` - Initial URL to load; empty opens the home page
+/// * `x` - X position in logical pixels (default from the browser config)
+/// * `y` - Y position in logical pixels (default from the browser config)
+/// * `width` - Width in logical pixels (default from the browser config)
+/// * `height` - Height in logical pixels (default from the browser config)
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_cef_browser(
     app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    config: State<'_, BrowserConfig>,
     tab_id: String,
     url: String,
-    x: f64,
-    y: f64,
-    width: f64,
-    height: f64,
+    x: Option<f64>,
+    y: Option<f64>,
+    width: Option<f64>,
+    height: Option<f64>,
 ) -> Result<(), AppError> {
-    // Validate URL
-    if url.is_empty() {
-        return Err(AppError::InvalidPath("URL cannot be empty".into()));
+    create_browser(&app, &manager, &config, tab_id, url, x, y, width, height, false)
+}
+
+/// Create a new private (incognito) CEF browser tab
+/// Private tabs keep no history, are left out of saved sessions and can't be bookmarked
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `config` - Browser configuration state
+/// * `tab_id` - Unique identifier for the browser tab
+/// * `url` - Initial URL to load; empty opens the home page
+/// * `x` - X position in logical pixels (default from the browser config)
+/// * `y` - Y position in logical pixels (default from the browser config)
+/// * `width` - Width in logical pixels (default from the browser config)
+/// * `height` - Height in logical pixels (default from the browser config)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn cef_create_private_tab(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    config: State<'_, BrowserConfig>,
+    tab_id: String,
+    url: String,
+    x: Option<f64>,
+    y: Option<f64>,
+    width: Option<f64>,
+    height: Option<f64>,
+) -> Result<(), AppError> {
+    create_browser(&app, &manager, &config, tab_id, url, x, y, width, height, true)
+}
+
+/// Get the configured home page URL
+/// 
+/// # Arguments
+/// * `config` - Browser configuration state
+#[tauri::command]
+pub async fn get_home_url(
+    config: State<'_, BrowserConfig>,
+) -> Result<String, AppError> {
+    Ok(config.home_url())
+}
+
+/// Set the home page URL; an empty URL resets it to `about:blank`
+/// Returns the normalized URL that was stored
+/// 
+/// # Arguments
+/// * `config` - Browser configuration state
+/// * `url` - New home page URL
+#[tauri::command]
+pub async fn set_home_url(
+    config: State<'_, BrowserConfig>,
+    url: String,
+) -> Result<String, AppError> {
+    debug!("[CEF] set_home_url: url={}", url);
+
+    config.set_home_url(&url)
+}
+
+/// Get the bounds used for new browsers when none are given
+/// 
+/// # Arguments
+/// * `config` - Browser configuration state
+#[tauri::command]
+pub async fn get_default_bounds(
+    config: State<'_, BrowserConfig>,
+) -> Result<BrowserBounds, AppError> {
+    Ok(config.default_bounds())
+}
+
+/// Set the bounds used for new browsers when none are given
+/// 
+/// # Arguments
+/// * `config` - Browser configuration state
+/// * `bounds` - New default bounds; width and height must be positive
+#[tauri::command]
+pub async fn set_default_bounds(
+    config: State<'_, BrowserConfig>,
+    bounds: BrowserBounds,
+) -> Result<(), AppError> {
+    debug!("[CEF] set_default_bounds: {:?}", bounds);
+
+    config.set_default_bounds(bounds)
+}
+
+/// Whether tabs may open local files
+/// 
+/// # Arguments
+/// * `config` - Browser configuration state
+#[tauri::command]
+pub async fn get_allow_local_files(
+    config: State<'_, BrowserConfig>,
+) -> Result<bool, AppError> {
+    Ok(config.allow_local())
+}
+
+/// Allow or forbid opening local files (absolute paths and `file://` URLs) in tabs
+/// 
+/// # Arguments
+/// * `config` - Browser configuration state
+/// * `allow` - Whether local files may be opened
+#[tauri::command]
+pub async fn set_allow_local_files(
+    config: State<'_, BrowserConfig>,
+    allow: bool,
+) -> Result<(), AppError> {
+    debug!("[CEF] set_allow_local_files: allow={}", allow);
+
+    config.set_allow_local(allow);
+    Ok(())
+}
+
+/// Navigate a tab to the configured home page
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `config` - Browser configuration state
+/// * `blocklist` - Navigation blocklist state
+/// * `tab_id` - Browser tab identifier
+#[tauri::command]
+pub async fn cef_go_home(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    config: State<'_, BrowserConfig>,
+    blocklist: State<'_, NavigationBlocklist>,
+    tab_id: String,
+) -> Result<String, AppError> {
+    require_tab_id("cef_go_home", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    let url = config.home_url();
+    reject_if_blocked(&app, &blocklist, &tab_id, &url, manager.now_ms())?;
+
+    debug!("[CEF] cef_go_home: tab_id={} url={}", tab_id, url);
+
+    if !manager.go_home(&tab_id, &url)? {
+        return Err(AppError::NotFound(format!("tab {}", tab_id)));
     }
 
-    // Validate tab_id
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
+    let _ = app.emit("cef:navigation-started", NavigateCefPayload {
+        tab_id: tab_id.clone(),
+        url: url.clone(),
+    });
+    if let Some(payload) = internal_page_payload(&tab_id, &url, manager.now_ms()) {
+        let _ = app.emit("cef:show-internal-page", payload);
     }
 
-    // Validate dimensions
-    if width <= 0.0 || height <= 0.0 {
-        return Err(AppError::InvalidPath("Width and height must be positive".into()));
+    Ok(url)
+}
+
+/// Emit `cef:navigation-blocked` and fail if the URL's host is on the blocklist
+fn reject_if_blocked(
+    app: &AppHandle,
+    blocklist: &NavigationBlocklist,
+    tab_id: &str,
+    url: &str,
+    timestamp: u64,
+) -> Result<(), AppError> {
+    if !blocklist.is_blocked(url) {
+        return Ok(());
     }
 
-    // TODO: Implement actual CEF browser creation
-    // This is a placeholder that will be replaced with actual CEF integration
-    
-    println!(
-        "[CEF] create_cef_browser: tab_id={} url={} pos=({}, {}) size={}x{}",
-        tab_id, url, x, y, width, height
-    );
+    info!("[CEF] navigation blocked: tab_id={} url={}", tab_id, url);
 
-    // Emit event to frontend
-    let _ = app.emit("cef:browser-created", CreateCefBrowserPayload {
-        tab_id: tab_id.clone(),
-        url: url.clone(),
+    let _ = app.emit("cef:navigation-blocked", NavigationBlockedPayload {
+        tab_id: tab_id.to_string(),
+        url: url.to_string(),
+        timestamp,
     });
 
-    Ok(())
+    Err(AppError::Blocked(url.to_string()))
 }
 
 /// Navigate to a URL in a CEF browser
+/// Local files (absolute paths or `file://` URLs) are only opened when the browser config
+/// allows them; a missing local file returns `AppError::NotFound`. Internal pages (`about:*`)
+/// skip the blocklist and are rendered by the frontend via `cef:show-internal-page`
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `config` - Browser configuration state
+/// * `blocklist` - Navigation blocklist state
 /// * `tab_id` - Browser tab identifier
 /// * `url` - URL to navigate to
 #[tauri::command]
 pub async fn navigate_cef(
     app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    config: State<'_, BrowserConfig>,
+    blocklist: State<'_, NavigationBlocklist>,
     tab_id: String,
     url: String,
 ) -> Result<(), AppError> {
     // Validate inputs
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    require_tab_id("navigate_cef", &tab_id)?;
 
-    if url.is_empty() {
-        return Err(AppError::InvalidPath("URL cannot be empty".into()));
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    if let Some(payload) = internal_page_payload(&tab_id, &url, manager.now_ms()) {
+        debug!("[CEF] navigate_cef: tab_id={} internal page={}", tab_id, payload.page);
+        let _ = app.emit("cef:show-internal-page", payload);
+        return Ok(());
     }
 
+    let url = config.resolve_navigation_url(&url)?;
+    reject_if_blocked(&app, &blocklist, &tab_id, &url, manager.now_ms())?;
+
     // TODO: Implement actual CEF navigation
     
-    println!("[CEF] navigate_cef: tab_id={} url={}", tab_id, url);
+    debug!("[CEF] navigate_cef: tab_id={} url={}", tab_id, url);
+
+    manager.mark_load_started(&tab_id)?;
 
     // Emit event to frontend
     let _ = app.emit("cef:navigation-started", NavigateCefPayload {
@@ -93,23 +358,26 @@ pub async fn navigate_cef(
 }
 
 /// Close a CEF browser instance
+/// The tab is kept on the recently-closed stack for `cef_restore_closed_tab`
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
 /// * `tab_id` - Browser tab identifier
 #[tauri::command]
 pub async fn close_cef_browser(
     app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
     tab_id: String,
 ) -> Result<(), AppError> {
     // Validate tab_id
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    require_tab_id("close_cef_browser", &tab_id)?;
 
     // TODO: Implement actual CEF browser closing
     
-    println!("[CEF] close_cef_browser: tab_id={}", tab_id);
+    debug!("[CEF] close_cef_browser: tab_id={}", tab_id);
+
+    manager.unregister_browser(&tab_id)?;
 
     // Emit event to frontend
     let _ = app.emit("cef:browser-closed", CloseCefBrowserPayload {
@@ -120,88 +388,87 @@ pub async fn close_cef_browser(
 }
 
 /// Go back in browser history
+/// Returns the target URL and its saved scroll position so the frontend can restore it
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
 /// * `tab_id` - Browser tab identifier
 #[tauri::command]
 pub async fn cef_go_back(
     app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
     tab_id: String,
-) -> Result<(), AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+) -> Result<Option<NavigationTarget>, AppError> {
+    require_tab_id("cef_go_back", &tab_id)?;
 
-    println!("[CEF] cef_go_back: tab_id={}", tab_id);
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    debug!("[CEF] cef_go_back: tab_id={}", tab_id);
 
     // Emit navigation event
     let _ = app.emit("cef:navigation-back", NavigationEventPayload {
         tab_id: tab_id.clone(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64,
+        timestamp: manager.now_ms(),
     });
 
     // TODO: Implement actual CEF back navigation
     
-    Ok(())
+    manager.on_go_back(&tab_id)
 }
 
 /// Go forward in browser history
+/// Returns the target URL and its saved scroll position so the frontend can restore it
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
 /// * `tab_id` - Browser tab identifier
 #[tauri::command]
 pub async fn cef_go_forward(
     app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
     tab_id: String,
-) -> Result<(), AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+) -> Result<Option<NavigationTarget>, AppError> {
+    require_tab_id("cef_go_forward", &tab_id)?;
 
-    println!("[CEF] cef_go_forward: tab_id={}", tab_id);
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    debug!("[CEF] cef_go_forward: tab_id={}", tab_id);
 
     // Emit navigation event
     let _ = app.emit("cef:navigation-forward", NavigationEventPayload {
         tab_id: tab_id.clone(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64,
+        timestamp: manager.now_ms(),
     });
 
     // TODO: Implement actual CEF forward navigation
     
-    Ok(())
+    manager.on_go_forward(&tab_id)
 }
 
 /// Reload the current page
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
 /// * `tab_id` - Browser tab identifier
 #[tauri::command]
 pub async fn cef_reload(
     app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
     tab_id: String,
 ) -> Result<(), AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    require_tab_id("cef_reload", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
 
-    println!("[CEF] cef_reload: tab_id={}", tab_id);
+    debug!("[CEF] cef_reload: tab_id={}", tab_id);
 
     // Emit reload event
     let _ = app.emit("cef:page-reload", PageReloadEventPayload {
         tab_id: tab_id.clone(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64,
+        timestamp: manager.now_ms(),
     });
 
     // TODO: Implement actual CEF reload
@@ -213,25 +480,24 @@ pub async fn cef_reload(
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
 /// * `tab_id` - Browser tab identifier
 #[tauri::command]
 pub async fn cef_stop(
     app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
     tab_id: String,
 ) -> Result<(), AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    require_tab_id("cef_stop", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
 
-    println!("[CEF] cef_stop: tab_id={}", tab_id);
+    debug!("[CEF] cef_stop: tab_id={}", tab_id);
 
     // Emit stop event
     let _ = app.emit("cef:page-stop", PageStopEventPayload {
         tab_id: tab_id.clone(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64,
+        timestamp: manager.now_ms(),
     });
 
     // TODO: Implement actual CEF stop
@@ -239,176 +505,649 @@ pub async fn cef_stop(
     Ok(())
 }
 
+/// How long `cef_execute_js` waits for the webview to report a result
+pub const JS_RESULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// JavaScript results reported by the webview as JSON, keyed by request id
+pub type JsResults = PendingResults<String>;
+
 /// Execute JavaScript in a CEF browser
+/// The webview runs the script on `cef:execute-js` and reports the result via `cef_report_js_result`
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `js_results` - Pending JavaScript results
 /// * `tab_id` - Browser tab identifier
 /// * `script` - JavaScript code to execute
 #[tauri::command]
-pub async fn cef_execute_js(
-    _app: AppHandle,
+pub async fn cef_execute_js<R: Runtime>(
+    app: AppHandle<R>,
+    manager: State<'_, CefBrowserManager>,
+    js_results: State<'_, JsResults>,
     tab_id: String,
     script: String,
 ) -> Result<String, AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    require_tab_id("cef_execute_js", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
 
     if script.is_empty() {
+        warn!("[CEF] cef_execute_js rejected: script cannot be empty");
         return Err(AppError::InvalidPath("script cannot be empty".into()));
     }
 
-    println!("[CEF] cef_execute_js: tab_id={} script_len={}", tab_id, script.len());
+    debug!("[CEF] cef_execute_js: tab_id={} script_len={}", tab_id, script.len());
 
-    // TODO: Implement actual CEF JS execution
-    
-    Ok("null".to_string())
+    run_js(&app, &manager, &js_results, &tab_id, script).await
 }
 
-/// Get page content from a CEF browser
+/// Ask the webview to run a script in a tab and wait for `cef_report_js_result`
+async fn run_js<R: Runtime>(
+    app: &AppHandle<R>,
+    manager: &CefBrowserManager,
+    js_results: &JsResults,
+    tab_id: &str,
+    script: String,
+) -> Result<String, AppError> {
+    let request_id = uuid::Uuid::new_v4().to_string();
+
+    let _ = app.emit("cef:execute-js", ExecuteJsPayload {
+        tab_id: tab_id.to_string(),
+        request_id: request_id.clone(),
+        script,
+        timestamp: manager.now_ms(),
+    });
+
+    js_results.wait_for(&request_id, JS_RESULT_TIMEOUT).await?
+        .ok_or_else(|| AppError::Timeout(format!("JavaScript execution in tab: {}", tab_id)))
+}
+
+/// Report a JavaScript execution result back from the webview
 /// 
 /// # Arguments
-/// * `app` - Tauri app handle
-/// * `tab_id` - Browser tab identifier
+/// * `js_results` - Pending JavaScript results
+/// * `request_id` - Request id from the `cef:execute-js` event
+/// * `result_json` - Script result serialized as JSON
 #[tauri::command]
-pub async fn cef_get_page_content(
-    _app: AppHandle,
-    tab_id: String,
-) -> Result<PageContent, AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
+pub async fn cef_report_js_result(
+    js_results: State<'_, JsResults>,
+    request_id: String,
+    result_json: String,
+) -> Result<(), AppError> {
+    if request_id.is_empty() {
+        warn!("[CEF] cef_report_js_result rejected: request_id cannot be empty");
+        return Err(AppError::InvalidPath("request_id cannot be empty".into()));
     }
 
-    println!("[CEF] cef_get_page_content: tab_id={}", tab_id);
+    debug!("[CEF] cef_report_js_result: request_id={} result_len={}", request_id, result_json.len());
 
-    // TODO: Implement actual page content extraction
-    
-    Ok(PageContent {
-        url: String::new(),
-        title: String::new(),
-        content: String::new(),
-        description: String::new(),
-        favicon: None,
-    })
+    js_results.put(request_id, result_json)
 }
 
-/// Get selected text from a CEF browser
+/// Get page content from a CEF browser
+/// In reader mode the content is reduced to clean article text with a word count
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `js_results` - Pending JavaScript results
 /// * `tab_id` - Browser tab identifier
+/// * `reader_mode` - Strip nav/ads and return readable text (default false)
 #[tauri::command]
-pub async fn cef_get_selection(
-    _app: AppHandle,
+pub async fn cef_get_page_content(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    js_results: State<'_, JsResults>,
     tab_id: String,
-) -> Result<String, AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    reader_mode: Option<bool>,
+) -> Result<PageContent, AppError> {
+    require_tab_id("cef_get_page_content", &tab_id)?;
 
-    println!("[CEF] cef_get_selection: tab_id={}", tab_id);
+    ensure_tab_exists(&manager, &tab_id)?;
 
-    // TODO: Implement actual selection retrieval
-    
-    Ok(String::new())
+    debug!("[CEF] cef_get_page_content: tab_id={} reader_mode={:?}", tab_id, reader_mode);
+
+    let page = fetch_page_content(&app, &manager, &js_results, &tab_id).await?;
+
+    Ok(if reader_mode.unwrap_or(false) { page.into_reader_mode() } else { page })
 }
 
-/// Notify about URL change in a CEF browser
-/// This is called by the CEF browser when the URL changes
+/// Expression the webview evaluates to report a page as `PageContent`
+const PAGE_CONTENT_SCRIPT: &str = r#"({
+    url: location.href,
+    title: document.title,
+    content: document.documentElement.outerHTML,
+    description: document.querySelector('meta[name="description"]')?.content ?? "",
+    favicon: document.querySelector('link[rel~="icon"]')?.href ?? null
+})"#;
+
+/// Raw page content (HTML in `content`) of a tab, read from the webview
+async fn fetch_page_content<R: Runtime>(
+    app: &AppHandle<R>,
+    manager: &CefBrowserManager,
+    js_results: &JsResults,
+    tab_id: &str,
+) -> Result<PageContent, AppError> {
+    let json = run_js(app, manager, js_results, tab_id, PAGE_CONTENT_SCRIPT.to_string()).await?;
+    parse_page_content(&json)
+}
+
+fn parse_page_content(json: &str) -> Result<PageContent, AppError> {
+    serde_json::from_str(json)
+        .map_err(|e| AppError::Parse(format!("Invalid page content: {}", e)))
+}
+
+/// How a clipped page is written into a note
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipFormat {
+    Markdown,
+    PlainText,
+}
+
+/// Render a page as note content: a header with the source URL and clip time, then the readable text
+/// `page` must already be in reader mode
+fn format_clip(page: PageContent, format: ClipFormat, clipped_at: chrono::DateTime<chrono::Local>) -> String {
+    let title = if page.title.is_empty() { page.url.clone() } else { page.title.clone() };
+    let clipped_at = clipped_at.format("%Y-%m-%d %H:%M");
+
+    match format {
+        ClipFormat::Markdown => format!(
+            "# {}\n\n> Source: <{}>\n> Clipped: {}\n\n{}\n",
+            title, page.url, clipped_at, page.content
+        ),
+        ClipFormat::PlainText => format!(
+            "{}\nSource: {}\nClipped: {}\n\n{}\n",
+            title, page.url, clipped_at, page.content
+        ),
+    }
+}
+
+/// Write a clipped page to a note, creating parent directories as needed
+/// A page without readable text is `AppError::NotFound` and writes nothing
+fn write_clip(page: PageContent, note_path: &str, format: ClipFormat) -> Result<(), AppError> {
+    let page = page.into_reader_mode();
+    if page.content.is_empty() {
+        return Err(AppError::NotFound(format!("readable content in {}", page.url)));
+    }
+    let content = format_clip(page, format, chrono::Local::now());
+    crate::fs::write_file_content(note_path, &content)
+}
+
+/// Clip the current page of a tab into a note
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `guard` - Workspace guard state
+/// * `manager` - Shared browser manager state
+/// * `js_results` - Pending JavaScript results
 /// * `tab_id` - Browser tab identifier
-/// * `url` - New URL
+/// * `note_path` - Note to write; overwritten if it exists
+/// * `format` - Markdown or plain text
 #[tauri::command]
-pub async fn cef_on_url_change(
+pub async fn cef_clip_page_to_note(
     app: AppHandle,
+    guard: State<'_, crate::fs::WorkspaceGuard>,
+    manager: State<'_, CefBrowserManager>,
+    js_results: State<'_, JsResults>,
     tab_id: String,
-    url: String,
+    note_path: String,
+    format: ClipFormat,
 ) -> Result<(), AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    require_tab_id("cef_clip_page_to_note", &tab_id)?;
 
-    if url.is_empty() {
-        return Err(AppError::InvalidPath("url cannot be empty".into()));
-    }
+    ensure_tab_exists(&manager, &tab_id)?;
 
-    println!("[CEF] cef_on_url_change: tab_id={} url={}", tab_id, url);
+    let note_path = guard.confine(&note_path)?;
 
-    // Emit URL change event
-    let _ = app.emit("cef:url-changed", UrlChangeEventPayload {
-        tab_id: tab_id.clone(),
-        url: url.clone(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64,
-    });
+    debug!("[CEF] cef_clip_page_to_note: tab_id={} note_path={} format={:?}", tab_id, note_path, format);
 
-    Ok(())
+    let page = fetch_page_content(&app, &manager, &js_results, &tab_id).await?;
+    write_clip(page, &note_path, format)
 }
 
-/// Notify about title change in a CEF browser
-/// This is called by the CEF browser when the page title changes
+/// Longest note file name (in characters) derived from a page title
+const MAX_NOTE_NAME_CHARS: usize = 100;
+
+/// Turn a page title into a file name that is valid on every platform
+/// Characters illegal on Windows or Unix become spaces, runs of whitespace collapse,
+/// and trailing dots/spaces (rejected by Windows) are trimmed
+fn sanitize_note_name(title: &str) -> String {
+    let replaced: String = title.chars()
+        .map(|c| if c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') { ' ' } else { c })
+        .collect();
+    let collapsed = replaced.split_whitespace().collect::<Vec<_>>().join(" ");
+    let truncated: String = collapsed.chars().take(MAX_NOTE_NAME_CHARS).collect();
+    let name = truncated.trim_end_matches(['.', ' ']).trim_start_matches('.');
+    if name.is_empty() { "Untitled".to_string() } else { name.to_string() }
+}
+
+/// Create a dated note under `notes_root` linking to a page
+/// The file is named `<date> <title>.md`; an existing note is never overwritten,
+/// a counter is appended instead. Returns the created path.
+fn write_bookmark_note(notes_root: &str, url: &str, title: &str, date: chrono::NaiveDate) -> Result<String, AppError> {
+    use std::io::Write;
+
+    let root = std::path::Path::new(notes_root);
+    std::fs::create_dir_all(root)?;
+
+    let title = if title.trim().is_empty() { url } else { title.trim() };
+    let stem = format!("{} {}", date.format("%Y-%m-%d"), sanitize_note_name(title));
+    let link_text = title.replace('[', "\\[").replace(']', "\\]");
+    let content = format!("# {}\n\n[{}](<{}>)\n", title, link_text, url);
+
+    for counter in 1.. {
+        let name = if counter == 1 { format!("{}.md", stem) } else { format!("{} ({}).md", stem, counter) };
+        let path = root.join(name);
+        // create_new makes the collision check and the creation a single step
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                file.write_all(content.as_bytes())?;
+                return Ok(path.to_string_lossy().into_owned());
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    unreachable!("note name counter exhausted")
+}
+
+/// Save the current page of a tab as a new dated note containing a link to it
 /// 
 /// # Arguments
-/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `guard` - Workspace guard state
 /// * `tab_id` - Browser tab identifier
-/// * `title` - New page title
+/// * `notes_root` - Folder to create the note in
 #[tauri::command]
-pub async fn cef_on_title_change(
-    app: AppHandle,
+pub async fn cef_bookmark_to_note(
+    manager: State<'_, CefBrowserManager>,
+    guard: State<'_, crate::fs::WorkspaceGuard>,
     tab_id: String,
-    title: String,
-) -> Result<(), AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    notes_root: String,
+) -> Result<String, AppError> {
+    require_tab_id("cef_bookmark_to_note", &tab_id)?;
 
-    println!("[CEF] cef_on_title_change: tab_id={} title={}", tab_id, title);
+    let notes_root = guard.confine(&notes_root)?;
+    let browser = manager.get_browser(&tab_id)?
+        .ok_or_else(|| AppError::NotFound(format!("tab {}", tab_id)))?;
 
-    // Emit title change event
-    let _ = app.emit("cef:title-changed", TitleChangeEventPayload {
-        tab_id: tab_id.clone(),
+    debug!("[CEF] cef_bookmark_to_note: tab_id={} notes_root={}", tab_id, notes_root);
+
+    write_bookmark_note(&notes_root, &browser.url, &browser.title, chrono::Local::now().date_naive())
+}
+
+/// How long selection requests wait for the webview to report back
+pub const SELECTION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Selected text reported by the webview, keyed by tab id
+pub type SelectionResults = PendingResults<String>;
+
+/// Ask the webview for a tab's selected text and wait for `cef_report_selection`
+async fn request_selection(app: &AppHandle, selections: &SelectionResults, tab_id: &str, timestamp: u64) -> Result<String, AppError> {
+    // Drop any stale result from an earlier, timed-out request
+    selections.take(tab_id)?;
+
+    let _ = app.emit("cef:selection-request", SelectionRequestPayload {
+        tab_id: tab_id.to_string(),
+        timestamp,
+    });
+
+    selections.wait_for(tab_id, SELECTION_TIMEOUT).await?
+        .ok_or_else(|| AppError::Timeout(format!("Selection of tab: {}", tab_id)))
+}
+
+/// Get selected text from a CEF browser
+/// The webview answers `cef:selection-request` via `cef_report_selection`
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `selections` - Pending selection results
+/// * `tab_id` - Browser tab identifier
+#[tauri::command]
+pub async fn cef_get_selection(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    selections: State<'_, SelectionResults>,
+    tab_id: String,
+) -> Result<String, AppError> {
+    require_tab_id("cef_get_selection", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    debug!("[CEF] cef_get_selection: tab_id={}", tab_id);
+
+    request_selection(&app, &selections, &tab_id, manager.now_ms()).await
+}
+
+/// Report a tab's selected text back from the webview
+/// 
+/// # Arguments
+/// * `selections` - Pending selection results
+/// * `tab_id` - Browser tab identifier
+/// * `text` - Selected text (empty if nothing is selected)
+#[tauri::command]
+pub async fn cef_report_selection(
+    selections: State<'_, SelectionResults>,
+    tab_id: String,
+    text: String,
+) -> Result<(), AppError> {
+    require_tab_id("cef_report_selection", &tab_id)?;
+
+    debug!("[CEF] cef_report_selection: tab_id={} len={}", tab_id, text.len());
+
+    selections.put(tab_id, text)
+}
+
+/// Format a selection as a Markdown blockquote followed by a source line
+fn format_selection_quote(selection: &str, url: &str, title: &str) -> String {
+    let mut quote: String = selection.trim_end().lines()
+        .map(|line| if line.trim().is_empty() { ">\n".to_string() } else { format!("> {}\n", line) })
+        .collect();
+    let source = if title.trim().is_empty() {
+        format!("<{}>", url)
+    } else {
+        format!("[{}](<{}>)", title.trim(), url)
+    };
+    quote.push_str(&format!(">\n> Source: {}\n", source));
+    quote
+}
+
+/// Append a selection quote to a note, separated from existing content by a blank line
+/// Returns false (and leaves the note untouched) when the selection is blank
+fn append_selection(note_path: &str, selection: &str, url: &str, title: &str) -> Result<bool, AppError> {
+    if selection.trim().is_empty() {
+        return Ok(false);
+    }
+
+    let has_content = std::fs::metadata(note_path).map(|m| m.len() > 0).unwrap_or(false);
+    let quote = format_selection_quote(selection, url, title);
+    let content = if has_content { format!("\n{}", quote) } else { quote };
+    crate::fs::append_to_file(note_path, &content)?;
+    Ok(true)
+}
+
+/// Append the selected text of a tab to a note as a blockquote with a source line
+/// Returns false if nothing is selected (the note is left untouched)
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `selections` - Pending selection results
+/// * `guard` - Workspace guard state
+/// * `tab_id` - Browser tab identifier
+/// * `note_path` - Note to append to; created if missing
+#[tauri::command]
+pub async fn cef_selection_to_note(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    selections: State<'_, SelectionResults>,
+    guard: State<'_, crate::fs::WorkspaceGuard>,
+    tab_id: String,
+    note_path: String,
+) -> Result<bool, AppError> {
+    require_tab_id("cef_selection_to_note", &tab_id)?;
+
+    let note_path = guard.confine(&note_path)?;
+    let browser = manager.get_browser(&tab_id)?
+        .ok_or_else(|| AppError::NotFound(format!("tab {}", tab_id)))?;
+
+    debug!("[CEF] cef_selection_to_note: tab_id={} note_path={}", tab_id, note_path);
+
+    let selection = request_selection(&app, &selections, &tab_id, manager.now_ms()).await?;
+    append_selection(&note_path, &selection, &browser.url, &browser.title)
+}
+
+/// Find text in the current page of a tab
+/// An empty query repeats the last search (find next) with the same settings;
+/// the webview highlights matches on `cef:find-in-page` and reports counts via `cef_on_find_result`
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `query` - Text to find; empty repeats the last search
+/// * `match_case` - Case-sensitive search; None keeps the tab's last preference
+#[tauri::command]
+pub async fn cef_find_in_page(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    query: String,
+    match_case: Option<bool>,
+) -> Result<FindState, AppError> {
+    require_tab_id("cef_find_in_page", &tab_id)?;
+
+    let state = manager.begin_find(&tab_id, &query, match_case)?
+        .ok_or_else(|| AppError::NotFound(format!("tab {}", tab_id)))?;
+
+    debug!("[CEF] cef_find_in_page: tab_id={} query={} match_case={}", tab_id, state.query, state.match_case);
+
+    let _ = app.emit("cef:find-in-page", FindInPagePayload {
+        tab_id,
+        query: state.query.clone(),
+        match_case: state.match_case,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(state)
+}
+
+/// Report find-in-page match counts back from the webview
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `active_match` - 1-based index of the highlighted match (0 if none)
+/// * `total_matches` - Number of matches in the page
+#[tauri::command]
+pub async fn cef_on_find_result(
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    active_match: usize,
+    total_matches: usize,
+) -> Result<Option<FindState>, AppError> {
+    require_tab_id("cef_on_find_result", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    manager.on_find_result(&tab_id, active_match, total_matches)
+}
+
+/// Notify about URL change in a CEF browser
+/// This is called by the CEF browser when the URL changes
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `config` - Browser configuration state
+/// * `blocklist` - Navigation blocklist state
+/// * `tab_id` - Browser tab identifier
+/// * `url` - New URL
+#[tauri::command]
+pub async fn cef_on_url_change(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    config: State<'_, BrowserConfig>,
+    blocklist: State<'_, NavigationBlocklist>,
+    tab_id: String,
+    url: String,
+) -> Result<(), AppError> {
+    require_tab_id("cef_on_url_change", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    let url = config.resolve_navigation_url(&url)?;
+    reject_if_blocked(&app, &blocklist, &tab_id, &url, manager.now_ms())?;
+
+    debug!("[CEF] cef_on_url_change: tab_id={} url={}", tab_id, url);
+
+    manager.on_url_change(&tab_id, url.clone())?;
+
+    // Emit URL change event
+    let _ = app.emit("cef:url-changed", UrlChangeEventPayload {
+        tab_id: tab_id.clone(),
+        url: url.clone(),
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// Notify about title change in a CEF browser
+/// This is called by the CEF browser when the page title changes
+/// 
+/// Rapid changes while the page loads are coalesced; the final title is emitted when loading finishes
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `title` - New page title
+#[tauri::command]
+pub async fn cef_on_title_change(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    title: String,
+) -> Result<(), AppError> {
+    require_tab_id("cef_on_title_change", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    if !manager.on_title_change(&tab_id, title.clone())? {
+        return Ok(());
+    }
+
+    debug!("[CEF] cef_on_title_change: tab_id={} title={}", tab_id, title);
+
+    // Emit title change event
+    let _ = app.emit("cef:title-changed", TitleChangeEventPayload {
+        tab_id: tab_id.clone(),
         title: title.clone(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// Notify about load progress in a CEF browser
+/// This is called by the CEF browser while a page loads
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `progress` - Load progress from 0.0 to 1.0 (clamped)
+#[tauri::command]
+pub async fn cef_on_load_progress(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    progress: f64,
+) -> Result<(), AppError> {
+    require_tab_id("cef_on_load_progress", &tab_id)?;
+
+    let progress = manager.on_load_progress(&tab_id, progress)?
+        .ok_or_else(|| AppError::NotFound(format!("tab {}", tab_id)))?;
+
+    // Emit load progress event
+    let _ = app.emit("cef:load-progress", LoadProgressPayload {
+        tab_id: tab_id.clone(),
+        progress,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// Notify about favicon change in a CEF browser
+/// This is called by the CEF browser when the page reports its icon
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `favicon_url` - Icon as a data URL or icon URL
+#[tauri::command]
+pub async fn cef_on_favicon_change(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    favicon_url: String,
+) -> Result<(), AppError> {
+    require_tab_id("cef_on_favicon_change", &tab_id)?;
+
+    if favicon_url.is_empty() {
+        warn!("[CEF] cef_on_favicon_change rejected: favicon_url cannot be empty");
+        return Err(AppError::InvalidPath("favicon_url cannot be empty".into()));
+    }
+
+    debug!("[CEF] cef_on_favicon_change: tab_id={}", tab_id);
+
+    if !manager.on_favicon_change(&tab_id, favicon_url.clone())? {
+        return Err(AppError::NotFound(format!("tab {}", tab_id)));
+    }
+
+    // Emit favicon change event
+    let _ = app.emit("cef:favicon-changed", FaviconChangeEventPayload {
+        tab_id: tab_id.clone(),
+        favicon: favicon_url,
+        timestamp: manager.now_ms(),
     });
 
     Ok(())
 }
 
+/// Clear the per-host favicon cache
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+#[tauri::command]
+pub async fn cef_clear_favicon_cache(
+    manager: State<'_, CefBrowserManager>,
+) -> Result<(), AppError> {
+    debug!("[CEF] cef_clear_favicon_cache");
+
+    manager.clear_favicon_cache();
+    Ok(())
+}
+
 /// Notify about loading state change in a CEF browser
 /// This is called by the CEF browser when loading starts or stops
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
 /// * `tab_id` - Browser tab identifier
 /// * `is_loading` - Whether the page is currently loading
 #[tauri::command]
 pub async fn cef_on_loading_state_change(
     app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
     tab_id: String,
     is_loading: bool,
 ) -> Result<(), AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    require_tab_id("cef_on_loading_state_change", &tab_id)?;
 
-    println!("[CEF] cef_on_loading_state_change: tab_id={} is_loading={}", tab_id, is_loading);
+    debug!("[CEF] cef_on_loading_state_change: tab_id={} is_loading={}", tab_id, is_loading);
+
+    // A title held back during the load is only published now
+    if let Some(title) = manager.on_loading_state_change(&tab_id, is_loading)? {
+        let _ = app.emit("cef:title-changed", TitleChangeEventPayload {
+            tab_id: tab_id.clone(),
+            title,
+            timestamp: manager.now_ms(),
+        });
+    }
 
     // Emit loading state change event
     let _ = app.emit("cef:loading-state-changed", LoadingStateChangeEventPayload {
         tab_id: tab_id.clone(),
         is_loading,
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64,
+        timestamp: manager.now_ms(),
     });
 
     Ok(())
@@ -418,34 +1157,129 @@ pub async fn cef_on_loading_state_change(
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
 /// * `tab_id` - Browser tab identifier to switch to
 #[tauri::command]
 pub async fn cef_switch_tab(
     app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
     tab_id: String,
 ) -> Result<(), AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    require_tab_id("cef_switch_tab", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
 
-    println!("[CEF] cef_switch_tab: tab_id={}", tab_id);
+    debug!("[CEF] cef_switch_tab: tab_id={}", tab_id);
 
     // Emit tab switch event
     let _ = app.emit("cef:tab-switched", TabSwitchEventPayload {
         tab_id: tab_id.clone(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// Move a tab to new bounds and switch to it in one step
+/// Emits a single `cef:tab-activated` event instead of a bounds update followed by a tab switch
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `pool` - Shared instance pool state
+/// * `tab_id` - Browser tab identifier to activate
+/// * `x` - X position in logical pixels
+/// * `y` - Y position in logical pixels
+/// * `width` - Width in logical pixels
+/// * `height` - Height in logical pixels
+#[tauri::command]
+pub async fn cef_activate_tab(
+    app: AppHandle,
+    pool: State<'_, CefInstancePool>,
+    tab_id: String,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) -> Result<(), AppError> {
+    require_tab_id("cef_activate_tab", &tab_id)?;
+
+    debug!("[CEF] cef_activate_tab: tab_id={} pos=({}, {}) size={}x{}", tab_id, x, y, width, height);
+
+    if !pool.activate_instance(&tab_id, x, y, width, height)? {
+        return Err(AppError::NotFound(format!("tab {}", tab_id)));
+    }
+
+    let _ = app.emit("cef:tab-activated", TabActivatedEventPayload {
+        tab_id,
+        x,
+        y,
+        width,
+        height,
+        timestamp: pool.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// Give a tab a new id, keeping its history and geometry
+/// Emits `cef:tab-renamed` so the frontend can rekey its own state
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `pool` - Shared instance pool state
+/// * `old_id` - Current tab identifier
+/// * `new_id` - New tab identifier
+#[tauri::command]
+pub async fn cef_rename_tab(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    pool: State<'_, CefInstancePool>,
+    old_id: String,
+    new_id: String,
+) -> Result<(), AppError> {
+    require_tab_id("cef_rename_tab", &old_id)?;
+    require_tab_id("cef_rename_tab", &new_id)?;
+
+    debug!("[CEF] cef_rename_tab: old_id={} new_id={}", old_id, new_id);
+
+    TabRegistry::new(&manager, &pool).rename_tab(&old_id, &new_id)?;
+
+    let _ = app.emit("cef:tab-renamed", TabRenamedEventPayload {
+        old_id,
+        new_id,
+        timestamp: manager.now_ms(),
     });
 
     Ok(())
 }
 
+/// Emit `cef:bounds-updated` for the latest pending bounds of every tab
+fn flush_pending_bounds(app: &AppHandle, debouncer: &BoundsDebouncer, pool: &CefInstancePool) -> Result<usize, AppError> {
+    let flushed = debouncer.drain()?;
+    let count = flushed.len();
+    let timestamp = pool.now_ms();
+    for (tab_id, bounds) in flushed {
+        let _ = app.emit("cef:bounds-updated", BoundsUpdateEventPayload {
+            tab_id,
+            x: bounds.x,
+            y: bounds.y,
+            width: bounds.width,
+            height: bounds.height,
+            timestamp,
+        });
+    }
+    Ok(count)
+}
+
 /// Update CEF browser instance bounds
+/// Updates are coalesced: `cef:bounds-updated` is emitted at most once per frame
+/// with the latest bounds of each tab. Bounds equal to a pooled instance's current ones emit nothing
 /// 
 /// # Arguments
 /// * `app` - Tauri app handle
+/// * `pool` - Shared instance pool state
+/// * `debouncer` - Pending bounds state
 /// * `tab_id` - Browser tab identifier
 /// * `x` - X position in logical pixels
 /// * `y` - Y position in logical pixels
@@ -454,40 +1288,1289 @@ pub async fn cef_switch_tab(
 #[tauri::command]
 pub async fn cef_update_bounds(
     app: AppHandle,
+    pool: State<'_, CefInstancePool>,
+    debouncer: State<'_, BoundsDebouncer>,
     tab_id: String,
     x: f64,
     y: f64,
     width: f64,
     height: f64,
 ) -> Result<(), AppError> {
-    if tab_id.is_empty() {
-        return Err(AppError::InvalidPath("tab_id cannot be empty".into()));
-    }
+    require_tab_id("cef_update_bounds", &tab_id)?;
 
     if width <= 0.0 || height <= 0.0 {
+        warn!("[CEF] cef_update_bounds rejected: width and height must be positive");
         return Err(AppError::InvalidPath("Width and height must be positive".into()));
     }
 
-    println!("[CEF] cef_update_bounds: tab_id={} pos=({}, {}) size={}x{}", tab_id, x, y, width, height);
+    debug!("[CEF] cef_update_bounds: tab_id={} pos=({}, {}) size={}x{}", tab_id, x, y, width, height);
 
-    // Emit bounds update event
-    let _ = app.emit("cef:bounds-updated", BoundsUpdateEventPayload {
-        tab_id: tab_id.clone(),
-        x,
-        y,
-        width,
-        height,
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64,
-    });
+    // Tabs without a pooled instance have nothing to compare against and are always emitted
+    if !pool.update_instance_bounds(&tab_id, x, y, width, height)? && pool.contains(&tab_id)? {
+        debug!("[CEF] cef_update_bounds: tab_id={} unchanged", tab_id);
+        return Ok(());
+    }
+
+    if debouncer.push(tab_id, BrowserBounds { x, y, width, height })? {
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(BOUNDS_FLUSH_INTERVAL).await;
+            let _ = flush_pending_bounds(&app, &app.state::<BoundsDebouncer>(), &app.state::<CefInstancePool>());
+        });
+    }
+
+    Ok(())
+}
+
+/// Emit pending bounds updates immediately (e.g. when a resize ends)
+/// Returns the number of tabs whose bounds were emitted
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `pool` - Shared instance pool state
+/// * `debouncer` - Pending bounds state
+#[tauri::command]
+pub async fn cef_flush_bounds(
+    app: AppHandle,
+    pool: State<'_, CefInstancePool>,
+    debouncer: State<'_, BoundsDebouncer>,
+) -> Result<usize, AppError> {
+    debug!("[CEF] cef_flush_bounds");
+
+    flush_pending_bounds(&app, &debouncer, &pool)
+}
+
+/// Show or hide one CEF instance without affecting the others (e.g. split views)
+/// 
+/// # Arguments
+/// * `pool` - Shared instance pool state
+/// * `tab_id` - Browser tab identifier
+/// * `visible` - Whether the instance should be visible
+#[tauri::command]
+pub async fn cef_set_instance_visible(
+    pool: State<'_, CefInstancePool>,
+    tab_id: String,
+    visible: bool,
+) -> Result<(), AppError> {
+    require_tab_id("cef_set_instance_visible", &tab_id)?;
+
+    debug!("[CEF] cef_set_instance_visible: tab_id={} visible={}", tab_id, visible);
+
+    if !pool.set_visible(&tab_id, visible)? {
+        return Err(AppError::NotFound(format!("tab {}", tab_id)));
+    }
+    Ok(())
+}
+
+/// Find the topmost visible CEF instance containing a point (e.g. for drag targets)
+/// 
+/// # Arguments
+/// * `pool` - Shared instance pool state
+/// * `x` - X coordinate
+/// * `y` - Y coordinate
+#[tauri::command]
+pub async fn cef_instance_at_point(
+    pool: State<'_, CefInstancePool>,
+    x: f64,
+    y: f64,
+) -> Result<Option<String>, AppError> {
+    pool.instance_at_point(x, y)
+}
+
+/// Update the bounds of many CEF browser instances at once (e.g. on window resize)
+/// Emits a single `cef:bounds-updated-batch` event for the tabs whose bounds changed; entries
+/// for unknown tabs or with non-finite values or non-positive width/height are skipped and
+/// their tab ids returned
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `pool` - Shared instance pool state
+/// * `updates` - New bounds per tab
+#[tauri::command]
+pub async fn cef_update_bounds_batch(
+    app: AppHandle,
+    pool: State<'_, CefInstancePool>,
+    updates: Vec<BoundsUpdate>,
+) -> Result<Vec<String>, AppError> {
+    debug!("[CEF] cef_update_bounds_batch: {} updates", updates.len());
+
+    let outcome = pool.update_many_bounds(
+        updates.iter()
+            .map(|u| (u.tab_id.clone(), u.x, u.y, u.width, u.height))
+            .collect(),
+    )?;
+
+    let applied: Vec<BoundsUpdate> = updates.into_iter()
+        .filter(|u| outcome.changed.contains(&u.tab_id))
+        .collect();
+
+    // Emit one batch event instead of one event per tab
+    if !applied.is_empty() {
+        let _ = app.emit("cef:bounds-updated-batch", BoundsBatchEventPayload {
+            updates: applied,
+            timestamp: pool.now_ms(),
+        });
+    }
+
+    Ok(outcome.failed)
+}
+
+/// Save all browser tabs (with navigation history) to the session file
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+#[tauri::command]
+pub async fn cef_save_session(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+) -> Result<(), AppError> {
+    let path = session_file_path(&app)?;
+
+    debug!("[CEF] cef_save_session: path={}", path.display());
+
+    manager.save_session(&path)
+}
+
+/// Restore browser tabs from the session file
+/// Returns an empty list if no (valid) session was saved
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+#[tauri::command]
+pub async fn cef_restore_session(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+) -> Result<Vec<CefBrowserInfo>, AppError> {
+    let path = session_file_path(&app)?;
+
+    debug!("[CEF] cef_restore_session: path={}", path.display());
+
+    let browsers = manager.load_session(&path)?;
+
+    // Reapply saved zoom levels
+    for browser in &browsers {
+        let _ = app.emit("cef:zoom-changed", ZoomChangeEventPayload {
+            tab_id: browser.tab_id.clone(),
+            zoom_level: browser.zoom_level,
+            timestamp: manager.now_ms(),
+        });
+    }
+
+    Ok(browsers)
+}
+
+/// Export all tabs (with navigation history) and bookmarks as a portable JSON bundle
+/// Private tabs are left out
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `store` - Shared bookmark store state
+#[tauri::command]
+pub async fn cef_export_session(
+    manager: State<'_, CefBrowserManager>,
+    store: State<'_, BookmarkStore>,
+) -> Result<String, AppError> {
+    debug!("[CEF] cef_export_session");
+
+    session::export_session(&manager, &store)
+}
+
+/// Import a session bundle produced by `cef_export_session`
+/// Returns the imported tabs so the frontend can open them
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `store` - Shared bookmark store state
+/// * `json` - Session bundle JSON
+/// * `merge` - Merge into the current tabs and bookmarks instead of replacing them
+#[tauri::command]
+pub async fn cef_import_session(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    store: State<'_, BookmarkStore>,
+    json: String,
+    merge: bool,
+) -> Result<Vec<CefBrowserInfo>, AppError> {
+    debug!("[CEF] cef_import_session: len={} merge={}", json.len(), merge);
+
+    let tabs = session::import_session(&manager, &store, &json, merge)?;
+    store.save(&bookmarks_file_path(&app)?)?;
+
+    Ok(tabs)
+}
+
+/// Get the most visited sites as (host, visit count), most visited first
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `n` - Maximum number of hosts (default 10)
+#[tauri::command]
+pub async fn cef_top_sites(
+    manager: State<'_, CefBrowserManager>,
+    n: Option<usize>,
+) -> Result<Vec<(String, u64)>, AppError> {
+    manager.top_hosts(n.unwrap_or(10))
+}
+
+/// Get URL suggestions ranked by frecency as (url, visit count, last visit ms), best first
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `n` - Maximum number of URLs (default 10)
+#[tauri::command]
+pub async fn cef_frecent_urls(
+    manager: State<'_, CefBrowserManager>,
+    n: Option<usize>,
+) -> Result<Vec<(String, u64, u64)>, AppError> {
+    manager.frecent_urls(n.unwrap_or(10))
+}
+
+/// Clear the navigation history of every tab, keeping each tab's current page
+/// Returns the number of history entries removed
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+#[tauri::command]
+pub async fn cef_clear_history(
+    manager: State<'_, CefBrowserManager>,
+) -> Result<usize, AppError> {
+    let removed = manager.clear_all_history()?;
+
+    debug!("[CEF] cef_clear_history: removed {} entries", removed);
+
+    Ok(removed)
+}
+
+/// Get the full navigation history of a tab
+/// A tab that hasn't navigated yet returns just its initial URL (see `CefBrowserManager::get_history`)
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+#[tauri::command]
+pub async fn cef_get_history(
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+) -> Result<HistoryView, AppError> {
+    require_tab_id("cef_get_history", &tab_id)?;
+
+    debug!("[CEF] cef_get_history: tab_id={}", tab_id);
+
+    manager.get_history(&tab_id)?
+        .ok_or_else(|| AppError::NotFound(format!("tab {}", tab_id)))
+}
+
+/// Search navigation history across all tabs (omnibox suggestions)
+/// Matches URL and title case-insensitively; most recent first, one hit per URL
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `query` - Text to search for
+/// * `limit` - Maximum number of hits (default 20)
+#[tauri::command]
+pub async fn cef_search_history(
+    manager: State<'_, CefBrowserManager>,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<HistorySearchHit>, AppError> {
+    manager.search_history(&query, limit.unwrap_or(20))
+}
+
+/// Jump directly to an entry in a tab's navigation history
+/// Returns the target URL, or None if the index is out of range
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `index` - Target history index
+#[tauri::command]
+pub async fn cef_go_to_history_index(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    index: usize,
+) -> Result<Option<String>, AppError> {
+    require_tab_id("cef_go_to_history_index", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    debug!("[CEF] cef_go_to_history_index: tab_id={} index={}", tab_id, index);
+
+    let url = manager.go_to_history_index(&tab_id, index)?;
+
+    if let Some(url) = &url {
+        // Emit navigation event so the webview loads the target entry
+        let _ = app.emit("cef:navigation-started", NavigateCefPayload {
+            tab_id: tab_id.clone(),
+            url: url.clone(),
+        });
+    }
+
+    Ok(url)
+}
+
+/// Move several entries back (negative) or forward (positive) through a tab's history
+/// The offset is clamped to the history range; returns the URL landed on, or None if unchanged
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `offset` - Number of entries to move
+#[tauri::command]
+pub async fn cef_navigate_offset(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    offset: i32,
+) -> Result<Option<String>, AppError> {
+    require_tab_id("cef_navigate_offset", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    debug!("[CEF] cef_navigate_offset: tab_id={} offset={}", tab_id, offset);
+
+    let url = manager.navigate_offset(&tab_id, offset)?;
+
+    if let Some(url) = &url {
+        // Emit navigation event so the webview loads the target entry
+        let _ = app.emit("cef:navigation-started", NavigateCefPayload {
+            tab_id: tab_id.clone(),
+            url: url.clone(),
+        });
+    }
+
+    Ok(url)
+}
+
+/// Save the scroll position of the current page into its history entry
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `x` - Horizontal scroll offset in pixels
+/// * `y` - Vertical scroll offset in pixels
+#[tauri::command]
+pub async fn cef_save_scroll(
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    x: f64,
+    y: f64,
+) -> Result<(), AppError> {
+    require_tab_id("cef_save_scroll", &tab_id)?;
+
+    if !x.is_finite() || !y.is_finite() {
+        warn!("[CEF] cef_save_scroll rejected: scroll position must be finite");
+        return Err(AppError::InvalidPath("Scroll position must be finite".into()));
+    }
+
+    if !manager.save_scroll(&tab_id, x, y)? {
+        return Err(AppError::NotFound(format!("tab {}", tab_id)));
+    }
+
+    Ok(())
+}
+
+/// Restore the most recently closed tab (Ctrl+Shift+T)
+/// Returns None if there is no closed tab to restore
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+#[tauri::command]
+pub async fn cef_restore_closed_tab(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+) -> Result<Option<CefBrowserInfo>, AppError> {
+    let restored = manager.restore_last_closed()?;
+
+    if let Some(browser) = &restored {
+        debug!("[CEF] cef_restore_closed_tab: tab_id={} url={}", browser.tab_id, browser.url);
+
+        let _ = app.emit("cef:browser-created", CreateCefBrowserPayload {
+            tab_id: browser.tab_id.clone(),
+            url: browser.url.clone(),
+        });
+    }
+
+    Ok(restored)
+}
+
+/// Set the zoom level of a tab
+/// Out-of-range or NaN levels are clamped, not rejected
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `zoom_level` - Requested zoom level (1.0 = 100%)
+#[tauri::command]
+pub async fn cef_set_zoom(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    zoom_level: f64,
+) -> Result<f64, AppError> {
+    require_tab_id("cef_set_zoom", &tab_id)?;
+
+    debug!("[CEF] cef_set_zoom: tab_id={} zoom_level={}", tab_id, zoom_level);
+
+    let zoom_level = manager.set_zoom(&tab_id, zoom_level)?
+        .ok_or_else(|| AppError::NotFound(format!("tab {}", tab_id)))?;
+
+    // Emit zoom change event
+    let _ = app.emit("cef:zoom-changed", ZoomChangeEventPayload {
+        tab_id: tab_id.clone(),
+        zoom_level,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(zoom_level)
+}
+
+/// Set or clear a tab's user agent override
+/// Passing None falls back to the default user agent
+/// The override only takes effect on the next navigation
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `user_agent` - User agent string, or None to clear
+#[tauri::command]
+pub async fn cef_set_user_agent(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    user_agent: Option<String>,
+) -> Result<(), AppError> {
+    require_tab_id("cef_set_user_agent", &tab_id)?;
+
+    if matches!(&user_agent, Some(ua) if ua.trim().is_empty()) {
+        warn!("[CEF] cef_set_user_agent rejected: user_agent cannot be empty");
+        return Err(AppError::InvalidPath("user_agent cannot be empty".into()));
+    }
+
+    debug!("[CEF] cef_set_user_agent: tab_id={} user_agent={:?}", tab_id, user_agent);
+
+    if !manager.set_user_agent(&tab_id, user_agent.clone())? {
+        return Err(AppError::NotFound(format!("tab {}", tab_id)));
+    }
+
+    // Emit user agent change event
+    let _ = app.emit("cef:user-agent-changed", UserAgentChangeEventPayload {
+        tab_id: tab_id.clone(),
+        user_agent,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// Validate a PDF output path and resolve it to an absolute path
+/// The path must end in `.pdf` and its directory must exist and be writable
+pub(crate) fn resolve_pdf_output_path(output_path: &str) -> Result<PathBuf, AppError> {
+    let path = std::path::Path::new(output_path);
+
+    let is_pdf = path.extension()
+        .map(|ext| ext.eq_ignore_ascii_case("pdf"))
+        .unwrap_or(false);
+    if !is_pdf {
+        warn!("[CEF] cef_print_to_pdf rejected: output path must end in .pdf: {}", output_path);
+        return Err(AppError::InvalidPath(format!("Output path must end in .pdf: {}", output_path)));
+    }
+
+    let file_name = path.file_name()
+        .ok_or_else(|| AppError::InvalidPath(format!("Invalid output path: {}", output_path)))?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => std::env::current_dir()?,
+    };
+    let dir = parent.canonicalize()
+        .map_err(|_| AppError::InvalidPath(format!("Output directory does not exist: {}", parent.display())))?;
+    if !dir.is_dir() {
+        warn!("[CEF] cef_print_to_pdf rejected: output directory does not exist: {}", dir.display());
+        return Err(AppError::InvalidPath(format!("Output directory does not exist: {}", dir.display())));
+    }
+
+    // Probe writability with a throwaway file
+    let probe = dir.join(format!(".lumina-write-test-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&probe, b"").map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => {
+            AppError::PermissionDenied(format!("Output directory is not writable: {}", dir.display()))
+        }
+        _ => AppError::InvalidPath(format!("Output directory is not writable: {}", dir.display())),
+    })?;
+    let _ = std::fs::remove_file(&probe);
+
+    Ok(dir.join(file_name))
+}
+
+/// Print the current page of a tab to a PDF file
+/// Returns the resolved absolute output path
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `output_path` - Destination `.pdf` file
+/// * `landscape` - Use landscape orientation
+/// * `include_background` - Print background graphics
+#[tauri::command]
+pub async fn cef_print_to_pdf(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    output_path: String,
+    landscape: bool,
+    include_background: bool,
+) -> Result<String, AppError> {
+    require_tab_id("cef_print_to_pdf", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    let output_path = resolve_pdf_output_path(&output_path)?
+        .to_string_lossy()
+        .to_string();
+
+    debug!("[CEF] cef_print_to_pdf: tab_id={} output_path={}", tab_id, output_path);
+
+    // Emit print request; the webview writes the PDF
+    let _ = app.emit("cef:print-pdf-request", PrintPdfPayload {
+        tab_id: tab_id.clone(),
+        output_path: output_path.clone(),
+        landscape,
+        include_background,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(output_path)
+}
+
+/// How long `cef_capture_screenshot` waits for the webview to report back
+pub const SCREENSHOT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Screenshots reported by the webview, keyed by tab id
+pub type ScreenshotResults = PendingResults<ScreenshotResult>;
+
+/// Capture a screenshot of the current page
+/// The webview captures the page on `cef:screenshot-request` and reports it via `cef_report_screenshot`
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `screenshots` - Pending screenshot results
+/// * `tab_id` - Browser tab identifier
+/// * `full_page` - Capture the full scrollable page instead of the viewport
+#[tauri::command]
+pub async fn cef_capture_screenshot(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    screenshots: State<'_, ScreenshotResults>,
+    tab_id: String,
+    full_page: bool,
+) -> Result<ScreenshotResult, AppError> {
+    require_tab_id("cef_capture_screenshot", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    debug!("[CEF] cef_capture_screenshot: tab_id={} full_page={}", tab_id, full_page);
+
+    // Drop any stale result from an earlier, timed-out request
+    screenshots.take(&tab_id)?;
+
+    let _ = app.emit("cef:screenshot-request", ScreenshotRequestPayload {
+        tab_id: tab_id.clone(),
+        full_page,
+        timestamp: manager.now_ms(),
+    });
+
+    screenshots.wait_for(&tab_id, SCREENSHOT_TIMEOUT).await?
+        .ok_or_else(|| AppError::Timeout(format!("Screenshot of tab: {}", tab_id)))
+}
+
+/// Report a captured screenshot back from the webview
+/// 
+/// # Arguments
+/// * `screenshots` - Pending screenshot results
+/// * `tab_id` - Browser tab identifier
+/// * `width` - Image width in pixels
+/// * `height` - Image height in pixels
+/// * `png_base64` - PNG image data, base64 encoded
+#[tauri::command]
+pub async fn cef_report_screenshot(
+    screenshots: State<'_, ScreenshotResults>,
+    tab_id: String,
+    width: u32,
+    height: u32,
+    png_base64: String,
+) -> Result<(), AppError> {
+    require_tab_id("cef_report_screenshot", &tab_id)?;
+
+    debug!("[CEF] cef_report_screenshot: tab_id={} size={}x{}", tab_id, width, height);
+
+    screenshots.put(tab_id, ScreenshotResult { width, height, png_base64 })
+}
+
+/// How long `cef_get_cookies` waits for the webview to report back
+pub const COOKIES_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Cookie lists reported by the webview, keyed by tab id
+pub type CookieResults = PendingResults<Vec<CookieInfo>>;
+
+/// Drop the cookies a clear for `domain` removes; `None` removes all of them
+fn retain_uncleared(cookies: Vec<CookieInfo>, domain: Option<&str>) -> Vec<CookieInfo> {
+    match domain {
+        Some(domain) => cookies.into_iter().filter(|c| !c.matches_domain(domain)).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Get the cookies visible to a tab
+/// The webview collects them on `cef:cookies-request` and reports them via `cef_report_cookies`
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `cookies` - Pending cookie results
+/// * `tab_id` - Browser tab identifier
+#[tauri::command]
+pub async fn cef_get_cookies(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    cookies: State<'_, CookieResults>,
+    tab_id: String,
+) -> Result<Vec<CookieInfo>, AppError> {
+    require_tab_id("cef_get_cookies", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    debug!("[CEF] cef_get_cookies: tab_id={}", tab_id);
+
+    // Drop any stale result from an earlier, timed-out request
+    cookies.take(&tab_id)?;
+
+    let _ = app.emit("cef:cookies-request", CookiesRequestPayload {
+        tab_id: tab_id.clone(),
+        timestamp: manager.now_ms(),
+    });
+
+    cookies.wait_for(&tab_id, COOKIES_TIMEOUT).await?
+        .ok_or_else(|| AppError::Timeout(format!("Cookies of tab: {}", tab_id)))
+}
+
+/// Report a tab's cookies back from the webview
+/// 
+/// # Arguments
+/// * `cookies` - Pending cookie results
+/// * `tab_id` - Browser tab identifier
+/// * `list` - Cookies visible to the tab
+#[tauri::command]
+pub async fn cef_report_cookies(
+    cookies: State<'_, CookieResults>,
+    tab_id: String,
+    list: Vec<CookieInfo>,
+) -> Result<(), AppError> {
+    require_tab_id("cef_report_cookies", &tab_id)?;
+
+    debug!("[CEF] cef_report_cookies: tab_id={} count={}", tab_id, list.len());
+
+    cookies.put(tab_id, list)
+}
+
+/// Clear a tab's cookies
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `cookies` - Pending cookie results
+/// * `tab_id` - Browser tab identifier
+/// * `domain` - Only clear cookies for this domain and its subdomains; `None` clears all
+#[tauri::command]
+pub async fn cef_clear_cookies(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    cookies: State<'_, CookieResults>,
+    tab_id: String,
+    domain: Option<String>,
+) -> Result<(), AppError> {
+    require_tab_id("cef_clear_cookies", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    let domain = match domain {
+        Some(domain) => {
+            let domain = domain.trim().trim_start_matches('.').to_lowercase();
+            if domain.is_empty() {
+                warn!("[CEF] cef_clear_cookies rejected: domain cannot be empty");
+                return Err(AppError::InvalidPath("domain cannot be empty".into()));
+            }
+            Some(domain)
+        }
+        None => None,
+    };
+
+    debug!("[CEF] cef_clear_cookies: tab_id={} domain={:?}", tab_id, domain);
+
+    // Keep a parked, not yet collected report consistent with the clear
+    if let Some(parked) = cookies.take(&tab_id)? {
+        cookies.put(tab_id.clone(), retain_uncleared(parked, domain.as_deref()))?;
+    }
+
+    let _ = app.emit("cef:cookies-clear", CookiesClearPayload {
+        tab_id,
+        domain,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// Reject bookmarking a page shown in a private tab
+/// Checks the given tab, and without one any private tab currently showing `url`
+fn ensure_bookmarkable(manager: &CefBrowserManager, url: &str, tab_id: Option<&str>) -> Result<(), AppError> {
+    let is_private = match tab_id {
+        Some(tab_id) => manager.get_browser(tab_id)?.is_some_and(|b| b.is_private),
+        None => manager.is_open_privately(url)?,
+    };
+    if is_private {
+        warn!("[CEF] cef_add_bookmark rejected: private tabs cannot be bookmarked");
+        return Err(AppError::InvalidPath("Private tabs cannot be bookmarked".into()));
+    }
+    Ok(())
+}
+
+/// Bookmark a page
+/// Bookmarking an already bookmarked URL updates its title; pages in private tabs are rejected
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `store` - Shared bookmark store state
+/// * `manager` - Shared browser manager state
+/// * `url` - Page URL
+/// * `title` - Page title
+/// * `tab_id` - Tab the page is shown in, if any
+#[tauri::command]
+pub async fn cef_add_bookmark(
+    app: AppHandle,
+    store: State<'_, BookmarkStore>,
+    manager: State<'_, CefBrowserManager>,
+    url: String,
+    title: String,
+    tab_id: Option<String>,
+) -> Result<Bookmark, AppError> {
+    if url.is_empty() {
+        warn!("[CEF] cef_add_bookmark rejected: url cannot be empty");
+        return Err(AppError::InvalidPath("url cannot be empty".into()));
+    }
+
+    ensure_bookmarkable(&manager, &url, tab_id.as_deref())?;
+
+    debug!("[CEF] cef_add_bookmark: url={} title={}", url, title);
+
+    let bookmark = store.add(url, title)?;
+    store.save(&bookmarks_file_path(&app)?)?;
+
+    Ok(bookmark)
+}
+
+/// Remove a bookmark
+/// Returns false if no bookmark has the given id
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `store` - Shared bookmark store state
+/// * `id` - Bookmark identifier
+#[tauri::command]
+pub async fn cef_remove_bookmark(
+    app: AppHandle,
+    store: State<'_, BookmarkStore>,
+    id: String,
+) -> Result<bool, AppError> {
+    debug!("[CEF] cef_remove_bookmark: id={}", id);
+
+    let removed = store.remove(&id)?;
+    if removed {
+        store.save(&bookmarks_file_path(&app)?)?;
+    }
+
+    Ok(removed)
+}
+
+/// List all bookmarks
+/// 
+/// # Arguments
+/// * `store` - Shared bookmark store state
+#[tauri::command]
+pub async fn cef_list_bookmarks(
+    store: State<'_, BookmarkStore>,
+) -> Result<Vec<Bookmark>, AppError> {
+    store.list()
+}
+
+/// List all bookmarks grouped into nested folders
+/// 
+/// # Arguments
+/// * `store` - Shared bookmark store state
+#[tauri::command]
+pub async fn cef_list_bookmarks_tree(
+    store: State<'_, BookmarkStore>,
+) -> Result<BookmarkTree, AppError> {
+    store.tree()
+}
+
+/// Move a bookmark into a folder
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `store` - Shared bookmark store state
+/// * `id` - Bookmark identifier
+/// * `folder` - Folder path such as `Work/Research`; `None` moves it to the top level
+#[tauri::command]
+pub async fn cef_move_bookmark(
+    app: AppHandle,
+    store: State<'_, BookmarkStore>,
+    id: String,
+    folder: Option<String>,
+) -> Result<(), AppError> {
+    debug!("[CEF] cef_move_bookmark: id={} folder={:?}", id, folder);
+
+    if !store.move_to_folder(&id, folder.as_deref())? {
+        return Err(AppError::NotFound(format!("bookmark {}", id)));
+    }
+    store.save(&bookmarks_file_path(&app)?)
+}
+
+/// Rename a bookmark folder, including its subfolders
+/// Returns the number of bookmarks moved
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `store` - Shared bookmark store state
+/// * `old` - Current folder path
+/// * `new` - New folder path
+#[tauri::command]
+pub async fn cef_rename_bookmark_folder(
+    app: AppHandle,
+    store: State<'_, BookmarkStore>,
+    old: String,
+    new: String,
+) -> Result<usize, AppError> {
+    debug!("[CEF] cef_rename_bookmark_folder: old={} new={}", old, new);
+
+    let moved = store.rename_folder(&old, &new)?;
+    if moved > 0 {
+        store.save(&bookmarks_file_path(&app)?)?;
+    }
+
+    Ok(moved)
+}
+
+/// Notify that a page load failed (HTTP error, DNS failure, ...)
+/// This is called by the CEF browser when a load fails
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `code` - CEF / network error code
+/// * `description` - Error description
+/// * `url` - URL that failed to load
+#[tauri::command]
+pub async fn cef_on_load_error(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    code: i32,
+    description: String,
+    url: String,
+) -> Result<(), AppError> {
+    require_tab_id("cef_on_load_error", &tab_id)?;
+
+    debug!("[CEF] cef_on_load_error: tab_id={} code={} url={}", tab_id, code, url);
+
+    if !manager.on_load_error(&tab_id, code, description.clone(), url.clone())? {
+        return Err(AppError::NotFound(format!("tab {}", tab_id)));
+    }
+
+    let _ = app.emit("cef:load-error", LoadErrorPayload {
+        tab_id,
+        code,
+        description,
+        failed_url: url,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// Get why the last page load of a tab failed
+/// Returns None if the last load succeeded
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+#[tauri::command]
+pub async fn cef_get_load_error(
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+) -> Result<Option<LoadError>, AppError> {
+    require_tab_id("cef_get_load_error", &tab_id)?;
+
+    manager.get_browser(&tab_id)?
+        .map(|browser| browser.last_error)
+        .ok_or_else(|| AppError::NotFound(format!("tab {}", tab_id)))
+}
+
+/// Get how long the last page load of a tab took, in milliseconds
+/// Returns None while the page is still loading
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+#[tauri::command]
+pub async fn cef_get_load_duration(
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+) -> Result<Option<u64>, AppError> {
+    require_tab_id("cef_get_load_duration", &tab_id)?;
+
+    ensure_tab_exists(&manager, &tab_id)?;
+
+    manager.get_last_load_duration(&tab_id)
+}
+
+/// Stop page loads that have stalled past the load timeout
+/// The frontend calls this periodically; `cef:load-timeout` is emitted for each stopped tab
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+#[tauri::command]
+pub async fn cef_poll_timeouts(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+) -> Result<Vec<String>, AppError> {
+    let now = manager.now_ms();
+
+    let timed_out = manager.check_timeouts(now);
+    for tab_id in &timed_out {
+        warn!("[CEF] load timed out: tab_id={}", tab_id);
+
+        let _ = app.emit("cef:load-timeout", LoadTimeoutPayload {
+            tab_id: tab_id.clone(),
+            timestamp: now,
+        });
+    }
+
+    Ok(timed_out)
+}
+
+/// Mute or unmute a browser tab
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `muted` - Whether the tab should be muted
+#[tauri::command]
+pub async fn cef_set_muted(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    muted: bool,
+) -> Result<(), AppError> {
+    require_tab_id("cef_set_muted", &tab_id)?;
+
+    debug!("[CEF] cef_set_muted: tab_id={} muted={}", tab_id, muted);
+
+    if !manager.set_muted(&tab_id, muted)? {
+        return Err(AppError::NotFound(format!("tab {}", tab_id)));
+    }
+
+    let _ = app.emit("cef:mute-changed", MuteChangePayload {
+        tab_id: tab_id.clone(),
+        is_muted: muted,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// Notify that a browser tab started or stopped producing sound
+/// This is called by the CEF browser when its audio state changes
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+/// * `tab_id` - Browser tab identifier
+/// * `audible` - Whether the tab is producing sound
+#[tauri::command]
+pub async fn cef_on_audio_state(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+    tab_id: String,
+    audible: bool,
+) -> Result<(), AppError> {
+    require_tab_id("cef_on_audio_state", &tab_id)?;
+
+    if !manager.on_audio_state_change(&tab_id, audible)? {
+        return Err(AppError::NotFound(format!("tab {}", tab_id)));
+    }
+
+    let _ = app.emit("cef:audio-state-changed", AudioStatePayload {
+        tab_id: tab_id.clone(),
+        is_audible: audible,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// List tabs currently producing sound
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+#[tauri::command]
+pub async fn cef_list_audible_tabs(
+    manager: State<'_, CefBrowserManager>,
+) -> Result<Vec<String>, AppError> {
+    manager.list_audible_tabs()
+}
+
+/// List all open tabs with their state, sorted by tab id
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+#[tauri::command]
+pub async fn cef_list_tabs(
+    manager: State<'_, CefBrowserManager>,
+) -> Result<Vec<TabSummary>, AppError> {
+    manager.list_tabs()
+}
+
+/// Find an open tab already showing a URL (trailing slash and http/https are ignored)
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `url` - URL to look for
+#[tauri::command]
+pub async fn cef_find_tab_by_url(
+    manager: State<'_, CefBrowserManager>,
+    url: String,
+) -> Result<Option<String>, AppError> {
+    manager.find_tab_by_url(&url)
+}
+
+/// Report internal state counts of the CEF subsystem (for attaching to bug reports)
+/// 
+/// # Arguments
+/// * `manager` - Shared browser manager state
+/// * `pool` - Shared instance pool state
+/// * `store` - Shared bookmark store state
+#[tauri::command]
+pub async fn cef_diagnostics(
+    manager: State<'_, CefBrowserManager>,
+    pool: State<'_, CefInstancePool>,
+    store: State<'_, BookmarkStore>,
+) -> Result<Diagnostics, AppError> {
+    collect_diagnostics(&manager, &pool, &store)
+}
+
+/// Push the full state of every tab to the frontend in one `cef:state-snapshot` event
+/// Lets the UI rebuild its tab model in a single pass instead of calling each getter
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `manager` - Shared browser manager state
+#[tauri::command]
+pub async fn cef_broadcast_state(
+    app: AppHandle,
+    manager: State<'_, CefBrowserManager>,
+) -> Result<(), AppError> {
+    let tabs = manager.snapshot()?;
+
+    debug!("[CEF] cef_broadcast_state: {} tabs", tabs.len());
+
+    let _ = app.emit("cef:state-snapshot", StateSnapshotPayload {
+        tabs,
+        timestamp: manager.now_ms(),
+    });
+
+    Ok(())
+}
+
+/// Block navigation to a host and its subdomains
+/// Returns false if the host was already blocked
+/// 
+/// # Arguments
+/// * `blocklist` - Navigation blocklist state
+/// * `pattern` - Host pattern, e.g. `example.com`
+#[tauri::command]
+pub async fn cef_block_host(
+    blocklist: State<'_, NavigationBlocklist>,
+    pattern: String,
+) -> Result<bool, AppError> {
+    debug!("[CEF] cef_block_host: pattern={}", pattern);
+
+    blocklist.add(&pattern)
+}
+
+/// Remove a host from the navigation blocklist
+/// Returns false if the host was not blocked
+/// 
+/// # Arguments
+/// * `blocklist` - Navigation blocklist state
+/// * `pattern` - Host pattern to unblock
+#[tauri::command]
+pub async fn cef_unblock_host(
+    blocklist: State<'_, NavigationBlocklist>,
+    pattern: String,
+) -> Result<bool, AppError> {
+    debug!("[CEF] cef_unblock_host: pattern={}", pattern);
+
+    Ok(blocklist.remove(&pattern))
+}
+
+/// List blocked host patterns
+/// 
+/// # Arguments
+/// * `blocklist` - Navigation blocklist state
+#[tauri::command]
+pub async fn cef_list_blocked_hosts(
+    blocklist: State<'_, NavigationBlocklist>,
+) -> Result<Vec<String>, AppError> {
+    Ok(blocklist.list())
+}
+
+/// Emit the current state of a download
+fn emit_download_progress(app: &AppHandle, downloads: &DownloadManager, item: &DownloadItem) {
+    let _ = app.emit("cef:download-progress", DownloadProgressPayload {
+        id: item.id.clone(),
+        filename: item.filename.clone(),
+        received_bytes: item.received_bytes,
+        total_bytes: item.total_bytes,
+        state: item.state,
+        timestamp: downloads.now_ms(),
+    });
+}
+
+/// Notify that a download started
+/// This is called by the CEF browser when a download begins
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `downloads` - Download manager state
+/// * `url` - Download URL
+/// * `filename` - Suggested file name
+/// * `total_bytes` - Download size, if known
+#[tauri::command]
+pub async fn cef_on_download_started(
+    app: AppHandle,
+    downloads: State<'_, DownloadManager>,
+    url: String,
+    filename: String,
+    total_bytes: Option<u64>,
+) -> Result<DownloadItem, AppError> {
+    if url.is_empty() {
+        warn!("[CEF] cef_on_download_started rejected: URL cannot be empty");
+        return Err(AppError::InvalidPath("URL cannot be empty".into()));
+    }
+
+    let item = downloads.start(url, filename, total_bytes)?;
+    debug!("[CEF] cef_on_download_started: id={} filename={}", item.id, item.filename);
+
+    emit_download_progress(&app, &downloads, &item);
+    Ok(item)
+}
+
+/// Notify that a download received more data
+/// This is called by the CEF browser as the download progresses
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `downloads` - Download manager state
+/// * `id` - Download identifier
+/// * `received_bytes` - Bytes received so far
+/// * `total_bytes` - Download size, if it became known
+#[tauri::command]
+pub async fn cef_on_download_progress(
+    app: AppHandle,
+    downloads: State<'_, DownloadManager>,
+    id: String,
+    received_bytes: u64,
+    total_bytes: Option<u64>,
+) -> Result<(), AppError> {
+    let item = downloads.update_progress(&id, received_bytes, total_bytes)?;
+    emit_download_progress(&app, &downloads, &item);
+    Ok(())
+}
+
+/// Notify that a download finished
+/// This is called by the CEF browser when a download succeeds or fails
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `downloads` - Download manager state
+/// * `id` - Download identifier
+/// * `success` - Whether the download completed successfully
+#[tauri::command]
+pub async fn cef_on_download_finished(
+    app: AppHandle,
+    downloads: State<'_, DownloadManager>,
+    id: String,
+    success: bool,
+) -> Result<(), AppError> {
+    debug!("[CEF] cef_on_download_finished: id={} success={}", id, success);
 
+    let item = if success { downloads.complete(&id)? } else { downloads.fail(&id)? };
+    emit_download_progress(&app, &downloads, &item);
     Ok(())
 }
 
+/// List all downloads, oldest first
+/// 
+/// # Arguments
+/// * `downloads` - Download manager state
+#[tauri::command]
+pub async fn cef_list_downloads(
+    downloads: State<'_, DownloadManager>,
+) -> Result<Vec<DownloadItem>, AppError> {
+    Ok(downloads.list())
+}
+
+/// Cancel a download
+/// The webview aborts the transfer when it sees the cancelled state in `cef:download-progress`
+/// 
+/// # Arguments
+/// * `app` - Tauri app handle
+/// * `downloads` - Download manager state
+/// * `id` - Download identifier
+#[tauri::command]
+pub async fn cef_cancel_download(
+    app: AppHandle,
+    downloads: State<'_, DownloadManager>,
+    id: String,
+) -> Result<DownloadItem, AppError> {
+    debug!("[CEF] cef_cancel_download: id={}", id);
+
+    let item = downloads.cancel(&id)?;
+    emit_download_progress(&app, &downloads, &item);
+    Ok(item)
+}
+
 // ============== Event Payloads ==============
 
+/// Full tab state snapshot payload
+#[derive(Serialize, Clone)]
+pub struct StateSnapshotPayload {
+    pub tabs: Vec<CefBrowserInfo>,
+    pub timestamp: u64,
+}
+
 #[derive(Serialize, Clone)]
 pub struct CreateCefBrowserPayload {
     pub tab_id: String,
@@ -534,6 +2617,15 @@ pub struct UrlChangeEventPayload {
     pub timestamp: u64,
 }
 
+/// Find-in-page request payload
+#[derive(Serialize, Clone)]
+pub struct FindInPagePayload {
+    pub tab_id: String,
+    pub query: String,
+    pub match_case: bool,
+    pub timestamp: u64,
+}
+
 /// Page title change event payload
 #[derive(Serialize, Clone)]
 pub struct TitleChangeEventPayload {
@@ -557,6 +2649,34 @@ pub struct TabSwitchEventPayload {
     pub timestamp: u64,
 }
 
+/// Internal page request payload
+#[derive(Serialize, Clone)]
+pub struct ShowInternalPagePayload {
+    pub tab_id: String,
+    /// Page name without the `about:` prefix, e.g. `newtab`
+    pub page: String,
+    pub timestamp: u64,
+}
+
+/// Tab rename event payload
+#[derive(Serialize, Clone)]
+pub struct TabRenamedEventPayload {
+    pub old_id: String,
+    pub new_id: String,
+    pub timestamp: u64,
+}
+
+/// Tab activation event payload (bounds and visibility changed together)
+#[derive(Serialize, Clone)]
+pub struct TabActivatedEventPayload {
+    pub tab_id: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub timestamp: u64,
+}
+
 /// Bounds update event payload
 #[derive(Serialize, Clone)]
 pub struct BoundsUpdateEventPayload {
@@ -568,6 +2688,220 @@ pub struct BoundsUpdateEventPayload {
     pub timestamp: u64,
 }
 
+/// New bounds for one browser tab in a batch update
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoundsUpdate {
+    pub tab_id: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Batch bounds update event payload
+#[derive(Serialize, Clone)]
+pub struct BoundsBatchEventPayload {
+    pub updates: Vec<BoundsUpdate>,
+    pub timestamp: u64,
+}
+
+/// Zoom change event payload
+#[derive(Serialize, Clone)]
+pub struct ZoomChangeEventPayload {
+    pub tab_id: String,
+    pub zoom_level: f64,
+    pub timestamp: u64,
+}
+
+/// User agent change event payload
+#[derive(Serialize, Clone)]
+pub struct UserAgentChangeEventPayload {
+    pub tab_id: String,
+    pub user_agent: Option<String>,
+    pub timestamp: u64,
+}
+
+/// Mute change event payload
+#[derive(Serialize, Clone)]
+pub struct MuteChangePayload {
+    pub tab_id: String,
+    pub is_muted: bool,
+    pub timestamp: u64,
+}
+
+/// Audio state change event payload
+#[derive(Serialize, Clone)]
+pub struct AudioStatePayload {
+    pub tab_id: String,
+    pub is_audible: bool,
+    pub timestamp: u64,
+}
+
+/// Load progress event payload
+#[derive(Serialize, Clone)]
+pub struct LoadProgressPayload {
+    pub tab_id: String,
+    pub progress: f64,
+    pub timestamp: u64,
+}
+
+/// Load timeout event payload
+#[derive(Serialize, Clone)]
+pub struct LoadTimeoutPayload {
+    pub tab_id: String,
+    pub timestamp: u64,
+}
+
+/// Favicon change event payload
+#[derive(Serialize, Clone)]
+pub struct FaviconChangeEventPayload {
+    pub tab_id: String,
+    pub favicon: String,
+    pub timestamp: u64,
+}
+
+/// Navigation blocked event payload
+#[derive(Serialize, Clone)]
+pub struct NavigationBlockedPayload {
+    pub tab_id: String,
+    pub url: String,
+    pub timestamp: u64,
+}
+
+/// Print-to-PDF request payload
+#[derive(Serialize, Clone)]
+pub struct PrintPdfPayload {
+    pub tab_id: String,
+    pub output_path: String,
+    pub landscape: bool,
+    pub include_background: bool,
+    pub timestamp: u64,
+}
+
+/// Load error payload
+#[derive(Serialize, Clone)]
+pub struct LoadErrorPayload {
+    pub tab_id: String,
+    pub code: i32,
+    pub description: String,
+    pub failed_url: String,
+    pub timestamp: u64,
+}
+
+/// Download progress payload
+#[derive(Serialize, Clone)]
+pub struct DownloadProgressPayload {
+    pub id: String,
+    pub filename: String,
+    pub received_bytes: u64,
+    pub total_bytes: Option<u64>,
+    pub state: DownloadState,
+    pub timestamp: u64,
+}
+
+/// Cookies request payload
+#[derive(Serialize, Clone)]
+pub struct CookiesRequestPayload {
+    pub tab_id: String,
+    pub timestamp: u64,
+}
+
+/// Selection request payload
+#[derive(Serialize, Clone)]
+pub struct SelectionRequestPayload {
+    pub tab_id: String,
+    pub timestamp: u64,
+}
+
+/// Cookies clear payload
+#[derive(Serialize, Clone)]
+pub struct CookiesClearPayload {
+    pub tab_id: String,
+    pub domain: Option<String>,
+    pub timestamp: u64,
+}
+
+/// Internal state counts of the CEF subsystem, for bug reports
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostics {
+    pub browser_count: usize,
+    pub instance_count: usize,
+    pub visible_count: usize,
+    pub total_history_entries: usize,
+    pub bookmark_count: usize,
+    /// Tab with the longest history (lowest tab id on ties), if any tab is open
+    pub largest_history_tab: Option<String>,
+}
+
+/// Count the state held by the browser manager, instance pool and bookmark store
+fn collect_diagnostics(
+    manager: &CefBrowserManager,
+    pool: &CefInstancePool,
+    store: &BookmarkStore,
+) -> Result<Diagnostics, AppError> {
+    let history_lengths = manager.history_lengths()?;
+    let largest_history_tab = history_lengths.iter()
+        .rev()
+        .max_by_key(|(_, len)| *len)
+        .map(|(tab_id, _)| tab_id.clone());
+
+    Ok(Diagnostics {
+        browser_count: history_lengths.len(),
+        instance_count: pool.get_instance_count()?,
+        visible_count: pool.get_visible_instance_count()?,
+        total_history_entries: history_lengths.iter().map(|(_, len)| len).sum(),
+        bookmark_count: store.count()?,
+        largest_history_tab,
+    })
+}
+
+/// A cookie visible to a tab
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CookieInfo {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    pub path: String,
+    pub secure: bool,
+    pub http_only: bool,
+    /// Expiry in milliseconds since the epoch; `None` for session cookies
+    pub expires_ms: Option<u64>,
+}
+
+impl CookieInfo {
+    /// Whether the cookie belongs to `domain` or one of its subdomains
+    pub fn matches_domain(&self, domain: &str) -> bool {
+        let own = self.domain.trim_start_matches('.').to_lowercase();
+        let domain = domain.trim_start_matches('.').to_lowercase();
+        own == domain || own.ends_with(&format!(".{}", domain))
+    }
+}
+
+/// JavaScript execution request payload
+#[derive(Serialize, Clone)]
+pub struct ExecuteJsPayload {
+    pub tab_id: String,
+    pub request_id: String,
+    pub script: String,
+    pub timestamp: u64,
+}
+
+/// Screenshot request payload
+#[derive(Serialize, Clone)]
+pub struct ScreenshotRequestPayload {
+    pub tab_id: String,
+    pub full_page: bool,
+    pub timestamp: u64,
+}
+
+/// Captured screenshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScreenshotResult {
+    pub width: u32,
+    pub height: u32,
+    pub png_base64: String,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct PageContent {
     pub url: String,
@@ -575,6 +2909,21 @@ pub struct PageContent {
     pub content: String,
     pub description: String,
     pub favicon: Option<String>,
+    /// Estimated word count, set in reader mode
+    #[serde(default)]
+    pub word_count: Option<usize>,
+}
+
+impl PageContent {
+    /// Replace raw page HTML in `content` with reader-mode text
+    pub fn into_reader_mode(self) -> Self {
+        let readable = extract_readable(&self.content);
+        PageContent {
+            content: readable.text,
+            word_count: Some(readable.word_count),
+            ..self
+        }
+    }
 }
 
 #[cfg(test)]
@@ -661,6 +3010,7 @@ mod tests {
             content: "Content".to_string(),
             description: "Description".to_string(),
             favicon: Some("https://example.com/favicon.ico".to_string()),
+            word_count: None,
         };
         
         assert_eq!(payload.url, "https://example.com");
@@ -785,6 +3135,231 @@ mod tests {
         assert_eq!(payload.timestamp, 1701234567890);
     }
 
+    #[test]
+    fn test_zoom_change_event_payload() {
+        let payload = ZoomChangeEventPayload {
+            tab_id: "tab-1".to_string(),
+            zoom_level: 1.25,
+            timestamp: 1701234567890,
+        };
+        
+        assert_eq!(payload.tab_id, "tab-1");
+        assert_eq!(payload.zoom_level, 1.25);
+        assert_eq!(payload.timestamp, 1701234567890);
+    }
+
+    #[test]
+    fn test_resolve_pdf_output_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("page.PDF");
+
+        let resolved = resolve_pdf_output_path(output.to_str().unwrap()).unwrap();
+        assert!(resolved.is_absolute());
+        assert_eq!(resolved, dir.path().canonicalize().unwrap().join("page.PDF"));
+        // The writability probe leaves nothing behind
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_resolve_pdf_output_path_rejects_non_pdf() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("page.html");
+
+        let result = resolve_pdf_output_path(output.to_str().unwrap());
+        assert!(matches!(result, Err(AppError::InvalidPath(_))));
+    }
+
+    #[test]
+    fn test_resolve_pdf_output_path_rejects_missing_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("missing").join("page.pdf");
+
+        let result = resolve_pdf_output_path(output.to_str().unwrap());
+        assert!(matches!(result, Err(AppError::InvalidPath(_))));
+    }
+
+    #[test]
+    fn test_page_content_reader_mode() {
+        let page = PageContent {
+            url: "https://example.com".to_string(),
+            title: "Example".to_string(),
+            content: "<nav>Menu</nav><script>track()</script><article><p>Clean text</p></article>".to_string(),
+            description: String::new(),
+            favicon: None,
+            word_count: None,
+        };
+
+        let page = page.into_reader_mode();
+        assert_eq!(page.content, "Clean text");
+        assert_eq!(page.word_count, Some(2));
+        assert_eq!(page.url, "https://example.com");
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_report_is_picked_up() {
+        let screenshots = ScreenshotResults::new();
+        let result = ScreenshotResult {
+            width: 800,
+            height: 600,
+            png_base64: "iVBORw0KGgo=".to_string(),
+        };
+        screenshots.put("tab-1".to_string(), result.clone()).unwrap();
+
+        let received = screenshots
+            .wait_for("tab-1", std::time::Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert_eq!(received, Some(result));
+        assert!(screenshots.take("tab-1").unwrap().is_none());
+    }
+
+    fn cookie(name: &str, domain: &str) -> CookieInfo {
+        CookieInfo {
+            name: name.to_string(),
+            value: "v".to_string(),
+            domain: domain.to_string(),
+            path: "/".to_string(),
+            secure: true,
+            http_only: false,
+            expires_ms: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cookie_report_is_picked_up() {
+        let cookies = CookieResults::new();
+        let list = vec![cookie("sid", "example.com"), cookie("pref", ".google.com")];
+        cookies.put("tab-1".to_string(), list.clone()).unwrap();
+
+        let received = cookies
+            .wait_for("tab-1", std::time::Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert_eq!(received, Some(list));
+        assert!(cookies.take("tab-1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cookie_domain_filter() {
+        assert!(cookie("sid", ".example.com").matches_domain("example.com"));
+        assert!(cookie("sid", "mail.example.com").matches_domain("Example.com"));
+        assert!(!cookie("sid", "notexample.com").matches_domain("example.com"));
+        assert!(!cookie("sid", "example.com").matches_domain("mail.example.com"));
+
+        let list = vec![
+            cookie("a", "example.com"),
+            cookie("b", ".mail.example.com"),
+            cookie("c", "google.com"),
+        ];
+        let remaining = retain_uncleared(list.clone(), Some("example.com"));
+        assert_eq!(remaining, vec![cookie("c", "google.com")]);
+        assert!(retain_uncleared(list, None).is_empty());
+    }
+
+    fn clipped_page() -> PageContent {
+        PageContent {
+            url: "https://example.com/article".to_string(),
+            title: "An Article".to_string(),
+            content: "<html><body><nav>Menu</nav><article><p>First paragraph.</p><p>Second one.</p></article></body></html>".to_string(),
+            description: String::new(),
+            favicon: None,
+            word_count: None,
+        }
+    }
+
+    #[test]
+    fn test_clip_page_to_markdown_note() {
+        let dir = tempfile::tempdir().unwrap();
+        let note = dir.path().join("clips").join("article.md");
+
+        write_clip(clipped_page(), note.to_str().unwrap(), ClipFormat::Markdown).unwrap();
+
+        let written = std::fs::read_to_string(&note).unwrap();
+        assert!(written.starts_with("# An Article\n\n> Source: <https://example.com/article>\n> Clipped: "));
+        assert!(written.contains("First paragraph.\n\nSecond one."));
+        assert!(!written.contains("Menu"));
+    }
+
+    #[test]
+    fn test_clip_page_to_plain_text_note() {
+        let dir = tempfile::tempdir().unwrap();
+        let note = dir.path().join("article.txt");
+        let mut page = clipped_page();
+        page.title = String::new();
+
+        write_clip(page, note.to_str().unwrap(), ClipFormat::PlainText).unwrap();
+
+        let written = std::fs::read_to_string(&note).unwrap();
+        assert!(written.starts_with("https://example.com/article\nSource: https://example.com/article\nClipped: "));
+        assert!(written.contains("First paragraph."));
+    }
+
+    #[test]
+    fn test_empty_page_is_not_clipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let note = dir.path().join("empty.md");
+        let page = parse_page_content(r#"{"url": "https://example.com", "title": "", "content": "<html><nav>Menu</nav></html>", "description": "", "favicon": null}"#).unwrap();
+
+        assert!(matches!(write_clip(page, note.to_str().unwrap(), ClipFormat::Markdown), Err(AppError::NotFound(_))));
+        assert!(!note.exists());
+        assert!(matches!(parse_page_content("null"), Err(AppError::Parse(_))));
+    }
+
+    #[test]
+    fn test_bookmark_rejects_private_pages() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://public.com".to_string()).unwrap();
+        manager.register_browser_private("tab-2".to_string(), "https://secret.com".to_string()).unwrap();
+
+        assert!(ensure_bookmarkable(&manager, "https://public.com", Some("tab-1")).is_ok());
+        assert!(ensure_bookmarkable(&manager, "https://secret.com", Some("tab-2")).is_err());
+        // Without a tab id, a URL open in a private tab is still rejected
+        assert!(matches!(ensure_bookmarkable(&manager, "https://secret.com", None), Err(AppError::InvalidPath(_))));
+        assert!(ensure_bookmarkable(&manager, "https://public.com", None).is_ok());
+    }
+
+    #[test]
+    fn test_bookmark_note_name_sanitized() {
+        assert_eq!(sanitize_note_name("Rust: A <Guide> / \"Intro\"?"), "Rust A Guide Intro");
+        assert_eq!(sanitize_note_name("  tabs\tand\nnewlines... "), "tabs and newlines");
+        assert_eq!(sanitize_note_name("***"), "Untitled");
+        assert_eq!(sanitize_note_name(&"x".repeat(300)).len(), MAX_NOTE_NAME_CHARS);
+
+        let dir = tempfile::tempdir().unwrap();
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+        let path = write_bookmark_note(dir.path().to_str().unwrap(), "https://example.com/a", "A/B [test]", date).unwrap();
+        assert!(path.ends_with("2024-03-09 A B [test].md"));
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written, "# A/B [test]\n\n[A/B \\[test\\]](<https://example.com/a>)\n");
+    }
+
+    #[test]
+    fn test_bookmark_note_avoids_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("clips");
+        std::fs::create_dir_all(&root).unwrap();
+        let existing = root.join("2024-03-09 Example.md");
+        std::fs::write(&existing, "keep me").unwrap();
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+
+        let second = write_bookmark_note(root.to_str().unwrap(), "https://example.com", "Example", date).unwrap();
+        let third = write_bookmark_note(root.to_str().unwrap(), "https://example.com", "Example", date).unwrap();
+
+        assert!(second.ends_with("2024-03-09 Example (2).md"));
+        assert!(third.ends_with("2024-03-09 Example (3).md"));
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "keep me");
+    }
+
+    #[test]
+    fn test_js_result_store_and_retrieve() {
+        let js_results = JsResults::new();
+        js_results.put("req-1".to_string(), "{\"answer\":42}".to_string()).unwrap();
+
+        assert!(js_results.take("req-2").unwrap().is_none());
+        assert_eq!(js_results.take("req-1").unwrap(), Some("{\"answer\":42}".to_string()));
+        assert!(js_results.take("req-1").unwrap().is_none());
+    }
+
     #[test]
     fn test_bounds_validation() {
         // Valid bounds
@@ -794,4 +3369,166 @@ mod tests {
         assert!(!(0.0 > 0.0 && 600.0 > 0.0));
         assert!(!(-800.0 > 0.0 && 600.0 > 0.0));
     }
+
+    #[test]
+    fn test_diagnostics_counts() {
+        let manager = CefBrowserManager::new();
+        let pool = CefInstancePool::new();
+        let store = BookmarkStore::new();
+
+        manager.register_browser("tab-1".to_string(), "https://a.com".to_string()).unwrap();
+        manager.register_browser("tab-2".to_string(), "https://b.com".to_string()).unwrap();
+        manager.on_url_change("tab-2", "https://b.com/one".to_string()).unwrap();
+        manager.on_url_change("tab-2", "https://b.com/two".to_string()).unwrap();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.show_instance("tab-2").unwrap();
+        store.add("https://a.com".to_string(), "A".to_string()).unwrap();
+
+        assert_eq!(collect_diagnostics(&manager, &pool, &store).unwrap(), Diagnostics {
+            browser_count: 2,
+            instance_count: 2,
+            visible_count: 1,
+            total_history_entries: 4,
+            bookmark_count: 1,
+            largest_history_tab: Some("tab-2".to_string()),
+        });
+
+        let empty = collect_diagnostics(&CefBrowserManager::new(), &CefInstancePool::new(), &BookmarkStore::new()).unwrap();
+        assert_eq!(empty.largest_history_tab, None);
+    }
+
+    #[test]
+    fn test_payload_timestamp_uses_injected_clock() {
+        let manager = CefBrowserManager::new().with_clock(|| 1_700_000_000_123);
+        let payload = UrlChangeEventPayload {
+            tab_id: "tab-1".to_string(),
+            url: "https://example.com".to_string(),
+            timestamp: manager.now_ms(),
+        };
+
+        assert_eq!(payload.timestamp, 1_700_000_000_123);
+        assert_ne!(CefBrowserManager::new().now_ms(), 1_700_000_000_123);
+    }
+
+    #[test]
+    fn test_execute_js_in_unknown_tab_is_not_found() {
+        let app = tauri::test::mock_app();
+        app.manage(CefBrowserManager::new());
+        app.manage(JsResults::new());
+
+        let result = tauri::async_runtime::block_on(cef_execute_js(
+            app.handle().clone(),
+            app.state::<CefBrowserManager>(),
+            app.state::<JsResults>(),
+            "missing".to_string(),
+            "1 + 1".to_string(),
+        ));
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_navigation_to_unknown_tab_is_not_found() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+
+        assert!(ensure_tab_exists(&manager, "tab-1").is_ok());
+        let err = ensure_tab_exists(&manager, "missing").unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+        assert_eq!(err.code(), "not_found");
+    }
+
+    thread_local! {
+        static CAPTURED_LOGS: std::cell::RefCell<Vec<(log::Level, String)>> = std::cell::RefCell::new(Vec::new());
+    }
+
+    /// Logger that records into a per-thread buffer, so parallel tests don't see each other's records
+    struct CaptureLogger;
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            CAPTURED_LOGS.with(|logs| logs.borrow_mut().push((record.level(), record.args().to_string())));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static CAPTURE_LOGGER: CaptureLogger = CaptureLogger;
+
+    /// Run `f` and return its result with the log records it produced
+    fn capture_logs<T>(f: impl FnOnce() -> T) -> (T, Vec<(log::Level, String)>) {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+        INSTALL.call_once(|| {
+            let _ = log::set_logger(&CAPTURE_LOGGER);
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+
+        CAPTURED_LOGS.with(|logs| logs.borrow_mut().clear());
+        let result = f();
+        (result, CAPTURED_LOGS.with(|logs| logs.borrow_mut().drain(..).collect()))
+    }
+
+    #[test]
+    fn test_empty_tab_id_logs_warning() {
+        let (result, records) = capture_logs(|| require_tab_id("cef_go_back", ""));
+        assert!(matches!(result, Err(AppError::InvalidPath(_))));
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].0, log::Level::Warn);
+        assert!(records[0].1.contains("cef_go_back"));
+
+        let (result, records) = capture_logs(|| require_tab_id("cef_go_back", "tab-1"));
+        assert!(result.is_ok());
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn test_newtab_produces_internal_page_event() {
+        let config = BrowserConfig::new();
+        let url = config.resolve_url("about:newtab").unwrap();
+
+        let payload = internal_page_payload("tab-1", &url, 1_700_000_000_000).unwrap();
+        assert_eq!(payload.tab_id, "tab-1");
+        assert_eq!(payload.page, "newtab");
+        assert_eq!(payload.timestamp, 1_700_000_000_000);
+
+        // Internal pages are matched before normalization, which would reject the `about` scheme
+        assert!(internal_page_payload("tab-1", "About:NewTab", 0).is_some());
+        assert!(internal_page_payload("tab-1", "https://example.com", 0).is_none());
+    }
+
+    #[test]
+    fn test_selection_appended_as_quote() {
+        let dir = tempfile::tempdir().unwrap();
+        let note = dir.path().join("reading.md");
+        std::fs::write(&note, "# Reading notes").unwrap();
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com/post".to_string()).unwrap();
+        manager.on_title_change("tab-1", "A Post".to_string()).unwrap();
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+
+        let selection = "First line.\n\nSecond paragraph.\n";
+        assert!(append_selection(note.to_str().unwrap(), selection, &browser.url, &browser.title).unwrap());
+
+        let written = std::fs::read_to_string(&note).unwrap();
+        assert_eq!(
+            written,
+            "# Reading notes\n\n> First line.\n>\n> Second paragraph.\n>\n> Source: [A Post](<https://example.com/post>)\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_selection_is_noop() {
+        let dir = tempfile::tempdir().unwrap();
+        let note = dir.path().join("reading.md");
+
+        assert!(!append_selection(note.to_str().unwrap(), "  \n", "https://example.com", "").unwrap());
+        assert!(!note.exists());
+
+        assert!(append_selection(note.to_str().unwrap(), "quote", "https://example.com", "").unwrap());
+        assert_eq!(std::fs::read_to_string(&note).unwrap(), "> quote\n>\n> Source: <https://example.com>\n");
+    }
 }
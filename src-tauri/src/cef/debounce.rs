@@ -0,0 +1,89 @@
+/// Coalescing of bounds updates
+///
+/// During a drag-resize the frontend reports new bounds many times per frame. Updates are
+/// parked here per tab and flushed at most once per `BOUNDS_FLUSH_INTERVAL`, so only the
+/// latest bounds of each tab reach the webview.
+
+use super::BrowserBounds;
+use crate::error::AppError;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Minimum time between two bounds flushes (about one frame at 60 Hz)
+pub const BOUNDS_FLUSH_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Latest pending bounds per tab
+pub struct BoundsDebouncer {
+    pending: Mutex<HashMap<String, BrowserBounds>>,
+    flush_scheduled: AtomicBool,
+}
+
+impl BoundsDebouncer {
+    pub fn new() -> Self {
+        BoundsDebouncer {
+            pending: Mutex::new(HashMap::new()),
+            flush_scheduled: AtomicBool::new(false),
+        }
+    }
+
+    /// Park bounds for a tab, replacing any not yet flushed
+    /// Returns true if the caller should schedule a flush (none is pending yet)
+    pub fn push(&self, tab_id: String, bounds: BrowserBounds) -> Result<bool, AppError> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+
+        pending.insert(tab_id, bounds);
+        Ok(!self.flush_scheduled.swap(true, Ordering::AcqRel))
+    }
+
+    /// Take all pending bounds, sorted by tab id
+    pub fn drain(&self) -> Result<Vec<(String, BrowserBounds)>, AppError> {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+
+        self.flush_scheduled.store(false, Ordering::Release);
+        let mut drained: Vec<(String, BrowserBounds)> = pending.drain().collect();
+        drained.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(drained)
+    }
+}
+
+impl Default for BoundsDebouncer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(width: f64) -> BrowserBounds {
+        BrowserBounds { x: 0.0, y: 0.0, width, height: 600.0 }
+    }
+
+    #[test]
+    fn test_only_latest_bounds_flushed() {
+        let debouncer = BoundsDebouncer::new();
+        assert!(debouncer.push("tab-1".to_string(), bounds(100.0)).unwrap());
+        assert!(!debouncer.push("tab-1".to_string(), bounds(200.0)).unwrap());
+        assert!(!debouncer.push("tab-2".to_string(), bounds(50.0)).unwrap());
+        assert!(!debouncer.push("tab-1".to_string(), bounds(300.0)).unwrap());
+
+        let flushed = debouncer.drain().unwrap();
+        assert_eq!(flushed, vec![
+            ("tab-1".to_string(), bounds(300.0)),
+            ("tab-2".to_string(), bounds(50.0)),
+        ]);
+        assert!(debouncer.drain().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_push_after_flush_schedules_again() {
+        let debouncer = BoundsDebouncer::new();
+        assert!(debouncer.push("tab-1".to_string(), bounds(100.0)).unwrap());
+        debouncer.drain().unwrap();
+
+        assert!(debouncer.push("tab-1".to_string(), bounds(120.0)).unwrap());
+    }
+}
@@ -0,0 +1,190 @@
+/// Download tracking for the AI Browser
+///
+/// The webview reports download lifecycle events; this keeps the current state of each download.
+
+use super::{system_clock, Clock};
+use crate::error::AppError;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Lifecycle state of a download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadState {
+    Pending,
+    InProgress,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+impl DownloadState {
+    /// Whether the download has finished and can no longer change
+    pub fn is_terminal(self) -> bool {
+        matches!(self, DownloadState::Completed | DownloadState::Cancelled | DownloadState::Failed)
+    }
+}
+
+/// A tracked download
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DownloadItem {
+    pub id: String,
+    pub url: String,
+    pub filename: String,
+    pub received_bytes: u64,
+    /// `None` while the server hasn't announced a size
+    pub total_bytes: Option<u64>,
+    pub state: DownloadState,
+    pub started_at: u64,
+}
+
+/// Download manager
+pub struct DownloadManager {
+    downloads: Mutex<HashMap<String, DownloadItem>>,
+    clock: Clock,
+}
+
+impl DownloadManager {
+    /// Create an empty download manager
+    pub fn new() -> Self {
+        DownloadManager {
+            downloads: Mutex::new(HashMap::new()),
+            clock: system_clock,
+        }
+    }
+
+    /// Use a custom clock for timestamps
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Current time in ms since the Unix epoch, from the manager's clock
+    pub fn now_ms(&self) -> u64 {
+        (self.clock)()
+    }
+
+    /// Start tracking a new download
+    pub fn start(&self, url: String, filename: String, total_bytes: Option<u64>) -> Result<DownloadItem, AppError> {
+        let mut downloads = self.downloads.lock().unwrap_or_else(|e| e.into_inner());
+
+        let item = DownloadItem {
+            id: uuid::Uuid::new_v4().to_string(),
+            url,
+            filename,
+            received_bytes: 0,
+            total_bytes,
+            state: DownloadState::Pending,
+            started_at: self.now_ms(),
+        };
+        downloads.insert(item.id.clone(), item.clone());
+
+        Ok(item)
+    }
+
+    /// Record received bytes; a `total_bytes` of `None` keeps the known total
+    pub fn update_progress(&self, id: &str, received_bytes: u64, total_bytes: Option<u64>) -> Result<DownloadItem, AppError> {
+        self.transition(id, |item| {
+            item.received_bytes = received_bytes;
+            if total_bytes.is_some() {
+                item.total_bytes = total_bytes;
+            }
+            item.state = DownloadState::InProgress;
+        })
+    }
+
+    /// Mark a download as completed
+    pub fn complete(&self, id: &str) -> Result<DownloadItem, AppError> {
+        self.transition(id, |item| {
+            if let Some(total) = item.total_bytes {
+                item.received_bytes = total;
+            }
+            item.state = DownloadState::Completed;
+        })
+    }
+
+    /// Mark a download as failed
+    pub fn fail(&self, id: &str) -> Result<DownloadItem, AppError> {
+        self.transition(id, |item| item.state = DownloadState::Failed)
+    }
+
+    /// Cancel a download
+    pub fn cancel(&self, id: &str) -> Result<DownloadItem, AppError> {
+        self.transition(id, |item| item.state = DownloadState::Cancelled)
+    }
+
+    /// List all downloads, oldest first
+    pub fn list(&self) -> Vec<DownloadItem> {
+        let downloads = self.downloads.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut list: Vec<DownloadItem> = downloads.values().cloned().collect();
+        list.sort_by(|a, b| a.started_at.cmp(&b.started_at).then_with(|| a.id.cmp(&b.id)));
+        list
+    }
+
+    /// Apply a state change to a download that hasn't finished yet
+    fn transition(&self, id: &str, apply: impl FnOnce(&mut DownloadItem)) -> Result<DownloadItem, AppError> {
+        let mut downloads = self.downloads.lock().unwrap_or_else(|e| e.into_inner());
+
+        let item = downloads.get_mut(id)
+            .ok_or_else(|| AppError::NotFound(format!("download {}", id)))?;
+        if item.state.is_terminal() {
+            return Err(AppError::InvalidState(format!("download {} has already finished", id)));
+        }
+
+        apply(item);
+        Ok(item.clone())
+    }
+}
+
+impl Default for DownloadManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_progresses_to_completion() {
+        let manager = DownloadManager::new();
+        let item = manager.start("https://example.com/file.zip".to_string(), "file.zip".to_string(), Some(1000)).unwrap();
+        assert_eq!(item.state, DownloadState::Pending);
+        assert_eq!(item.received_bytes, 0);
+
+        let item = manager.update_progress(&item.id, 400, None).unwrap();
+        assert_eq!(item.state, DownloadState::InProgress);
+        assert_eq!(item.received_bytes, 400);
+        assert_eq!(item.total_bytes, Some(1000));
+
+        let item = manager.update_progress(&item.id, 1000, None).unwrap();
+        assert_eq!(item.received_bytes, 1000);
+
+        let item = manager.complete(&item.id).unwrap();
+        assert_eq!(item.state, DownloadState::Completed);
+        assert_eq!(manager.list(), vec![item]);
+    }
+
+    #[test]
+    fn test_cancelled_download_cannot_complete() {
+        let manager = DownloadManager::new();
+        let item = manager.start("https://example.com/big.iso".to_string(), "big.iso".to_string(), None).unwrap();
+        manager.update_progress(&item.id, 512, None).unwrap();
+
+        let item = manager.cancel(&item.id).unwrap();
+        assert_eq!(item.state, DownloadState::Cancelled);
+
+        assert!(matches!(manager.complete(&item.id), Err(AppError::InvalidState(_))));
+        assert!(manager.update_progress(&item.id, 1024, None).is_err());
+        assert_eq!(manager.list()[0].state, DownloadState::Cancelled);
+    }
+
+    #[test]
+    fn test_unknown_download() {
+        let manager = DownloadManager::new();
+        assert!(matches!(manager.cancel("missing"), Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,68 @@
+/// CEF events for consumers inside Rust
+///
+/// The managers publish state changes on a broadcast channel, mirroring the `cef:*`
+/// events sent to the frontend, so automation code can observe tabs without a webview.
+
+use super::{BrowserBounds, LoadError};
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber before the slowest one starts missing events
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// A browser state change
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CefEvent {
+    BrowserCreated { tab_id: String, url: String },
+    BrowserClosed { tab_id: String },
+    UrlChanged { tab_id: String, url: String },
+    TitleChanged { tab_id: String, title: String },
+    LoadingStateChanged { tab_id: String, is_loading: bool },
+    FaviconChanged { tab_id: String, favicon_url: String },
+    LoadFailed { tab_id: String, error: LoadError },
+    AudioStateChanged { tab_id: String, is_audible: bool },
+    BoundsChanged { tab_id: String, bounds: BrowserBounds },
+    TabShown { tab_id: String },
+    VisibilityChanged { tab_id: String, is_visible: bool },
+    LoadTimedOut { tab_id: String },
+    MuteChanged { tab_id: String, is_muted: bool },
+    ZoomChanged { tab_id: String, zoom_level: f64 },
+    TabRenamed { old_id: String, new_id: String },
+}
+
+/// Sending half shared by a manager; sending with no subscribers is not an error
+pub(crate) struct EventBus {
+    sender: broadcast::Sender<CefEvent>,
+}
+
+impl EventBus {
+    pub(crate) fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        EventBus { sender }
+    }
+
+    pub(crate) fn send(&self, event: CefEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<CefEvent> {
+        self.sender.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_without_subscribers() {
+        let bus = EventBus::new();
+        bus.send(CefEvent::BrowserClosed { tab_id: "tab-1".to_string() });
+
+        let mut receiver = bus.subscribe();
+        bus.send(CefEvent::TabShown { tab_id: "tab-2".to_string() });
+        assert_eq!(receiver.try_recv().unwrap(), CefEvent::TabShown { tab_id: "tab-2".to_string() });
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -0,0 +1,119 @@
+/// Favicon cache for the AI Browser
+///
+/// Icons are cached per host so repeat visits reuse them instead of re-fetching.
+
+use std::collections::{HashMap, VecDeque};
+
+/// Maximum number of hosts kept in the favicon cache
+pub const FAVICON_CACHE_CAP: usize = 200;
+
+/// Bounded LRU cache of host -> favicon (data URL or icon URL)
+pub struct FaviconCache {
+    icons: HashMap<String, String>,
+    /// Hosts ordered from least to most recently used
+    order: VecDeque<String>,
+    cap: usize,
+}
+
+impl FaviconCache {
+    /// Create an empty cache holding up to `FAVICON_CACHE_CAP` hosts
+    pub fn new() -> Self {
+        Self::with_capacity(FAVICON_CACHE_CAP)
+    }
+
+    /// Create an empty cache holding up to `cap` hosts
+    pub fn with_capacity(cap: usize) -> Self {
+        FaviconCache {
+            icons: HashMap::new(),
+            order: VecDeque::new(),
+            cap: cap.max(1),
+        }
+    }
+
+    fn touch(&mut self, host: &str) {
+        if let Some(pos) = self.order.iter().position(|h| h == host) {
+            if let Some(h) = self.order.remove(pos) {
+                self.order.push_back(h);
+            }
+        }
+    }
+
+    /// Look up the icon for a host, marking it as recently used
+    pub fn get(&mut self, host: &str) -> Option<String> {
+        let icon = self.icons.get(host).cloned()?;
+        self.touch(host);
+        Some(icon)
+    }
+
+    /// Cache the icon for a host, evicting the least recently used host when full
+    pub fn put(&mut self, host: String, icon: String) {
+        if self.icons.insert(host.clone(), icon).is_some() {
+            self.touch(&host);
+            return;
+        }
+
+        self.order.push_back(host);
+        while self.order.len() > self.cap {
+            if let Some(evicted) = self.order.pop_front() {
+                self.icons.remove(&evicted);
+            }
+        }
+    }
+
+    /// Remove all cached icons
+    pub fn clear(&mut self) {
+        self.icons.clear();
+        self.order.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.icons.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.icons.is_empty()
+    }
+}
+
+impl Default for FaviconCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hit() {
+        let mut cache = FaviconCache::new();
+        cache.put("example.com".to_string(), "data:image/png;base64,AAAA".to_string());
+
+        assert_eq!(cache.get("example.com").as_deref(), Some("data:image/png;base64,AAAA"));
+        assert!(cache.get("google.com").is_none());
+
+        cache.put("example.com".to_string(), "data:image/png;base64,BBBB".to_string());
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("example.com").as_deref(), Some("data:image/png;base64,BBBB"));
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let mut cache = FaviconCache::with_capacity(2);
+        cache.put("a.com".to_string(), "a".to_string());
+        cache.put("b.com".to_string(), "b".to_string());
+
+        // Touch a.com so b.com becomes the eviction candidate
+        assert!(cache.get("a.com").is_some());
+        cache.put("c.com".to_string(), "c".to_string());
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("b.com").is_none());
+        assert!(cache.get("a.com").is_some());
+        assert!(cache.get("c.com").is_some());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -5,15 +5,70 @@
 
 use crate::error::AppError;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Mutex;
 use serde::{Serialize, Deserialize};
+use favicon::FaviconCache;
+use visits::{VisitIndex, VisitStats};
+use events::{CefEvent, EventBus};
+use tokio::sync::broadcast;
 
 pub mod commands;
+pub mod bookmarks;
+pub mod blocklist;
+pub mod debounce;
+pub mod downloads;
+pub mod events;
+pub mod favicon;
+pub mod pending;
+pub mod reader;
+pub mod registry;
+pub mod session;
+pub mod visits;
+
+/// Check instance bounds: all values finite, width and height positive
+/// Negative x/y are allowed (the instance is partly or fully off-screen)
+fn validate_bounds(x: f64, y: f64, width: f64, height: f64) -> Result<(), AppError> {
+    if !(x.is_finite() && y.is_finite() && width.is_finite() && height.is_finite()) {
+        return Err(AppError::InvalidPath("Bounds must be finite".into()));
+    }
+    if width <= 0.0 || height <= 0.0 {
+        return Err(AppError::InvalidPath("Width and height must be positive".into()));
+    }
+    Ok(())
+}
+
+/// Bounds closer than this (in logical pixels) count as unchanged
+pub const BOUNDS_EPSILON: f64 = 1e-3;
+
+/// Source of the current time in ms since the Unix epoch
+/// Managers hold one so tests can substitute a fixed clock
+pub type Clock = fn() -> u64;
+
+/// The wall clock
+pub fn system_clock() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Result of a batch bounds update
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BoundsBatchOutcome {
+    /// Tabs whose bounds changed
+    pub changed: Vec<String>,
+    /// Tabs that were unknown or given invalid bounds
+    pub failed: Vec<String>,
+}
 
 /// CEF Instance Pool Manager
 /// Manages multiple CEF browser instances for multi-tab support
+/// A poisoned lock is recovered rather than failing every later call
 pub struct CefInstancePool {
     instances: Mutex<HashMap<String, CefInstance>>,
+    events: EventBus,
+    clock: Clock,
 }
 
 /// CEF Browser Instance
@@ -25,6 +80,10 @@ pub struct CefInstance {
     pub y: f64,
     pub width: f64,
     pub height: f64,
+    pub z_index: i32,
+    pub group_id: Option<String>,
+    /// Position in the tab strip (0-based, no gaps)
+    pub order: usize,
 }
 
 impl CefInstancePool {
@@ -32,9 +91,27 @@ impl CefInstancePool {
     pub fn new() -> Self {
         CefInstancePool {
             instances: Mutex::new(HashMap::new()),
+            events: EventBus::new(),
+            clock: system_clock,
         }
     }
 
+    /// Use a custom clock for timestamps
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Current time in ms since the Unix epoch, from the pool's clock
+    pub fn now_ms(&self) -> u64 {
+        (self.clock)()
+    }
+
+    /// Subscribe to bounds and visibility changes of the pool's instances
+    pub fn subscribe(&self) -> broadcast::Receiver<CefEvent> {
+        self.events.subscribe()
+    }
+
     /// Register a new CEF instance
     pub fn register_instance(
         &self,
@@ -44,9 +121,17 @@ impl CefInstancePool {
         width: f64,
         height: f64,
     ) -> Result<(), AppError> {
-        let mut instances = self.instances.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock instance pool".into()))?;
+        validate_bounds(x, y, width, height)?;
         
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        // Replacing an instance would lose its visibility, group and position
+        if instances.contains_key(&tab_id) {
+            return Err(AppError::AlreadyExists(format!("tab {}", tab_id)));
+        }
+        
+        // New tabs go to the end of the tab strip
+        let order = instances.len();
         instances.insert(tab_id.clone(), CefInstance {
             tab_id,
             is_visible: true,
@@ -54,41 +139,277 @@ impl CefInstancePool {
             y,
             width,
             height,
+            z_index: 0,
+            group_id: None,
+            order,
         });
         
         Ok(())
     }
 
     /// Unregister a CEF instance
+    /// If it was the only visible instance, a neighbouring one is shown (see `unregister_and_activate_next`)
     pub fn unregister_instance(&self, tab_id: &str) -> Result<(), AppError> {
-        let mut instances = self.instances.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock instance pool".into()))?;
+        self.unregister_and_activate_next(tab_id).map(|_| ())
+    }
+
+    /// Move an instance to a new tab id, keeping its bounds, visibility, group and position
+    /// Returns `AppError::NotFound` if `old_id` is unknown and `AppError::AlreadyExists` if `new_id` is taken
+    pub fn rename_instance(&self, old_id: &str, new_id: &str) -> Result<(), AppError> {
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if !instances.contains_key(old_id) {
+            return Err(AppError::NotFound(format!("tab {}", old_id)));
+        }
+        if old_id == new_id {
+            return Ok(());
+        }
+        if instances.contains_key(new_id) {
+            return Err(AppError::AlreadyExists(format!("tab {}", new_id)));
+        }
+        
+        if let Some(mut instance) = instances.remove(old_id) {
+            instance.tab_id = new_id.to_string();
+            instances.insert(new_id.to_string(), instance);
+        }
+        self.events.send(CefEvent::TabRenamed { old_id: old_id.to_string(), new_id: new_id.to_string() });
+        Ok(())
+    }
+
+    /// Unregister a CEF instance, keeping something on screen
+    /// When the removed instance was the last visible one, the tab before it in the tab strip
+    /// (or after it, if it was first) is made visible and its id returned
+    pub fn unregister_and_activate_next(&self, tab_id: &str) -> Result<Option<String>, AppError> {
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let Some(removed) = instances.remove(tab_id) else {
+            return Ok(None);
+        };
+        
+        // Close the gap left in the tab order
+        for instance in instances.values_mut() {
+            if instance.order > removed.order {
+                instance.order -= 1;
+            }
+        }
+        
+        if !removed.is_visible || instances.values().any(|i| i.is_visible) {
+            return Ok(None);
+        }
+        
+        let next = instances.values_mut()
+            .min_by_key(|i| (i.order as isize - removed.order as isize + 1).abs());
+        Ok(next.map(|instance| {
+            self.apply_visibility(instance, true);
+            instance.tab_id.clone()
+        }))
+    }
+
+    /// Move a CEF instance to `new_order` in the tab strip, shifting the tabs in between
+    /// `new_order` is clamped to the last position
+    pub fn reorder_instance(&self, tab_id: &str, new_order: usize) -> Result<(), AppError> {
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let Some(old_order) = instances.get(tab_id).map(|i| i.order) else {
+            return Ok(());
+        };
+        let new_order = new_order.min(instances.len() - 1);
+        
+        for instance in instances.values_mut() {
+            if instance.tab_id == tab_id {
+                instance.order = new_order;
+            } else if new_order < old_order && (new_order..old_order).contains(&instance.order) {
+                instance.order += 1;
+            } else if new_order > old_order && (old_order + 1..=new_order).contains(&instance.order) {
+                instance.order -= 1;
+            }
+        }
         
-        instances.remove(tab_id);
         Ok(())
     }
 
+    /// Get all instances in tab-strip order
+    pub fn get_instances_ordered(&self) -> Result<Vec<CefInstance>, AppError> {
+        let instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut ordered: Vec<CefInstance> = instances.values().cloned().collect();
+        ordered.sort_by(|a, b| a.order.cmp(&b.order).then_with(|| a.tab_id.cmp(&b.tab_id)));
+        
+        Ok(ordered)
+    }
+
     /// Get a CEF instance
     pub fn get_instance(&self, tab_id: &str) -> Result<Option<CefInstance>, AppError> {
-        let instances = self.instances.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock instance pool".into()))?;
+        let instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
         
         Ok(instances.get(tab_id).cloned())
     }
 
     /// Show a CEF instance (hide all others)
     pub fn show_instance(&self, tab_id: &str) -> Result<(), AppError> {
-        let mut instances = self.instances.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock instance pool".into()))?;
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let exists = instances.contains_key(tab_id);
+        for instance in instances.values_mut() {
+            let visible = instance.tab_id == tab_id;
+            self.apply_visibility(instance, visible);
+        }
+        if exists {
+            self.events.send(CefEvent::TabShown { tab_id: tab_id.to_string() });
+        }
+        
+        Ok(())
+    }
+
+    /// Move a CEF instance to new bounds and show it exclusively, under a single lock
+    /// Bounds are validated like `update_instance_bounds`. Returns false if the instance is unknown
+    pub fn activate_instance(
+        &self,
+        tab_id: &str,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> Result<bool, AppError> {
+        validate_bounds(x, y, width, height)?;
+        
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
         
-        // Hide all instances
+        if !instances.contains_key(tab_id) {
+            return Ok(false);
+        }
         for instance in instances.values_mut() {
-            instance.is_visible = false;
+            let visible = instance.tab_id == tab_id;
+            self.apply_visibility(instance, visible);
+            if visible {
+                instance.x = x;
+                instance.y = y;
+                instance.width = width;
+                instance.height = height;
+            }
+        }
+        self.events.send(CefEvent::BoundsChanged {
+            tab_id: tab_id.to_string(),
+            bounds: BrowserBounds { x, y, width, height },
+        });
+        self.events.send(CefEvent::TabShown { tab_id: tab_id.to_string() });
+        
+        Ok(true)
+    }
+
+    /// Show or hide a single CEF instance, leaving the others untouched
+    /// Unlike `show_instance` this allows several instances to be visible side by side.
+    /// Returns false if the instance is unknown
+    pub fn set_visible(&self, tab_id: &str, visible: bool) -> Result<bool, AppError> {
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        match instances.get_mut(tab_id) {
+            Some(instance) => {
+                self.apply_visibility(instance, visible);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Show a CEF instance on top of the others without hiding them
+    pub fn show_instance_keeping_others(&self, tab_id: &str) -> Result<(), AppError> {
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let top = instances.values()
+            .filter(|i| i.tab_id != tab_id)
+            .map(|i| i.z_index)
+            .max()
+            .unwrap_or(0);
+        
+        if let Some(instance) = instances.get_mut(tab_id) {
+            self.apply_visibility(instance, true);
+            if instance.z_index <= top {
+                instance.z_index = top + 1;
+            }
+        }
+        
+        Ok(())
+    }
+
+    /// Set the stacking order of a CEF instance
+    pub fn set_z_index(&self, tab_id: &str, z: i32) -> Result<(), AppError> {
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if let Some(instance) = instances.get_mut(tab_id) {
+            instance.z_index = z;
         }
         
-        // Show the target instance
+        Ok(())
+    }
+
+    /// Get visible instances ordered bottom-most first
+    pub fn get_instances_sorted_by_z(&self) -> Result<Vec<CefInstance>, AppError> {
+        let instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut visible: Vec<CefInstance> = instances.values()
+            .filter(|i| i.is_visible)
+            .cloned()
+            .collect();
+        visible.sort_by(|a, b| a.z_index.cmp(&b.z_index).then_with(|| a.tab_id.cmp(&b.tab_id)));
+        
+        Ok(visible)
+    }
+
+    /// Find the topmost visible instance containing a point
+    /// Bounds are inclusive, so a point on a shared edge belongs to the instance with the
+    /// higher z-index (ties go to the later tab in the tab strip)
+    pub fn instance_at_point(&self, x: f64, y: f64) -> Result<Option<String>, AppError> {
+        let instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(instances.values()
+            .filter(|i| i.is_visible)
+            .filter(|i| x >= i.x && x <= i.x + i.width && y >= i.y && y <= i.y + i.height)
+            .max_by(|a, b| a.z_index.cmp(&b.z_index).then_with(|| a.order.cmp(&b.order)))
+            .map(|i| i.tab_id.clone()))
+    }
+
+    /// Assign a CEF instance to a tab group (None removes it from its group)
+    pub fn set_group(&self, tab_id: &str, group_id: Option<String>) -> Result<(), AppError> {
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
         if let Some(instance) = instances.get_mut(tab_id) {
-            instance.is_visible = true;
+            instance.group_id = group_id;
+        }
+        
+        Ok(())
+    }
+
+    /// Get all instances in a tab group, ordered by tab id
+    pub fn get_instances_in_group(&self, group_id: &str) -> Result<Vec<CefInstance>, AppError> {
+        let instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut grouped: Vec<CefInstance> = instances.values()
+            .filter(|i| i.group_id.as_deref() == Some(group_id))
+            .cloned()
+            .collect();
+        grouped.sort_by(|a, b| a.tab_id.cmp(&b.tab_id));
+        
+        Ok(grouped)
+    }
+
+    /// Show all instances in a tab group (instances outside the group are untouched)
+    pub fn show_group(&self, group_id: &str) -> Result<(), AppError> {
+        self.set_group_visibility(group_id, true)
+    }
+
+    /// Hide all instances in a tab group (instances outside the group are untouched)
+    pub fn hide_group(&self, group_id: &str) -> Result<(), AppError> {
+        self.set_group_visibility(group_id, false)
+    }
+
+    fn set_group_visibility(&self, group_id: &str, is_visible: bool) -> Result<(), AppError> {
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        for instance in instances.values_mut() {
+            if instance.group_id.as_deref() == Some(group_id) {
+                self.apply_visibility(instance, is_visible);
+            }
         }
         
         Ok(())
@@ -96,17 +417,26 @@ impl CefInstancePool {
 
     /// Hide a CEF instance
     pub fn hide_instance(&self, tab_id: &str) -> Result<(), AppError> {
-        let mut instances = self.instances.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock instance pool".into()))?;
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
         
         if let Some(instance) = instances.get_mut(tab_id) {
-            instance.is_visible = false;
+            self.apply_visibility(instance, false);
         }
         
         Ok(())
     }
 
+    /// Set an instance's visibility and announce it, unless it already matches
+    fn apply_visibility(&self, instance: &mut CefInstance, is_visible: bool) {
+        if instance.is_visible != is_visible {
+            instance.is_visible = is_visible;
+            self.events.send(CefEvent::VisibilityChanged { tab_id: instance.tab_id.clone(), is_visible });
+        }
+    }
+
     /// Update instance bounds
+    /// Returns true if the bounds changed; false if they are within `BOUNDS_EPSILON` of the
+    /// current ones (nothing is written) or the instance is unknown
     pub fn update_instance_bounds(
         &self,
         tab_id: &str,
@@ -114,40 +444,100 @@ impl CefInstancePool {
         y: f64,
         width: f64,
         height: f64,
-    ) -> Result<(), AppError> {
-        let mut instances = self.instances.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock instance pool".into()))?;
+    ) -> Result<bool, AppError> {
+        validate_bounds(x, y, width, height)?;
         
-        if let Some(instance) = instances.get_mut(tab_id) {
-            instance.x = x;
-            instance.y = y;
-            instance.width = width;
-            instance.height = height;
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let Some(instance) = instances.get_mut(tab_id) else {
+            return Ok(false);
+        };
+        
+        Ok(self.apply_bounds(instance, BrowserBounds { x, y, width, height }))
+    }
+
+    /// Set an instance's bounds and announce them, unless they are within `BOUNDS_EPSILON`
+    /// of the current ones; returns whether they changed
+    fn apply_bounds(&self, instance: &mut CefInstance, bounds: BrowserBounds) -> bool {
+        let BrowserBounds { x, y, width, height } = bounds;
+        let unchanged = [(instance.x, x), (instance.y, y), (instance.width, width), (instance.height, height)]
+            .iter()
+            .all(|(current, new)| (current - new).abs() < BOUNDS_EPSILON);
+        if unchanged {
+            return false;
         }
         
-        Ok(())
+        instance.x = x;
+        instance.y = y;
+        instance.width = width;
+        instance.height = height;
+        self.events.send(CefEvent::BoundsChanged {
+            tab_id: instance.tab_id.clone(),
+            bounds,
+        });
+        true
+    }
+
+    /// Update bounds of many instances under a single lock
+    /// Each entry goes through the same change detection as `update_instance_bounds`;
+    /// entries with invalid bounds (see `validate_bounds`) or unknown tab ids fail
+    pub fn update_many_bounds(
+        &self,
+        updates: Vec<(String, f64, f64, f64, f64)>,
+    ) -> Result<BoundsBatchOutcome, AppError> {
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut outcome = BoundsBatchOutcome::default();
+        for (tab_id, x, y, width, height) in updates {
+            if validate_bounds(x, y, width, height).is_err() {
+                outcome.failed.push(tab_id);
+                continue;
+            }
+            
+            match instances.get_mut(&tab_id) {
+                Some(instance) => {
+                    if self.apply_bounds(instance, BrowserBounds { x, y, width, height }) {
+                        outcome.changed.push(tab_id);
+                    }
+                }
+                None => outcome.failed.push(tab_id),
+            }
+        }
+        
+        Ok(outcome)
+    }
+
+    /// Whether an instance is registered for `tab_id`
+    pub fn contains(&self, tab_id: &str) -> Result<bool, AppError> {
+        let instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(instances.contains_key(tab_id))
+    }
+
+    /// Whether no instances are registered
+    pub fn is_empty(&self) -> Result<bool, AppError> {
+        let instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(instances.is_empty())
     }
 
     /// Get all instances
     pub fn get_all_instances(&self) -> Result<Vec<CefInstance>, AppError> {
-        let instances = self.instances.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock instance pool".into()))?;
+        let instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
         
         Ok(instances.values().cloned().collect())
     }
 
     /// Get visible instance count
     pub fn get_visible_instance_count(&self) -> Result<usize, AppError> {
-        let instances = self.instances.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock instance pool".into()))?;
+        let instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
         
         Ok(instances.values().filter(|i| i.is_visible).count())
     }
 
     /// Get instance count
     pub fn get_instance_count(&self) -> Result<usize, AppError> {
-        let instances = self.instances.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock instance pool".into()))?;
+        let instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
         
         Ok(instances.len())
     }
@@ -165,6 +555,28 @@ pub struct NavigationHistoryEntry {
     pub url: String,
     pub title: String,
     pub timestamp: u64,
+    #[serde(default)]
+    pub scroll_x: f64,
+    #[serde(default)]
+    pub scroll_y: f64,
+}
+
+/// Result of a back/forward navigation: the target URL and its saved scroll position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NavigationTarget {
+    pub url: String,
+    pub scroll_x: f64,
+    pub scroll_y: f64,
+}
+
+impl From<&NavigationHistoryEntry> for NavigationTarget {
+    fn from(entry: &NavigationHistoryEntry) -> Self {
+        NavigationTarget {
+            url: entry.url.clone(),
+            scroll_x: entry.scroll_x,
+            scroll_y: entry.scroll_y,
+        }
+    }
 }
 
 /// CEF Browser Instance Information
@@ -178,506 +590,3161 @@ pub struct CefBrowserInfo {
     pub can_go_forward: bool,
     pub history: Vec<NavigationHistoryEntry>,
     pub history_index: usize,
+    #[serde(default = "default_zoom_level")]
+    pub zoom_level: f64,
+    /// Custom user agent override; takes effect on the next navigation
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    #[serde(default)]
+    pub favicon: Option<String>,
+    /// When the current page load started / last finished (ms since epoch)
+    #[serde(default)]
+    pub load_started_ms: u64,
+    #[serde(default)]
+    pub load_finished_ms: u64,
+    /// Page load progress from 0.0 to 1.0
+    #[serde(default)]
+    pub load_progress: f64,
+    /// Private tabs keep no history and are never saved or bookmarked
+    #[serde(default)]
+    pub is_private: bool,
+    #[serde(default)]
+    pub is_muted: bool,
+    /// Whether the tab is currently producing sound
+    #[serde(default)]
+    pub is_audible: bool,
+    /// When the title was last applied (ms since epoch), for coalescing rapid changes
+    #[serde(default)]
+    pub last_title_update_ms: u64,
+    /// Latest title held back while loading; applied once loading settles
+    #[serde(default)]
+    pub pending_title: Option<String>,
+    /// Why the last page load failed; cleared by the next successful load
+    #[serde(default)]
+    pub last_error: Option<LoadError>,
+    /// Last find-in-page search; cleared when the tab navigates
+    #[serde(default)]
+    pub find_state: Option<FindState>,
 }
 
-/// CEF Browser Manager
-/// Manages multiple CEF browser instances for multi-tab support
-pub struct CefBrowserManager {
-    browsers: Mutex<HashMap<String, CefBrowserInfo>>,
+/// Find-in-page search of a tab and its latest result
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FindState {
+    pub query: String,
+    pub match_case: bool,
+    /// 1-based index of the highlighted match (0 when nothing matched)
+    pub active_match: usize,
+    pub total_matches: usize,
 }
 
-impl CefBrowserManager {
-    /// Create a new CEF Browser Manager
-    pub fn new() -> Self {
-        CefBrowserManager {
-            browsers: Mutex::new(HashMap::new()),
+/// A failed page load (HTTP error, DNS failure, ...)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadError {
+    /// CEF / network error code
+    pub code: i32,
+    pub description: String,
+    pub failed_url: String,
+}
+
+/// Lightweight per-tab state for the tab overview
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TabSummary {
+    pub tab_id: String,
+    pub url: String,
+    pub title: String,
+    pub is_loading: bool,
+    pub can_go_back: bool,
+    pub can_go_forward: bool,
+}
+
+impl From<&CefBrowserInfo> for TabSummary {
+    fn from(browser: &CefBrowserInfo) -> Self {
+        TabSummary {
+            tab_id: browser.tab_id.clone(),
+            url: browser.url.clone(),
+            title: browser.title.clone(),
+            is_loading: browser.is_loading,
+            can_go_back: browser.can_go_back,
+            can_go_forward: browser.can_go_forward,
         }
     }
+}
 
-    /// Register a new browser instance
-    pub fn register_browser(&self, tab_id: String, url: String) -> Result<(), AppError> {
-        let mut browsers = self.browsers.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock browser manager".into()))?;
-        
-        let history = vec![NavigationHistoryEntry {
-            url: url.clone(),
-            title: String::new(),
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis() as u64,
-        }];
-        
-        browsers.insert(tab_id.clone(), CefBrowserInfo {
-            tab_id,
-            url,
-            title: String::new(),
-            is_loading: true,
-            can_go_back: false,
-            can_go_forward: false,
-            history,
-            history_index: 0,
-        });
-        
-        Ok(())
+/// Zoom level bounds for browser tabs
+pub const MIN_ZOOM_LEVEL: f64 = 0.25;
+pub const MAX_ZOOM_LEVEL: f64 = 5.0;
+
+fn default_zoom_level() -> f64 {
+    1.0
+}
+
+/// Clamp a zoom level into the supported range (NaN falls back to the default)
+fn clamp_zoom(level: f64) -> f64 {
+    if level.is_nan() {
+        return default_zoom_level();
     }
+    level.clamp(MIN_ZOOM_LEVEL, MAX_ZOOM_LEVEL)
+}
 
-    /// Unregister a browser instance
-    pub fn unregister_browser(&self, tab_id: &str) -> Result<(), AppError> {
-        let mut browsers = self.browsers.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock browser manager".into()))?;
-        
-        browsers.remove(tab_id);
-        Ok(())
+/// A history entry matching a history search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistorySearchHit {
+    pub tab_id: String,
+    pub url: String,
+    pub title: String,
+    pub timestamp: u64,
+}
+
+/// Snapshot of a tab's navigation history and current position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryView {
+    pub entries: Vec<NavigationHistoryEntry>,
+    pub index: usize,
+}
+
+/// Persisted browser session (all tabs and their navigation history)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CefSession {
+    pub browsers: Vec<CefBrowserInfo>,
+    /// Per-host visit counts
+    #[serde(default)]
+    pub visits: VisitStats,
+}
+
+/// Derive can_go_back / can_go_forward from history_index and the history vector
+/// A stale index past the end of history can go neither way, matching `on_go_back`/`on_go_forward`
+fn recompute_nav_flags(browser: &mut CefBrowserInfo) {
+    let len = browser.history.len();
+    browser.can_go_back = browser.history_index > 0 && browser.history_index < len;
+    browser.can_go_forward = browser.history_index + 1 < len;
+}
+
+/// Recompute history_index / can_go_back / can_go_forward from the history vector
+fn restore_nav_state(browser: &mut CefBrowserInfo) {
+    if browser.history.is_empty() {
+        browser.history_index = 0;
+        recompute_nav_flags(browser);
+        return;
     }
 
-    /// Get browser info
-    pub fn get_browser(&self, tab_id: &str) -> Result<Option<CefBrowserInfo>, AppError> {
-        let browsers = self.browsers.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock browser manager".into()))?;
-        
-        Ok(browsers.get(tab_id).cloned())
+    let last = browser.history.len() - 1;
+    browser.history_index = browser.history_index.min(last);
+    recompute_nav_flags(browser);
+
+    if let Some(entry) = browser.history.get(browser.history_index) {
+        browser.url = entry.url.clone();
     }
+}
 
-    /// Update browser info
-    #[allow(dead_code)]
-    pub fn update_browser(&self, tab_id: &str, info: CefBrowserInfo) -> Result<(), AppError> {
-        let mut browsers = self.browsers.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock browser manager".into()))?;
-        
-        browsers.insert(tab_id.to_string(), info);
-        Ok(())
+/// Info for a freshly opened tab, with its first history entry
+fn new_browser_info(tab_id: String, url: String, is_private: bool, now: u64) -> CefBrowserInfo {
+    let history = vec![NavigationHistoryEntry {
+        url: url.clone(),
+        title: String::new(),
+        timestamp: now,
+        scroll_x: 0.0,
+        scroll_y: 0.0,
+    }];
+    
+    CefBrowserInfo {
+        tab_id,
+        url,
+        title: String::new(),
+        is_loading: true,
+        can_go_back: false,
+        can_go_forward: false,
+        history,
+        history_index: 0,
+        zoom_level: default_zoom_level(),
+        user_agent: None,
+        favicon: None,
+        load_started_ms: now,
+        load_finished_ms: 0,
+        load_progress: 0.0,
+        is_private,
+        is_muted: false,
+        is_audible: false,
+        last_title_update_ms: 0,
+        pending_title: None,
+        last_error: None,
+        find_state: None,
     }
+}
 
-    /// Get all browsers
-    #[allow(dead_code)]
-    pub fn get_all_browsers(&self) -> Result<Vec<CefBrowserInfo>, AppError> {
-        let browsers = self.browsers.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock browser manager".into()))?;
-        
-        Ok(browsers.values().cloned().collect())
+/// Set a tab's title and the title of its current history entry
+fn apply_title(browser: &mut CefBrowserInfo, title: String) {
+    browser.pending_title = None;
+    browser.title = title.clone();
+    
+    if let Some(entry) = browser.history.get_mut(browser.history_index) {
+        entry.title = title;
     }
+}
 
-    /// Handle URL change event
-    pub fn on_url_change(&self, tab_id: &str, url: String) -> Result<(), AppError> {
-        let mut browsers = self.browsers.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock browser manager".into()))?;
-        
-        if let Some(browser) = browsers.get_mut(tab_id) {
-            // If we're not at the end of history, truncate forward history
-            if browser.history_index < browser.history.len() - 1 {
-                browser.history.truncate(browser.history_index + 1);
-            }
-            
-            // Add new entry to history
-            browser.history.push(NavigationHistoryEntry {
-                url: url.clone(),
-                title: String::new(),
-                timestamp: std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis() as u64,
-            });
-            
-            browser.url = url;
-            browser.history_index = browser.history.len() - 1;
-            browser.can_go_back = browser.history_index > 0;
-            browser.can_go_forward = false;
-        }
-        
-        Ok(())
+/// Drop the oldest history entries beyond `max_len`, keeping the back/forward invariants
+fn trim_history(browser: &mut CefBrowserInfo, max_len: usize) {
+    if browser.history.len() <= max_len {
+        return;
     }
 
-    /// Handle title change event
-    pub fn on_title_change(&self, tab_id: &str, title: String) -> Result<(), AppError> {
-        let mut browsers = self.browsers.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock browser manager".into()))?;
-        
-        if let Some(browser) = browsers.get_mut(tab_id) {
-            browser.title = title.clone();
-            
-            // Update title in current history entry
-            if let Some(entry) = browser.history.get_mut(browser.history_index) {
-                entry.title = title;
+    let excess = browser.history.len() - max_len;
+    browser.history.drain(..excess);
+    // An index pointing into the trimmed region clamps to 0
+    browser.history_index = browser.history_index.saturating_sub(excess);
+    restore_nav_state(browser);
+}
+
+/// Default maximum number of history entries kept per tab
+pub const DEFAULT_MAX_HISTORY_LEN: usize = 500;
+
+/// Maximum number of recently closed tabs kept for restoring
+pub const CLOSED_TAB_STACK_CAP: usize = 10;
+
+/// Default time after which a load still in progress is considered stalled
+pub const DEFAULT_LOAD_TIMEOUT_MS: u64 = 30_000;
+
+/// Default minimum time between applied title changes while a page loads
+pub const DEFAULT_MIN_TITLE_INTERVAL_MS: u64 = 250;
+
+/// Schemes the AI browser is allowed to load
+const SUPPORTED_URL_SCHEMES: &[&str] = &["http", "https"];
+
+/// Normalize a user-entered URL
+/// Trims whitespace, prepends `https://` when no scheme is given (`example.com`, `localhost:3000`),
+/// lowercases the scheme and host, and rejects unsupported schemes such as `ftp://` or `javascript:`.
+pub fn normalize_url(input: &str) -> Result<String, AppError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(AppError::InvalidPath("URL cannot be empty".into()));
+    }
+
+    let (scheme, rest) = match trimmed.split_once("://") {
+        Some((scheme, rest)) => (scheme.to_ascii_lowercase(), rest),
+        None => {
+            // `mailto:x` / `javascript:x` carry a scheme without `//`; `host:port` does not
+            if let Some((prefix, after)) = trimmed.split_once(':') {
+                let is_scheme = prefix.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                    && prefix.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+                    && !after.starts_with(|c: char| c.is_ascii_digit());
+                if is_scheme {
+                    return Err(AppError::InvalidPath(format!("Unsupported URL scheme: {}", prefix)));
+                }
             }
+            ("https".to_string(), trimmed)
         }
-        
-        Ok(())
+    };
+
+    if !SUPPORTED_URL_SCHEMES.contains(&scheme.as_str()) {
+        return Err(AppError::InvalidPath(format!("Unsupported URL scheme: {}", scheme)));
     }
 
-    /// Handle loading state change event
-    pub fn on_loading_state_change(&self, tab_id: &str, is_loading: bool) -> Result<(), AppError> {
-        let mut browsers = self.browsers.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock browser manager".into()))?;
-        
-        if let Some(browser) = browsers.get_mut(tab_id) {
-            browser.is_loading = is_loading;
-        }
-        
-        Ok(())
+    let host_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (host, tail) = rest.split_at(host_end);
+    if host.is_empty() {
+        return Err(AppError::InvalidPath(format!("URL has no host: {}", trimmed)));
     }
 
-    /// Handle back navigation
-    pub fn on_go_back(&self, tab_id: &str) -> Result<Option<String>, AppError> {
-        let mut browsers = self.browsers.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock browser manager".into()))?;
-        
-        if let Some(browser) = browsers.get_mut(tab_id) {
-            if browser.history_index > 0 {
-                browser.history_index -= 1;
-                browser.can_go_back = browser.history_index > 0;
-                browser.can_go_forward = true;
-                
-                if let Some(entry) = browser.history.get(browser.history_index) {
-                    browser.url = entry.url.clone();
-                    return Ok(Some(entry.url.clone()));
-                }
+    Ok(format!("{}://{}{}", scheme, host.to_ascii_lowercase(), tail))
+}
+
+/// Whether a navigation target is a local file: a `file://` URL or an absolute path
+pub fn is_local_target(input: &str) -> bool {
+    let trimmed = input.trim();
+    trimmed.get(..7).is_some_and(|prefix| prefix.eq_ignore_ascii_case("file://"))
+        || Path::new(trimmed).is_absolute()
+}
+
+/// Turn a `file://` URL or absolute local path into a `file://` URL
+/// The file must exist; a missing file returns `AppError::NotFound`.
+pub fn local_file_url(input: &str) -> Result<String, AppError> {
+    let trimmed = input.trim();
+    let path = match trimmed.get(..7) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("file://") => {
+            let rest = urlencoding::decode(&trimmed[7..])
+                .map_err(|_| AppError::InvalidPath(format!("Invalid file URL: {}", trimmed)))?
+                .into_owned();
+            // `file:///C:/notes` carries the Windows drive after the leading slash
+            match rest.strip_prefix('/') {
+                Some(drive) if drive.get(1..2) == Some(":") => drive.to_string(),
+                _ => rest,
             }
         }
-        
-        Ok(None)
+        _ => trimmed.to_string(),
+    };
+
+    let path = Path::new(&path);
+    if !path.is_absolute() {
+        return Err(AppError::InvalidPath(format!("Local path must be absolute: {}", trimmed)));
+    }
+    if !path.is_file() {
+        return Err(AppError::NotFound(path.display().to_string()));
     }
 
-    /// Handle forward navigation
-    pub fn on_go_forward(&self, tab_id: &str) -> Result<Option<String>, AppError> {
-        let mut browsers = self.browsers.lock()
-            .map_err(|_| AppError::InvalidPath("Failed to lock browser manager".into()))?;
-        
-        if let Some(browser) = browsers.get_mut(tab_id) {
-            if browser.history_index < browser.history.len() - 1 {
-                browser.history_index += 1;
-                browser.can_go_forward = browser.history_index < browser.history.len() - 1;
-                browser.can_go_back = true;
-                
-                if let Some(entry) = browser.history.get(browser.history_index) {
-                    browser.url = entry.url.clone();
-                    return Ok(Some(entry.url.clone()));
-                }
+    let slashed = path.to_string_lossy().replace('\\', "/");
+    let encoded: Vec<String> = slashed
+        .split('/')
+        .map(|segment| {
+            // Keep a Windows drive such as `C:` readable
+            if segment.len() == 2 && segment.ends_with(':') {
+                segment.to_string()
+            } else {
+                urlencoding::encode(segment).into_owned()
             }
+        })
+        .collect();
+    let encoded = encoded.join("/");
+
+    if encoded.starts_with('/') {
+        Ok(format!("file://{}", encoded))
+    } else {
+        Ok(format!("file:///{}", encoded))
+    }
+}
+
+/// Key for comparing URLs loosely: normalized, without an http/https scheme or trailing slash
+fn url_match_key(url: &str) -> Option<String> {
+    let url = normalize_url(url).ok()?;
+    let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")).unwrap_or(&url);
+    Some(rest.trim_end_matches('/').to_string())
+}
+
+/// Extract the lowercase host from a URL (scheme, userinfo and port are dropped)
+pub(crate) fn url_host(url: &str) -> Option<String> {
+    let rest = url.trim().split_once("://").map(|(_, rest)| rest).unwrap_or(url.trim());
+    let authority = &rest[..rest.find(['/', '?', '#']).unwrap_or(rest.len())];
+    let host = authority.rsplit_once('@').map(|(_, host)| host).unwrap_or(authority);
+    let host = match host.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+        _ => host,
+    };
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+
+    if host.is_empty() { None } else { Some(host) }
+}
+
+/// Home page used for new tabs and `cef_go_home` until one is configured
+pub const DEFAULT_HOME_URL: &str = "about:blank";
+
+/// Prefix of pages rendered by the app itself (start page, blank page) instead of loaded
+const INTERNAL_PAGE_PREFIX: &str = "about:";
+
+/// Name of an internal page (`about:newtab` -> `newtab`), or None for any other URL
+/// Internal pages bypass URL normalization and the blocklist
+pub fn internal_page_name(input: &str) -> Option<String> {
+    let trimmed = input.trim();
+    let prefix = trimmed.get(..INTERNAL_PAGE_PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(INTERNAL_PAGE_PREFIX) {
+        return None;
+    }
+    let name = trimmed[INTERNAL_PAGE_PREFIX.len()..].to_ascii_lowercase();
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    valid.then_some(name)
+}
+
+/// Canonical URL of an internal page, or None for any other URL
+fn internal_page_url(input: &str) -> Option<String> {
+    internal_page_name(input).map(|name| format!("{}{}", INTERNAL_PAGE_PREFIX, name))
+}
+
+/// Position and size of a browser view in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BrowserBounds {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Bounds used for new browsers when the caller leaves them out
+pub const DEFAULT_BROWSER_BOUNDS: BrowserBounds = BrowserBounds {
+    x: 0.0,
+    y: 0.0,
+    width: 1024.0,
+    height: 768.0,
+};
+
+/// User-configurable browser settings
+pub struct BrowserConfig {
+    home_url: Mutex<String>,
+    default_bounds: Mutex<BrowserBounds>,
+    /// Whether tabs may open local files (`file://` URLs and absolute paths)
+    allow_local: Mutex<bool>,
+}
+
+impl BrowserConfig {
+    /// Create a config with the default home page and bounds
+    pub fn new() -> Self {
+        BrowserConfig {
+            home_url: Mutex::new(DEFAULT_HOME_URL.to_string()),
+            default_bounds: Mutex::new(DEFAULT_BROWSER_BOUNDS),
+            allow_local: Mutex::new(false),
+        }
+    }
+
+    /// Whether tabs may open local files
+    pub fn allow_local(&self) -> bool {
+        *self.allow_local.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Allow or forbid opening local files in tabs
+    pub fn set_allow_local(&self, allow: bool) {
+        *self.allow_local.lock().unwrap_or_else(|e| e.into_inner()) = allow;
+    }
+
+    /// URL a navigation should load
+    /// With `allow_local` set, local paths and `file://` URLs become `file://` URLs for existing
+    /// files; otherwise (and for everything else) this is `normalize_url`, which rejects them.
+    pub fn resolve_navigation_url(&self, input: &str) -> Result<String, AppError> {
+        if let Some(url) = internal_page_url(input) {
+            Ok(url)
+        } else if self.allow_local() && is_local_target(input) {
+            local_file_url(input)
+        } else {
+            normalize_url(input)
         }
+    }
+
+    /// Bounds used for new browsers when the caller leaves them out
+    pub fn default_bounds(&self) -> BrowserBounds {
+        *self.default_bounds.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Set the bounds used for new browsers; they must be finite with a positive size
+    pub fn set_default_bounds(&self, bounds: BrowserBounds) -> Result<(), AppError> {
+        validate_bounds(bounds.x, bounds.y, bounds.width, bounds.height)?;
         
-        Ok(None)
+        *self.default_bounds.lock().unwrap_or_else(|e| e.into_inner()) = bounds;
+        Ok(())
+    }
+
+    /// Fill in missing bounds from the defaults, then validate the result
+    pub fn resolve_bounds(
+        &self,
+        x: Option<f64>,
+        y: Option<f64>,
+        width: Option<f64>,
+        height: Option<f64>,
+    ) -> Result<BrowserBounds, AppError> {
+        let defaults = self.default_bounds();
+        let bounds = BrowserBounds {
+            x: x.unwrap_or(defaults.x),
+            y: y.unwrap_or(defaults.y),
+            width: width.unwrap_or(defaults.width),
+            height: height.unwrap_or(defaults.height),
+        };
+        
+        validate_bounds(bounds.x, bounds.y, bounds.width, bounds.height)?;
+        Ok(bounds)
+    }
+
+    /// Current home page URL
+    pub fn home_url(&self) -> String {
+        self.home_url.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Set the home page; an empty URL resets it to `about:blank`
+    /// Returns the normalized URL that was stored
+    pub fn set_home_url(&self, url: &str) -> Result<String, AppError> {
+        let url = match url.trim() {
+            "" => DEFAULT_HOME_URL.to_string(),
+            trimmed => match internal_page_url(trimmed) {
+                Some(internal) => internal,
+                None => normalize_url(trimmed)?,
+            },
+        };
+        
+        *self.home_url.lock().unwrap_or_else(|e| e.into_inner()) = url.clone();
+        Ok(url)
+    }
+
+    /// URL a new tab should load: the home page for an empty URL, an internal page as is,
+    /// otherwise the normalized URL
+    pub fn resolve_url(&self, url: &str) -> Result<String, AppError> {
+        if url.trim().is_empty() {
+            Ok(self.home_url())
+        } else if let Some(internal) = internal_page_url(url) {
+            Ok(internal)
+        } else {
+            normalize_url(url)
+        }
     }
 }
 
-impl Default for CefBrowserManager {
+impl Default for BrowserConfig {
     fn default() -> Self {
         Self::new()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// CEF Browser Manager
+/// Manages multiple CEF browser instances for multi-tab support
+/// A poisoned lock is recovered rather than failing every later call
+pub struct CefBrowserManager {
+    browsers: Mutex<HashMap<String, CefBrowserInfo>>,
+    closed_stack: Mutex<Vec<CefBrowserInfo>>,
+    favicon_cache: Mutex<FaviconCache>,
+    visit_stats: Mutex<VisitStats>,
+    url_visits: Mutex<VisitIndex>,
+    max_history_len: usize,
+    load_timeout_ms: u64,
+    min_title_interval_ms: u64,
+    events: EventBus,
+    clock: Clock,
+}
 
-    #[test]
-    fn test_register_browser() {
-        let manager = CefBrowserManager::new();
-        let result = manager.register_browser(
-            "tab-1".to_string(),
-            "https://example.com".to_string(),
-        );
-        assert!(result.is_ok());
+impl CefBrowserManager {
+    /// Create a new CEF Browser Manager
+    pub fn new() -> Self {
+        Self::with_max_history(DEFAULT_MAX_HISTORY_LEN)
     }
 
-    #[test]
-    fn test_get_browser() {
-        let manager = CefBrowserManager::new();
-        manager.register_browser(
-            "tab-1".to_string(),
-            "https://example.com".to_string(),
-        ).unwrap();
+    /// Create a new CEF Browser Manager with a custom history bound per tab
+    pub fn with_max_history(len: usize) -> Self {
+        CefBrowserManager {
+            browsers: Mutex::new(HashMap::new()),
+            closed_stack: Mutex::new(Vec::new()),
+            favicon_cache: Mutex::new(FaviconCache::new()),
+            visit_stats: Mutex::new(VisitStats::new()),
+            url_visits: Mutex::new(VisitIndex::new()),
+            max_history_len: len.max(1),
+            load_timeout_ms: DEFAULT_LOAD_TIMEOUT_MS,
+            min_title_interval_ms: DEFAULT_MIN_TITLE_INTERVAL_MS,
+            events: EventBus::new(),
+            clock: system_clock,
+        }
+    }
+
+    /// Subscribe to browser state changes (the same changes the commands emit to the frontend)
+    pub fn subscribe(&self) -> broadcast::Receiver<CefEvent> {
+        self.events.subscribe()
+    }
+
+    /// Use a custom timeout for stalled page loads
+    pub fn with_load_timeout(mut self, timeout_ms: u64) -> Self {
+        self.load_timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Use a custom minimum interval between title changes while loading (0 disables coalescing)
+    pub fn with_min_title_interval(mut self, interval_ms: u64) -> Self {
+        self.min_title_interval_ms = interval_ms;
+        self
+    }
+
+    /// Use a custom clock for history, load and event timestamps
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Current time in ms since the Unix epoch, from the manager's clock
+    pub fn now_ms(&self) -> u64 {
+        (self.clock)()
+    }
+
+    /// Register a new browser instance
+    pub fn register_browser(&self, tab_id: String, url: String) -> Result<(), AppError> {
+        self.register(tab_id, url, false)
+    }
+
+    /// Register a new private (incognito) browser instance
+    pub fn register_browser_private(&self, tab_id: String, url: String) -> Result<(), AppError> {
+        self.register(tab_id, url, true)
+    }
+
+    /// Register a browser unless the tab is already known, returning the tab's info
+    /// Unlike `register_browser` a duplicate is not an error; the existing info (and history) is kept
+    pub fn register_or_get(&self, tab_id: String, url: String) -> Result<CefBrowserInfo, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
         
-        let browser = manager.get_browser("tab-1").unwrap();
-        assert!(browser.is_some());
-        assert_eq!(browser.unwrap().tab_id, "tab-1");
+        if let Some(existing) = browsers.get(&tab_id) {
+            return Ok(existing.clone());
+        }
+        let info = new_browser_info(tab_id.clone(), url, false, self.now_ms());
+        browsers.insert(tab_id, info.clone());
+        self.events.send(CefEvent::BrowserCreated { tab_id: info.tab_id.clone(), url: info.url.clone() });
+        Ok(info)
     }
 
-    #[test]
-    fn test_unregister_browser() {
-        let manager = CefBrowserManager::new();
-        manager.register_browser(
-            "tab-1".to_string(),
-            "https://example.com".to_string(),
-        ).unwrap();
+    /// Register a browser; a tab id that is already registered returns `AppError::AlreadyExists`
+    /// so a double-fired create event can't wipe the tab's history
+    fn register(&self, tab_id: String, url: String, is_private: bool) -> Result<(), AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
         
-        let result = manager.unregister_browser("tab-1");
-        assert!(result.is_ok());
+        if browsers.contains_key(&tab_id) {
+            return Err(AppError::AlreadyExists(format!("tab {}", tab_id)));
+        }
+        browsers.insert(tab_id.clone(), new_browser_info(tab_id.clone(), url.clone(), is_private, self.now_ms()));
+        self.events.send(CefEvent::BrowserCreated { tab_id, url });
         
-        let browser = manager.get_browser("tab-1").unwrap();
-        assert!(browser.is_none());
+        Ok(())
     }
 
-    #[test]
-    fn test_on_url_change() {
-        let manager = CefBrowserManager::new();
-        manager.register_browser(
-            "tab-1".to_string(),
-            "https://example.com".to_string(),
-        ).unwrap();
+    /// Unregister a browser instance
+    /// The closed tab is kept on a bounded stack so it can be restored later (private tabs are not)
+    pub fn unregister_browser(&self, tab_id: &str) -> Result<(), AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
         
-        let result = manager.on_url_change("tab-1", "https://google.com".to_string());
-        assert!(result.is_ok());
+        let Some(browser) = browsers.remove(tab_id) else {
+            return Ok(());
+        };
+        self.events.send(CefEvent::BrowserClosed { tab_id: tab_id.to_string() });
         
-        let browser = manager.get_browser("tab-1").unwrap().unwrap();
-        assert_eq!(browser.url, "https://google.com");
-        assert_eq!(browser.history.len(), 2);
-        assert!(browser.can_go_back);
-        assert!(!browser.can_go_forward);
+        if !browser.is_private {
+            let mut closed_stack = self.closed_stack.lock().unwrap_or_else(|e| e.into_inner());
+            
+            closed_stack.push(browser);
+            if closed_stack.len() > CLOSED_TAB_STACK_CAP {
+                closed_stack.remove(0);
+            }
+        }
+        
+        Ok(())
     }
 
-    #[test]
-    fn test_on_title_change() {
-        let manager = CefBrowserManager::new();
-        manager.register_browser(
-            "tab-1".to_string(),
-            "https://example.com".to_string(),
-        ).unwrap();
+    /// Remove a browser without keeping it for restore
+    /// Used to roll back a registration that never became a usable tab
+    pub(crate) fn discard_browser(&self, tab_id: &str) -> Result<bool, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
         
-        let result = manager.on_title_change("tab-1", "Example Page".to_string());
-        assert!(result.is_ok());
+        if browsers.remove(tab_id).is_none() {
+            return Ok(false);
+        }
+        self.events.send(CefEvent::BrowserClosed { tab_id: tab_id.to_string() });
+        Ok(true)
+    }
+
+    /// Move a browser to a new tab id, keeping its history and page state
+    /// Returns `AppError::NotFound` if `old_id` is unknown and `AppError::AlreadyExists` if `new_id` is taken
+    pub fn rename_tab(&self, old_id: &str, new_id: &str) -> Result<(), AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
         
-        let browser = manager.get_browser("tab-1").unwrap().unwrap();
-        assert_eq!(browser.title, "Example Page");
+        if !browsers.contains_key(old_id) {
+            return Err(AppError::NotFound(format!("tab {}", old_id)));
+        }
+        if old_id == new_id {
+            return Ok(());
+        }
+        if browsers.contains_key(new_id) {
+            return Err(AppError::AlreadyExists(format!("tab {}", new_id)));
+        }
+        
+        if let Some(mut browser) = browsers.remove(old_id) {
+            browser.tab_id = new_id.to_string();
+            browsers.insert(new_id.to_string(), browser);
+        }
+        self.events.send(CefEvent::TabRenamed { old_id: old_id.to_string(), new_id: new_id.to_string() });
+        Ok(())
+    }
+
+    /// Restore the most recently closed tab
+    /// If its tab id has been reused meanwhile, a fresh suffixed id is generated
+    pub fn restore_last_closed(&self) -> Result<Option<CefBrowserInfo>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        let mut closed_stack = self.closed_stack.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut browser = match closed_stack.pop() {
+            Some(browser) => browser,
+            None => return Ok(None),
+        };
+        
+        if browsers.contains_key(&browser.tab_id) {
+            let mut suffix = 1;
+            while browsers.contains_key(&format!("{}-{}", browser.tab_id, suffix)) {
+                suffix += 1;
+            }
+            browser.tab_id = format!("{}-{}", browser.tab_id, suffix);
+        }
+        
+        browsers.insert(browser.tab_id.clone(), browser.clone());
+        self.events.send(CefEvent::BrowserCreated { tab_id: browser.tab_id.clone(), url: browser.url.clone() });
+        Ok(Some(browser))
+    }
+
+    /// Get browser info
+    /// Back/forward flags that disagree with the history index are repaired before returning
+    pub fn get_browser(&self, tab_id: &str) -> Result<Option<CefBrowserInfo>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.get_mut(tab_id).map(|browser| {
+            recompute_nav_flags(browser);
+            browser.clone()
+        }))
+    }
+
+    /// Whether a browser is registered for `tab_id`
+    pub fn contains(&self, tab_id: &str) -> Result<bool, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.contains_key(tab_id))
+    }
+
+    /// Whether no browsers are registered
+    pub fn is_empty(&self) -> Result<bool, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.is_empty())
+    }
+
+    /// Update browser info
+    #[allow(dead_code)]
+    pub fn update_browser(&self, tab_id: &str, info: CefBrowserInfo) -> Result<(), AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        browsers.insert(tab_id.to_string(), info);
+        Ok(())
+    }
+
+    /// Get all browsers
+    pub fn get_all_browsers(&self) -> Result<Vec<CefBrowserInfo>, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.values().cloned().collect())
+    }
+
+    /// History length of every tab as (tab id, entry count), sorted by tab id
+    pub fn history_lengths(&self) -> Result<Vec<(String, usize)>, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut lengths: Vec<(String, usize)> = browsers.values()
+            .map(|b| (b.tab_id.clone(), b.history.len()))
+            .collect();
+        lengths.sort();
+        
+        Ok(lengths)
+    }
+
+    /// Consistent copy of every tab's full state, sorted by tab id
+    /// Taken under a single lock, so it never mixes states from before and after an update
+    pub fn snapshot(&self) -> Result<Vec<CefBrowserInfo>, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut tabs: Vec<CefBrowserInfo> = browsers.values().cloned().collect();
+        tabs.sort_by(|a, b| a.tab_id.cmp(&b.tab_id));
+        
+        Ok(tabs)
+    }
+
+    /// Find an open tab showing `url`, to switch to it instead of opening a duplicate
+    /// URLs are compared after normalization, ignoring a trailing slash and http vs https;
+    /// if several tabs match, the lowest tab id wins
+    pub fn find_tab_by_url(&self, url: &str) -> Result<Option<String>, AppError> {
+        let Some(key) = url_match_key(url) else {
+            return Ok(None);
+        };
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.values()
+            .filter(|b| url_match_key(&b.url).as_ref() == Some(&key))
+            .map(|b| b.tab_id.clone())
+            .min())
+    }
+
+    /// Get a lightweight summary of every open tab, sorted by tab id
+    pub fn list_tabs(&self) -> Result<Vec<TabSummary>, AppError> {
+        let mut tabs: Vec<TabSummary> = self.get_all_browsers()?
+            .iter()
+            .map(TabSummary::from)
+            .collect();
+        tabs.sort_by(|a, b| a.tab_id.cmp(&b.tab_id));
+        
+        Ok(tabs)
+    }
+
+    /// Get a tab's navigation history and current history index
+    /// A freshly registered tab has one entry, its initial URL: back/forward navigate by
+    /// index into this list, so the page the tab opened on has to be part of it.
+    pub fn get_history(&self, tab_id: &str) -> Result<Option<HistoryView>, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.get(tab_id).map(|browser| HistoryView {
+            entries: browser.history.clone(),
+            index: browser.history_index,
+        }))
+    }
+
+    /// Whether any private tab is currently showing `url`
+    pub fn is_open_privately(&self, url: &str) -> Result<bool, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.values().any(|b| b.is_private && b.url == url))
+    }
+
+    /// Search every tab's history for entries whose URL or title contains `query` (case-insensitive)
+    /// Results are most recent first, one per URL, at most `limit`; private tabs are not searched
+    pub fn search_history(&self, query: &str, limit: usize) -> Result<Vec<HistorySearchHit>, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let query = query.trim().to_lowercase();
+        if query.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+        
+        let mut hits: Vec<HistorySearchHit> = browsers.values()
+            .filter(|b| !b.is_private)
+            .flat_map(|b| b.history.iter().map(move |entry| (b, entry)))
+            .filter(|(_, entry)| {
+                entry.url.to_lowercase().contains(&query) || entry.title.to_lowercase().contains(&query)
+            })
+            .map(|(b, entry)| HistorySearchHit {
+                tab_id: b.tab_id.clone(),
+                url: entry.url.clone(),
+                title: entry.title.clone(),
+                timestamp: entry.timestamp,
+            })
+            .collect();
+        hits.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| a.tab_id.cmp(&b.tab_id)));
+        
+        // Keep only the most recent hit per URL
+        let mut seen = std::collections::HashSet::new();
+        hits.retain(|hit| seen.insert(hit.url.clone()));
+        hits.truncate(limit);
+        
+        Ok(hits)
+    }
+
+    /// Handle URL change event
+    pub fn on_url_change(&self, tab_id: &str, url: String) -> Result<(), AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if let Some(browser) = browsers.get_mut(tab_id) {
+            // Private tabs keep only the current entry
+            if browser.is_private {
+                browser.history.clear();
+                browser.history_index = 0;
+            }
+            
+            // If we're not at the end of history, truncate forward history
+            // An empty history (e.g. from a malformed session) just gets its first entry
+            if browser.history_index + 1 < browser.history.len() {
+                browser.history.truncate(browser.history_index + 1);
+            }
+            
+            let now = self.now_ms();
+            
+            // Add new entry to history
+            browser.history.push(NavigationHistoryEntry {
+                url: url.clone(),
+                title: String::new(),
+                timestamp: now,
+                scroll_x: 0.0,
+                scroll_y: 0.0,
+            });
+            browser.load_started_ms = now;
+            browser.load_progress = 0.0;
+            // A title held back for the previous page must not land on the new entry
+            browser.pending_title = None;
+            browser.find_state = None;
+            
+            let host = url_host(&url);
+            if let (Some(host), false) = (&host, browser.is_private) {
+                self.visit_stats.lock().unwrap_or_else(|e| e.into_inner()).record(host);
+            }
+            if !browser.is_private {
+                self.url_visits.lock().unwrap_or_else(|e| e.into_inner()).record(&url, now);
+            }
+            
+            // Reuse a cached icon for the host until the page reports its own
+            browser.favicon = host.and_then(|host| {
+                self.favicon_cache.lock().unwrap_or_else(|e| e.into_inner()).get(&host)
+            });
+            browser.url = url.clone();
+            browser.history_index = browser.history.len() - 1;
+            
+            trim_history(browser, self.max_history_len);
+            recompute_nav_flags(browser);
+            self.events.send(CefEvent::UrlChanged { tab_id: tab_id.to_string(), url });
+        }
+        
+        Ok(())
+    }
+
+    /// Navigate a tab to the home page, recording it in the tab's history
+    /// Returns false if the tab is unknown
+    pub fn go_home(&self, tab_id: &str, home_url: &str) -> Result<bool, AppError> {
+        if self.get_browser(tab_id)?.is_none() {
+            return Ok(false);
+        }
+        
+        self.mark_load_started(tab_id)?;
+        self.on_url_change(tab_id, home_url.to_string())?;
+        Ok(true)
+    }
+
+    /// Handle title change event
+    /// Returns false if the tab is unknown or the change was coalesced (see `on_title_change_at`)
+    pub fn on_title_change(&self, tab_id: &str, title: String) -> Result<bool, AppError> {
+        let now = self.now_ms();
+        
+        self.on_title_change_at(tab_id, title, now)
+    }
+
+    /// Handle title change event arriving at `now_ms`
+    /// While loading, changes closer than the minimum interval to the last applied one are held
+    /// back; the latest held-back title is applied when loading finishes
+    pub fn on_title_change_at(&self, tab_id: &str, title: String, now_ms: u64) -> Result<bool, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let Some(browser) = browsers.get_mut(tab_id) else {
+            return Ok(false);
+        };
+        
+        let too_soon = now_ms.saturating_sub(browser.last_title_update_ms) < self.min_title_interval_ms;
+        if browser.is_loading && too_soon {
+            browser.pending_title = Some(title);
+            return Ok(false);
+        }
+        
+        apply_title(browser, title.clone());
+        browser.last_title_update_ms = now_ms;
+        self.events.send(CefEvent::TitleChanged { tab_id: tab_id.to_string(), title });
+        Ok(true)
+    }
+
+    /// Handle favicon change event
+    /// The icon is cached under the tab's current host for later visits
+    pub fn on_favicon_change(&self, tab_id: &str, favicon_url: String) -> Result<bool, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+
+        let Some(browser) = browsers.get_mut(tab_id) else {
+            return Ok(false);
+        };
+
+        if let Some(host) = url_host(&browser.url) {
+            self.favicon_cache.lock().unwrap_or_else(|e| e.into_inner())
+                .put(host, favicon_url.clone());
+        }
+        browser.favicon = Some(favicon_url.clone());
+        self.events.send(CefEvent::FaviconChanged { tab_id: tab_id.to_string(), favicon_url });
+
+        Ok(true)
+    }
+
+    /// Look up the cached favicon for a URL's host
+    pub fn cached_favicon(&self, url: &str) -> Option<String> {
+        let host = url_host(url)?;
+        self.favicon_cache.lock().unwrap_or_else(|e| e.into_inner()).get(&host)
+    }
+
+    /// Remove all cached favicons
+    pub fn clear_favicon_cache(&self) {
+        self.favicon_cache.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Handle loading state change event
+    /// A load finishing while still in progress (i.e. without `on_load_error`) clears `last_error`.
+    /// Returns the held-back title that was applied now that loading finished, if any
+    pub fn on_loading_state_change(&self, tab_id: &str, is_loading: bool) -> Result<Option<String>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if let Some(browser) = browsers.get_mut(tab_id) {
+            let was_loading = browser.is_loading;
+            browser.is_loading = is_loading;
+            self.events.send(CefEvent::LoadingStateChanged { tab_id: tab_id.to_string(), is_loading });
+            if is_loading {
+                browser.load_progress = 0.0;
+            } else {
+                // A failed load was already ended by `on_load_error`
+                if was_loading {
+                    browser.last_error = None;
+                }
+                browser.load_progress = 1.0;
+                browser.load_finished_ms = self.now_ms();
+                
+                if let Some(title) = browser.pending_title.take() {
+                    apply_title(browser, title.clone());
+                    browser.last_title_update_ms = browser.load_finished_ms;
+                    self.events.send(CefEvent::TitleChanged { tab_id: tab_id.to_string(), title: title.clone() });
+                    return Ok(Some(title));
+                }
+            }
+        }
+        
+        Ok(None)
+    }
+
+    /// Handle load progress event
+    /// Progress is clamped to [0, 1]; returns the stored value, or None if the tab is unknown
+    pub fn on_load_progress(&self, tab_id: &str, progress: f64) -> Result<Option<f64>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let progress = if progress.is_nan() { 0.0 } else { progress.clamp(0.0, 1.0) };
+        Ok(browsers.get_mut(tab_id).map(|browser| {
+            browser.load_progress = progress;
+            progress
+        }))
+    }
+
+    /// Mute or unmute a tab
+    /// Returns false if the tab is unknown
+    pub fn set_muted(&self, tab_id: &str, muted: bool) -> Result<bool, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        match browsers.get_mut(tab_id) {
+            Some(browser) => {
+                browser.is_muted = muted;
+                self.events.send(CefEvent::MuteChanged { tab_id: tab_id.to_string(), is_muted: muted });
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Start (or repeat) a find-in-page search
+    /// An empty query repeats the last search with its settings; `match_case` of None keeps the
+    /// tab's previous preference. A new query resets the match counts until the webview reports.
+    /// Returns None if the tab is unknown
+    pub fn begin_find(&self, tab_id: &str, query: &str, match_case: Option<bool>) -> Result<Option<FindState>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let Some(browser) = browsers.get_mut(tab_id) else {
+            return Ok(None);
+        };
+        
+        let previous = browser.find_state.take().unwrap_or_default();
+        let match_case = match_case.unwrap_or(previous.match_case);
+        let state = if query.is_empty() {
+            if previous.query.is_empty() {
+                browser.find_state = None;
+                return Err(AppError::InvalidPath("No previous search to repeat".into()));
+            }
+            FindState { match_case, ..previous }
+        } else if query == previous.query && match_case == previous.match_case {
+            previous
+        } else {
+            FindState {
+                query: query.to_string(),
+                match_case,
+                active_match: 0,
+                total_matches: 0,
+            }
+        };
+        
+        browser.find_state = Some(state.clone());
+        Ok(Some(state))
+    }
+
+    /// Handle a find-in-page result reported by the webview
+    /// Returns the updated state, or None if the tab is unknown or has no active search
+    pub fn on_find_result(&self, tab_id: &str, active_match: usize, total_matches: usize) -> Result<Option<FindState>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.get_mut(tab_id)
+            .and_then(|browser| browser.find_state.as_mut())
+            .map(|state| {
+                state.total_matches = total_matches;
+                state.active_match = active_match.min(total_matches);
+                state.clone()
+            }))
+    }
+
+    /// Handle load error event
+    /// The failed load counts as finished; the error stays until the next successful load.
+    /// Returns false if the tab is unknown
+    pub fn on_load_error(&self, tab_id: &str, code: i32, description: String, url: String) -> Result<bool, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        match browsers.get_mut(tab_id) {
+            Some(browser) => {
+                browser.is_loading = false;
+                let error = LoadError {
+                    code,
+                    description,
+                    failed_url: url,
+                };
+                browser.last_error = Some(error.clone());
+                self.events.send(CefEvent::LoadFailed { tab_id: tab_id.to_string(), error });
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Handle audio state change event
+    /// Returns false if the tab is unknown
+    pub fn on_audio_state_change(&self, tab_id: &str, audible: bool) -> Result<bool, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        match browsers.get_mut(tab_id) {
+            Some(browser) => {
+                browser.is_audible = audible;
+                self.events.send(CefEvent::AudioStateChanged { tab_id: tab_id.to_string(), is_audible: audible });
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Ids of tabs currently producing sound, sorted
+    pub fn list_audible_tabs(&self) -> Result<Vec<String>, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut audible: Vec<String> = browsers.values()
+            .filter(|b| b.is_audible)
+            .map(|b| b.tab_id.clone())
+            .collect();
+        audible.sort();
+        
+        Ok(audible)
+    }
+
+    /// Record that a page load has started (navigation requested)
+    pub fn mark_load_started(&self, tab_id: &str) -> Result<(), AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if let Some(browser) = browsers.get_mut(tab_id) {
+            browser.is_loading = true;
+            browser.load_progress = 0.0;
+            browser.load_started_ms = self.now_ms();
+        }
+        
+        Ok(())
+    }
+
+    /// Find tabs whose load has been in progress longer than the load timeout
+    /// Timed-out tabs are marked as no longer loading; their ids are returned sorted
+    pub fn check_timeouts(&self, now_ms: u64) -> Vec<String> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut timed_out: Vec<String> = browsers.values_mut()
+            .filter(|b| b.is_loading && now_ms.saturating_sub(b.load_started_ms) > self.load_timeout_ms)
+            .map(|b| {
+                b.is_loading = false;
+                b.tab_id.clone()
+            })
+            .collect();
+        timed_out.sort();
+        for tab_id in &timed_out {
+            self.events.send(CefEvent::LoadTimedOut { tab_id: tab_id.clone() });
+        }
+        
+        timed_out
+    }
+
+    /// Duration of the last completed page load in milliseconds
+    /// Returns None if the tab is unknown or a load is still in progress
+    pub fn get_last_load_duration(&self, tab_id: &str) -> Result<Option<u64>, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.get(tab_id).and_then(|browser| {
+            if browser.is_loading || browser.load_finished_ms < browser.load_started_ms {
+                None
+            } else {
+                Some(browser.load_finished_ms - browser.load_started_ms)
+            }
+        }))
+    }
+
+    /// Handle back navigation
+    /// Returns the target URL with its saved scroll position
+    pub fn on_go_back(&self, tab_id: &str) -> Result<Option<NavigationTarget>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if let Some(browser) = browsers.get_mut(tab_id) {
+            // A stale index into an empty or shorter history has nowhere to go back to
+            if browser.history_index > 0 && browser.history_index < browser.history.len() {
+                browser.history_index -= 1;
+                recompute_nav_flags(browser);
+                
+                if let Some(entry) = browser.history.get(browser.history_index) {
+                    browser.url = entry.url.clone();
+                    self.events.send(CefEvent::UrlChanged { tab_id: tab_id.to_string(), url: entry.url.clone() });
+                    return Ok(Some(NavigationTarget::from(entry)));
+                }
+            }
+        }
+        
+        Ok(None)
+    }
+
+    /// Handle forward navigation
+    /// Returns the target URL with its saved scroll position
+    pub fn on_go_forward(&self, tab_id: &str) -> Result<Option<NavigationTarget>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if let Some(browser) = browsers.get_mut(tab_id) {
+            if browser.history_index + 1 < browser.history.len() {
+                browser.history_index += 1;
+                recompute_nav_flags(browser);
+                
+                if let Some(entry) = browser.history.get(browser.history_index) {
+                    browser.url = entry.url.clone();
+                    self.events.send(CefEvent::UrlChanged { tab_id: tab_id.to_string(), url: entry.url.clone() });
+                    return Ok(Some(NavigationTarget::from(entry)));
+                }
+            }
+        }
+        
+        Ok(None)
+    }
+
+    /// Move `offset` entries through history (negative goes back), clamped to the history range
+    /// Returns the URL landed on, or None if that is the current entry (or the tab is unknown)
+    pub fn navigate_offset(&self, tab_id: &str, offset: i32) -> Result<Option<String>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if let Some(browser) = browsers.get_mut(tab_id) {
+            if browser.history.is_empty() {
+                return Ok(None);
+            }
+            
+            let last = browser.history.len() - 1;
+            let current = browser.history_index.min(last);
+            let target = if offset < 0 {
+                current.saturating_sub(offset.unsigned_abs() as usize)
+            } else {
+                current.saturating_add(offset as usize).min(last)
+            };
+            
+            if target != current {
+                browser.history_index = target;
+                restore_nav_state(browser);
+                self.events.send(CefEvent::UrlChanged { tab_id: tab_id.to_string(), url: browser.url.clone() });
+                return Ok(Some(browser.url.clone()));
+            }
+        }
+        
+        Ok(None)
+    }
+
+    /// The `n` most visited hosts with their visit counts, most visited first
+    /// Every navigation recorded by `on_url_change` counts; private tabs are not counted
+    pub fn top_hosts(&self, n: usize) -> Result<Vec<(String, u64)>, AppError> {
+        let stats = self.visit_stats.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(stats.top_hosts(n))
+    }
+
+    /// The `n` most frecent URLs as (url, visit count, last visit ms), best first
+    /// Frecency weighs how often a URL was visited by how recently; private tabs are not counted
+    pub fn frecent_urls(&self, n: usize) -> Result<Vec<(String, u64, u64)>, AppError> {
+        let now = self.now_ms();
+        let visits = self.url_visits.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(visits.frecent(n, now))
+    }
+
+    /// Clear the navigation history of every tab, keeping only each tab's current page
+    /// Returns the number of history entries removed
+    pub fn clear_all_history(&self) -> Result<usize, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut removed = 0;
+        for browser in browsers.values_mut() {
+            if browser.history.is_empty() {
+                continue;
+            }
+            
+            let current = browser.history_index.min(browser.history.len() - 1);
+            removed += browser.history.len() - 1;
+            browser.history.swap(0, current);
+            browser.history.truncate(1);
+            browser.history_index = 0;
+            restore_nav_state(browser);
+        }
+        
+        Ok(removed)
+    }
+
+    /// Jump directly to a history entry
+    /// Returns the target URL, or None if the index is out of range
+    pub fn go_to_history_index(&self, tab_id: &str, index: usize) -> Result<Option<String>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if let Some(browser) = browsers.get_mut(tab_id) {
+            if index < browser.history.len() {
+                browser.history_index = index;
+                restore_nav_state(browser);
+                self.events.send(CefEvent::UrlChanged { tab_id: tab_id.to_string(), url: browser.url.clone() });
+                return Ok(Some(browser.url.clone()));
+            }
+        }
+        
+        Ok(None)
+    }
+
+    /// Save the scroll position into the current history entry
+    /// Returns false if the tab doesn't exist
+    pub fn save_scroll(&self, tab_id: &str, x: f64, y: f64) -> Result<bool, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if let Some(browser) = browsers.get_mut(tab_id) {
+            if let Some(entry) = browser.history.get_mut(browser.history_index) {
+                entry.scroll_x = x;
+                entry.scroll_y = y;
+            }
+            return Ok(true);
+        }
+        
+        Ok(false)
+    }
+
+    /// Set a tab's zoom level, clamped to [MIN_ZOOM_LEVEL, MAX_ZOOM_LEVEL]
+    /// Returns the applied level, or None if the tab doesn't exist
+    pub fn set_zoom(&self, tab_id: &str, level: f64) -> Result<Option<f64>, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.get_mut(tab_id).map(|browser| {
+            browser.zoom_level = clamp_zoom(level);
+            self.events.send(CefEvent::ZoomChanged { tab_id: tab_id.to_string(), zoom_level: browser.zoom_level });
+            browser.zoom_level
+        }))
+    }
+
+    /// Get a tab's zoom level
+    pub fn get_zoom(&self, tab_id: &str) -> Result<Option<f64>, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        Ok(browsers.get(tab_id).map(|browser| browser.zoom_level))
+    }
+
+    /// Set or clear (None) a tab's user agent override
+    /// The override only takes effect on the next navigation
+    /// Returns false if the tab doesn't exist
+    pub fn set_user_agent(&self, tab_id: &str, ua: Option<String>) -> Result<bool, AppError> {
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if let Some(browser) = browsers.get_mut(tab_id) {
+            browser.user_agent = ua;
+            return Ok(true);
+        }
+        
+        Ok(false)
+    }
+
+    /// Snapshot all non-private browsers (including navigation history), sorted by tab id
+    pub fn export_tabs(&self) -> Result<Vec<CefBrowserInfo>, AppError> {
+        let browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        let mut tabs: Vec<CefBrowserInfo> = browsers.values().filter(|b| !b.is_private).cloned().collect();
+        tabs.sort_by(|a, b| a.tab_id.cmp(&b.tab_id));
+        
+        Ok(tabs)
+    }
+
+    /// Install browsers from a snapshot, replacing the current set or merging by tab id
+    /// Returns the installed browsers with their navigation state recomputed; a snapshot with
+    /// empty or repeated tab ids is rejected before anything changes
+    pub fn import_tabs(&self, tabs: Vec<CefBrowserInfo>, merge: bool) -> Result<Vec<CefBrowserInfo>, AppError> {
+        let mut seen = std::collections::HashSet::new();
+        for browser in &tabs {
+            if browser.tab_id.is_empty() || !seen.insert(browser.tab_id.as_str()) {
+                return Err(AppError::Parse(format!("Invalid tab id in import: {:?}", browser.tab_id)));
+            }
+        }
+        
+        let mut browsers = self.browsers.lock().unwrap_or_else(|e| e.into_inner());
+        
+        if !merge {
+            browsers.clear();
+        }
+        let mut installed = Vec::with_capacity(tabs.len());
+        for mut browser in tabs {
+            restore_nav_state(&mut browser);
+            browser.zoom_level = clamp_zoom(browser.zoom_level);
+            browsers.insert(browser.tab_id.clone(), browser.clone());
+            installed.push(browser);
+        }
+        
+        Ok(installed)
+    }
+
+    /// Save all browsers (including navigation history) to a JSON session file
+    pub fn save_session(&self, path: &Path) -> Result<(), AppError> {
+        let session = CefSession {
+            browsers: self.export_tabs()?,
+            visits: self.visit_stats.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        };
+        
+        let json = serde_json::to_string_pretty(&session)
+            .map_err(|e| AppError::InvalidPath(format!("Failed to serialize session: {}", e)))?;
+        
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, json)?;
+        
+        Ok(())
+    }
+
+    /// Load browsers from a JSON session file, replacing the current set
+    /// A missing or corrupt file yields an empty session
+    pub fn load_session(&self, path: &Path) -> Result<Vec<CefBrowserInfo>, AppError> {
+        let session = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<CefSession>(&json).ok())
+            .unwrap_or_default();
+        
+        *self.visit_stats.lock().unwrap_or_else(|e| e.into_inner()) = session.visits;
+        self.import_tabs(session.browsers, false)
+    }
+}
+
+impl Default for CefBrowserManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_browser() {
+        let manager = CefBrowserManager::new();
+        let result = manager.register_browser(
+            "tab-1".to_string(),
+            "https://example.com".to_string(),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_get_browser() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser(
+            "tab-1".to_string(),
+            "https://example.com".to_string(),
+        ).unwrap();
+        
+        let browser = manager.get_browser("tab-1").unwrap();
+        assert!(browser.is_some());
+        assert_eq!(browser.unwrap().tab_id, "tab-1");
+    }
+
+    #[test]
+    fn test_unregister_browser() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser(
+            "tab-1".to_string(),
+            "https://example.com".to_string(),
+        ).unwrap();
+        
+        let result = manager.unregister_browser("tab-1");
+        assert!(result.is_ok());
+        
+        let browser = manager.get_browser("tab-1").unwrap();
+        assert!(browser.is_none());
+    }
+
+    #[test]
+    fn test_on_url_change() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser(
+            "tab-1".to_string(),
+            "https://example.com".to_string(),
+        ).unwrap();
+        
+        let result = manager.on_url_change("tab-1", "https://google.com".to_string());
+        assert!(result.is_ok());
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.url, "https://google.com");
+        assert_eq!(browser.history.len(), 2);
+        assert!(browser.can_go_back);
+        assert!(!browser.can_go_forward);
+    }
+
+    #[test]
+    fn test_on_title_change() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser(
+            "tab-1".to_string(),
+            "https://example.com".to_string(),
+        ).unwrap();
+        
+        let result = manager.on_title_change("tab-1", "Example Page".to_string());
+        assert!(result.is_ok());
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.title, "Example Page");
+    }
+
+    #[test]
+    fn test_on_loading_state_change() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser(
+            "tab-1".to_string(),
+            "https://example.com".to_string(),
+        ).unwrap();
+        
+        let result = manager.on_loading_state_change("tab-1", false);
+        assert!(result.is_ok());
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert!(!browser.is_loading);
+    }
+
+    #[test]
+    fn test_on_go_back() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser(
+            "tab-1".to_string(),
+            "https://example.com".to_string(),
+        ).unwrap();
+        
+        // Navigate to a new URL
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        
+        // Go back
+        let result = manager.on_go_back("tab-1").unwrap().map(|t| t.url);
+        assert!(result.is_some());
+        assert_eq!(result.unwrap(), "https://example.com");
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.url, "https://example.com");
+        assert!(!browser.can_go_back);
+        assert!(browser.can_go_forward);
+    }
+
+    #[test]
+    fn test_on_go_forward() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser(
+            "tab-1".to_string(),
+            "https://example.com".to_string(),
+        ).unwrap();
+        
+        // Navigate to a new URL
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        
+        // Go back
+        manager.on_go_back("tab-1").unwrap();
+        
+        // Go forward
+        let result = manager.on_go_forward("tab-1").unwrap().map(|t| t.url);
+        assert!(result.is_some());
+        assert_eq!(result.unwrap(), "https://google.com");
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.url, "https://google.com");
+        assert!(browser.can_go_back);
+        assert!(!browser.can_go_forward);
+    }
+
+    #[test]
+    fn test_navigation_history_consistency() {
+        // Property 1: Navigation History Back/Forward Consistency
+        // For any sequence of URL navigations, clicking back should return to the previous URL,
+        // and clicking forward after back should return to the URL we came from.
+        
+        let manager = CefBrowserManager::new();
+        manager.register_browser(
+            "tab-1".to_string(),
+            "https://example.com".to_string(),
+        ).unwrap();
+        
+        // Navigate through multiple URLs
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://github.com".to_string()).unwrap();
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.url, "https://github.com");
+        assert_eq!(browser.history.len(), 3);
+        
+        // Go back twice
+        let back1 = manager.on_go_back("tab-1").unwrap().map(|t| t.url);
+        assert_eq!(back1, Some("https://google.com".to_string()));
+        
+        let back2 = manager.on_go_back("tab-1").unwrap().map(|t| t.url);
+        assert_eq!(back2, Some("https://example.com".to_string()));
+        
+        // Go forward twice
+        let forward1 = manager.on_go_forward("tab-1").unwrap().map(|t| t.url);
+        assert_eq!(forward1, Some("https://google.com".to_string()));
+        
+        let forward2 = manager.on_go_forward("tab-1").unwrap().map(|t| t.url);
+        assert_eq!(forward2, Some("https://github.com".to_string()));
+    }
+
+    #[test]
+    fn test_cef_instance_pool_register() {
+        let pool = CefInstancePool::new();
+        let result = pool.register_instance(
+            "tab-1".to_string(),
+            0.0,
+            0.0,
+            800.0,
+            600.0,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_cef_instance_pool_get() {
+        let pool = CefInstancePool::new();
+        pool.register_instance(
+            "tab-1".to_string(),
+            0.0,
+            0.0,
+            800.0,
+            600.0,
+        ).unwrap();
+        
+        let instance = pool.get_instance("tab-1").unwrap();
+        assert!(instance.is_some());
+        assert_eq!(instance.unwrap().tab_id, "tab-1");
+    }
+
+    #[test]
+    fn test_cef_instance_pool_unregister() {
+        let pool = CefInstancePool::new();
+        pool.register_instance(
+            "tab-1".to_string(),
+            0.0,
+            0.0,
+            800.0,
+            600.0,
+        ).unwrap();
+        
+        let result = pool.unregister_instance("tab-1");
+        assert!(result.is_ok());
+        
+        let instance = pool.get_instance("tab-1").unwrap();
+        assert!(instance.is_none());
+    }
+
+    #[test]
+    fn test_cef_instance_pool_show_instance() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        
+        // Show tab-2 (should hide tab-1)
+        pool.show_instance("tab-2").unwrap();
+        
+        let tab1 = pool.get_instance("tab-1").unwrap().unwrap();
+        let tab2 = pool.get_instance("tab-2").unwrap().unwrap();
+        
+        assert!(!tab1.is_visible);
+        assert!(tab2.is_visible);
+    }
+
+    #[test]
+    fn test_cef_instance_pool_hide_instance() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        
+        pool.hide_instance("tab-1").unwrap();
+        
+        let instance = pool.get_instance("tab-1").unwrap().unwrap();
+        assert!(!instance.is_visible);
+    }
+
+    #[test]
+    fn test_cef_instance_pool_update_bounds() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        
+        pool.update_instance_bounds("tab-1", 100.0, 100.0, 1024.0, 768.0).unwrap();
+        
+        let instance = pool.get_instance("tab-1").unwrap().unwrap();
+        assert_eq!(instance.x, 100.0);
+        assert_eq!(instance.y, 100.0);
+        assert_eq!(instance.width, 1024.0);
+        assert_eq!(instance.height, 768.0);
+    }
+
+    #[test]
+    fn test_cef_instance_pool_get_all_instances() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        
+        let instances = pool.get_all_instances().unwrap();
+        assert_eq!(instances.len(), 2);
+    }
+
+    #[test]
+    fn test_cef_instance_pool_get_visible_count() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        
+        // Initially both are visible
+        assert_eq!(pool.get_visible_instance_count().unwrap(), 2);
+        
+        // Show tab-1 (hides tab-2)
+        pool.show_instance("tab-1").unwrap();
+        assert_eq!(pool.get_visible_instance_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_cef_instance_pool_get_count() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        
+        assert_eq!(pool.get_instance_count().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_cef_instance_pool_sorted_by_z() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-3".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        
+        pool.set_z_index("tab-1", 5).unwrap();
+        pool.set_z_index("tab-2", -1).unwrap();
+        pool.set_z_index("tab-3", 2).unwrap();
+        
+        let sorted: Vec<_> = pool.get_instances_sorted_by_z().unwrap()
+            .into_iter()
+            .map(|i| i.tab_id)
+            .collect();
+        assert_eq!(sorted, vec!["tab-2", "tab-3", "tab-1"]);
+        
+        // Hidden instances are excluded
+        pool.hide_instance("tab-3").unwrap();
+        assert_eq!(pool.get_instances_sorted_by_z().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_cef_instance_pool_show_keeping_others() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-3".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        
+        pool.show_instance_keeping_others("tab-1").unwrap();
+        
+        assert_eq!(pool.get_visible_instance_count().unwrap(), 3);
+        let top = pool.get_instances_sorted_by_z().unwrap().pop().unwrap();
+        assert_eq!(top.tab_id, "tab-1");
+        
+        // show_instance is still exclusive
+        pool.show_instance("tab-2").unwrap();
+        assert_eq!(pool.get_visible_instance_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_tab_independence() {
+        // Property 2: Tab Independence
+        // For any set of browser tabs, navigating in one tab should not affect the URL or state of other tabs.
+        
+        let manager = CefBrowserManager::new();
+        
+        // Create two tabs
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.register_browser("tab-2".to_string(), "https://google.com".to_string()).unwrap();
+        
+        // Navigate in tab-1
+        manager.on_url_change("tab-1", "https://github.com".to_string()).unwrap();
+        
+        // Check that tab-2 is unaffected
+        let tab1 = manager.get_browser("tab-1").unwrap().unwrap();
+        let tab2 = manager.get_browser("tab-2").unwrap().unwrap();
+        
+        assert_eq!(tab1.url, "https://github.com");
+        assert_eq!(tab2.url, "https://google.com");
+    }
+
+    #[test]
+    fn test_get_history() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        
+        // The initial URL is the first entry, not an empty history
+        let fresh = manager.get_history("tab-1").unwrap().unwrap();
+        assert_eq!(fresh.entries.len(), 1);
+        assert_eq!(fresh.index, 0);
+        
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://github.com".to_string()).unwrap();
+        manager.on_go_back("tab-1").unwrap();
+        
+        let view = manager.get_history("tab-1").unwrap().unwrap();
+        assert_eq!(view.entries.len(), 3);
+        assert_eq!(view.index, 1);
+        assert_eq!(view.entries[2].url, "https://github.com");
+        
+        assert!(manager.get_history("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_go_to_history_index() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://github.com".to_string()).unwrap();
+        
+        let result = manager.go_to_history_index("tab-1", 0).unwrap();
+        assert_eq!(result, Some("https://example.com".to_string()));
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.url, "https://example.com");
+        assert_eq!(browser.history_index, 0);
+        assert!(!browser.can_go_back);
+        assert!(browser.can_go_forward);
+    }
+
+    #[test]
+    fn test_go_to_history_index_out_of_range() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        
+        assert_eq!(manager.go_to_history_index("tab-1", 5).unwrap(), None);
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.url, "https://google.com");
+        assert_eq!(browser.history_index, 1);
+        assert!(browser.can_go_back);
+    }
+
+    #[test]
+    fn test_zoom_level() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        assert_eq!(manager.get_zoom("tab-1").unwrap(), Some(1.0));
+        
+        assert_eq!(manager.set_zoom("tab-1", 1.5).unwrap(), Some(1.5));
+        assert_eq!(manager.get_zoom("tab-1").unwrap(), Some(1.5));
+        
+        assert!(manager.set_zoom("missing", 1.5).unwrap().is_none());
+        assert!(manager.get_zoom("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_zoom_level_is_clamped() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        
+        assert_eq!(manager.set_zoom("tab-1", 10.0).unwrap(), Some(MAX_ZOOM_LEVEL));
+        assert_eq!(manager.set_zoom("tab-1", 0.0).unwrap(), Some(MIN_ZOOM_LEVEL));
+        assert_eq!(manager.set_zoom("tab-1", f64::NAN).unwrap(), Some(1.0));
+        assert_eq!(manager.set_zoom("tab-1", f64::INFINITY).unwrap(), Some(MAX_ZOOM_LEVEL));
+    }
+
+    #[test]
+    fn test_session_restores_zoom() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.set_zoom("tab-1", 2.0).unwrap();
+        manager.save_session(&path).unwrap();
+
+        let restored = CefBrowserManager::new();
+        restored.load_session(&path).unwrap();
+        assert_eq!(restored.get_zoom("tab-1").unwrap(), Some(2.0));
+    }
+
+    #[test]
+    fn test_scroll_position_restored_on_back_forward() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        assert!(manager.save_scroll("tab-1", 10.0, 250.0).unwrap());
+        
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.history[1].scroll_x, 0.0);
+        assert_eq!(browser.history[1].scroll_y, 0.0);
+        manager.save_scroll("tab-1", 0.0, 900.0).unwrap();
+        
+        let back = manager.on_go_back("tab-1").unwrap().unwrap();
+        assert_eq!(back.url, "https://example.com");
+        assert_eq!(back.scroll_x, 10.0);
+        assert_eq!(back.scroll_y, 250.0);
+        
+        let forward = manager.on_go_forward("tab-1").unwrap().unwrap();
+        assert_eq!(forward.url, "https://google.com");
+        assert_eq!(forward.scroll_y, 900.0);
+        
+        assert!(!manager.save_scroll("missing", 0.0, 0.0).unwrap());
+    }
+
+    #[test]
+    fn test_history_entry_without_scroll_deserializes() {
+        let json = r#"{"url":"https://example.com","title":"Example","timestamp":1701234567890}"#;
+        let entry: NavigationHistoryEntry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry.scroll_x, 0.0);
+        assert_eq!(entry.scroll_y, 0.0);
+    }
+
+    #[test]
+    fn test_restore_last_closed() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        manager.unregister_browser("tab-1").unwrap();
+        assert!(manager.get_browser("tab-1").unwrap().is_none());
+        
+        let restored = manager.restore_last_closed().unwrap().unwrap();
+        assert_eq!(restored.tab_id, "tab-1");
+        assert_eq!(restored.history.len(), 2);
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.url, "https://google.com");
+        assert!(browser.can_go_back);
+    }
+
+    #[test]
+    fn test_restore_last_closed_empty_stack() {
+        let manager = CefBrowserManager::new();
+        assert!(manager.restore_last_closed().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_restore_last_closed_id_collision() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.unregister_browser("tab-1").unwrap();
+        manager.register_browser("tab-1".to_string(), "https://google.com".to_string()).unwrap();
+        
+        let restored = manager.restore_last_closed().unwrap().unwrap();
+        assert_eq!(restored.tab_id, "tab-1-1");
+        assert_eq!(restored.url, "https://example.com");
+        assert_eq!(manager.get_browser("tab-1").unwrap().unwrap().url, "https://google.com");
+    }
+
+    #[test]
+    fn test_closed_stack_is_bounded() {
+        let manager = CefBrowserManager::new();
+        for i in 0..(CLOSED_TAB_STACK_CAP + 5) {
+            let tab_id = format!("tab-{}", i);
+            manager.register_browser(tab_id.clone(), "https://example.com".to_string()).unwrap();
+            manager.unregister_browser(&tab_id).unwrap();
+        }
+        
+        let mut restored = 0;
+        while manager.restore_last_closed().unwrap().is_some() {
+            restored += 1;
+        }
+        assert_eq!(restored, CLOSED_TAB_STACK_CAP);
+    }
+
+    #[test]
+    fn test_set_user_agent() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        assert!(manager.get_browser("tab-1").unwrap().unwrap().user_agent.is_none());
+        
+        assert!(manager.set_user_agent("tab-1", Some("LuminaBot/1.0".to_string())).unwrap());
+        assert_eq!(
+            manager.get_browser("tab-1").unwrap().unwrap().user_agent,
+            Some("LuminaBot/1.0".to_string())
+        );
+        
+        assert!(manager.set_user_agent("tab-1", None).unwrap());
+        assert!(manager.get_browser("tab-1").unwrap().unwrap().user_agent.is_none());
+        
+        assert!(!manager.set_user_agent("missing", None).unwrap());
+    }
+
+    #[test]
+    fn test_session_restores_user_agent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.set_user_agent("tab-1", Some("LuminaBot/1.0".to_string())).unwrap();
+        manager.save_session(&path).unwrap();
+
+        let restored = CefBrowserManager::new();
+        restored.load_session(&path).unwrap();
+        assert_eq!(
+            restored.get_browser("tab-1").unwrap().unwrap().user_agent,
+            Some("LuminaBot/1.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com/0".to_string()).unwrap();
+        
+        for i in 1..=600 {
+            manager.on_url_change("tab-1", format!("https://example.com/{}", i)).unwrap();
+        }
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.history.len(), DEFAULT_MAX_HISTORY_LEN);
+        assert_eq!(browser.history_index, DEFAULT_MAX_HISTORY_LEN - 1);
+        assert_eq!(browser.url, "https://example.com/600");
+        assert_eq!(browser.history[0].url, "https://example.com/101");
+        assert!(browser.can_go_back);
+        assert!(!browser.can_go_forward);
+    }
+
+    #[test]
+    fn test_with_max_history_trims_oldest() {
+        let manager = CefBrowserManager::with_max_history(3);
+        manager.register_browser("tab-1".to_string(), "https://a.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://b.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://c.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://d.com".to_string()).unwrap();
+        
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        let urls: Vec<_> = browser.history.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://b.com", "https://c.com", "https://d.com"]);
+        
+        assert_eq!(manager.on_go_back("tab-1").unwrap().map(|t| t.url), Some("https://c.com".to_string()));
+        assert_eq!(manager.on_go_back("tab-1").unwrap().map(|t| t.url), Some("https://b.com".to_string()));
+        assert_eq!(manager.on_go_back("tab-1").unwrap().map(|t| t.url), None);
+    }
+
+    #[test]
+    fn test_session_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://github.com".to_string()).unwrap();
+        manager.on_go_back("tab-1").unwrap();
+        manager.register_browser("tab-2".to_string(), "https://rust-lang.org".to_string()).unwrap();
+        manager.save_session(&path).unwrap();
+
+        let restored = CefBrowserManager::new();
+        let browsers = restored.load_session(&path).unwrap();
+        assert_eq!(browsers.len(), 2);
+
+        let tab1 = restored.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(tab1.url, "https://google.com");
+        assert_eq!(tab1.history.len(), 3);
+        assert_eq!(tab1.history_index, 1);
+        assert!(tab1.can_go_back);
+        assert!(tab1.can_go_forward);
+
+        // Back/forward consistency still holds after restore
+        assert_eq!(restored.on_go_back("tab-1").unwrap().map(|t| t.url), Some("https://example.com".to_string()));
+        assert_eq!(restored.on_go_forward("tab-1").unwrap().map(|t| t.url), Some("https://google.com".to_string()));
+        assert_eq!(restored.on_go_forward("tab-1").unwrap().map(|t| t.url), Some("https://github.com".to_string()));
+    }
+
+    #[test]
+    fn test_session_load_recomputes_nav_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        let mut info = manager.get_browser("tab-1").unwrap().unwrap();
+        info.history_index = 42;
+        info.can_go_back = false;
+        info.can_go_forward = true;
+        manager.update_browser("tab-1", info).unwrap();
+        manager.save_session(&path).unwrap();
+
+        let restored = CefBrowserManager::new();
+        restored.load_session(&path).unwrap();
+        let tab1 = restored.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(tab1.history_index, 1);
+        assert_eq!(tab1.url, "https://google.com");
+        assert!(tab1.can_go_back);
+        assert!(!tab1.can_go_forward);
+    }
+
+    #[test]
+    fn test_session_load_missing_or_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = CefBrowserManager::new();
+
+        let missing = manager.load_session(&dir.path().join("missing.json")).unwrap();
+        assert!(missing.is_empty());
+
+        let corrupt = dir.path().join("corrupt.json");
+        std::fs::write(&corrupt, "{ not valid json").unwrap();
+        let loaded = manager.load_session(&corrupt).unwrap();
+        assert!(loaded.is_empty());
+        assert!(manager.get_all_browsers().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pool_recovers_from_poisoned_lock() {
+        let pool = std::sync::Arc::new(CefInstancePool::new());
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+
+        let poisoner = std::sync::Arc::clone(&pool);
+        let result = std::thread::spawn(move || {
+            let _guard = poisoner.instances.lock().unwrap();
+            panic!("poison the instance pool");
+        }).join();
+        assert!(result.is_err());
+        assert!(pool.instances.is_poisoned());
+
+        let instance = pool.get_instance("tab-1").unwrap();
+        assert!(instance.is_some());
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        assert!(pool.get_instance("tab-2").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_manager_recovers_from_poisoned_lock() {
+        let manager = std::sync::Arc::new(CefBrowserManager::new());
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+
+        let poisoner = std::sync::Arc::clone(&manager);
+        let result = std::thread::spawn(move || {
+            let _guard = poisoner.browsers.lock().unwrap();
+            panic!("poison the browser manager");
+        }).join();
+        assert!(result.is_err());
+
+        let browser = manager.get_browser("tab-1").unwrap();
+        assert!(browser.is_some());
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(normalize_url("example.com").unwrap(), "https://example.com");
+        assert_eq!(normalize_url("  https://x.com ").unwrap(), "https://x.com");
+        assert_eq!(normalize_url("HTTP://Example.COM/Path?Q=1").unwrap(), "http://example.com/Path?Q=1");
+        assert_eq!(normalize_url("localhost:3000/app").unwrap(), "https://localhost:3000/app");
+    }
+
+    #[test]
+    fn test_normalize_url_rejects_unsupported() {
+        assert!(matches!(normalize_url("ftp://example.com"), Err(AppError::InvalidPath(_))));
+        assert!(normalize_url("javascript:alert(1)").is_err());
+        assert!(normalize_url("file:///etc/passwd").is_err());
+        assert!(normalize_url("   ").is_err());
+        assert!(normalize_url("https://").is_err());
+    }
+
+    #[test]
+    fn test_local_navigation_allowed() {
+        let dir = tempfile::tempdir().unwrap();
+        let page = dir.path().join("export page.html");
+        std::fs::write(&page, "<h1>Export</h1>").unwrap();
+        let config = BrowserConfig::new();
+        config.set_allow_local(true);
+
+        let url = config.resolve_navigation_url(page.to_str().unwrap()).unwrap();
+        assert!(url.starts_with("file:///"));
+        assert!(url.ends_with("/export%20page.html"));
+        // An existing file:// URL round-trips
+        assert_eq!(config.resolve_navigation_url(&url).unwrap(), url);
+        assert_eq!(config.resolve_navigation_url("example.com").unwrap(), "https://example.com");
+    }
+
+    #[test]
+    fn test_local_navigation_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.html");
+        let config = BrowserConfig::new();
+        config.set_allow_local(true);
+
+        let result = config.resolve_navigation_url(missing.to_str().unwrap());
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_local_navigation_disallowed() {
+        let dir = tempfile::tempdir().unwrap();
+        let page = dir.path().join("page.html");
+        std::fs::write(&page, "<p>hi</p>").unwrap();
+        let config = BrowserConfig::new();
+        assert!(!config.allow_local());
+
+        assert!(matches!(config.resolve_navigation_url(page.to_str().unwrap()), Err(AppError::InvalidPath(_))));
+        let url = format!("file://{}", page.display());
+        assert!(matches!(config.resolve_navigation_url(&url), Err(AppError::InvalidPath(_))));
+    }
+
+    #[test]
+    fn test_favicon_reused_for_same_host() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+
+        assert!(manager.on_favicon_change("tab-1", "data:image/png;base64,AAAA".to_string()).unwrap());
+        assert!(!manager.on_favicon_change("missing", "x".to_string()).unwrap());
+
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert!(browser.favicon.is_none());
+
+        manager.on_url_change("tab-1", "https://example.com/other".to_string()).unwrap();
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.favicon.as_deref(), Some("data:image/png;base64,AAAA"));
+
+        manager.clear_favicon_cache();
+        assert!(manager.cached_favicon("https://example.com").is_none());
+    }
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(url_host("https://user@www.example.com:443/x").as_deref(), Some("www.example.com"));
+        assert_eq!(url_host("example.com/path").as_deref(), Some("example.com"));
+        assert_eq!(url_host("https://"), None);
+    }
+
+    #[test]
+    fn test_hide_group_leaves_other_groups_untouched() {
+        let pool = CefInstancePool::new();
+        for tab_id in ["tab-1", "tab-2", "tab-3", "tab-4", "tab-5"] {
+            pool.register_instance(tab_id.to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        }
+        pool.set_group("tab-1", Some("work".to_string())).unwrap();
+        pool.set_group("tab-2", Some("work".to_string())).unwrap();
+        pool.set_group("tab-3", Some("research".to_string())).unwrap();
+        pool.set_group("tab-4", Some("research".to_string())).unwrap();
+
+        let work: Vec<String> = pool.get_instances_in_group("work").unwrap()
+            .into_iter().map(|i| i.tab_id).collect();
+        assert_eq!(work, vec!["tab-1".to_string(), "tab-2".to_string()]);
+
+        pool.hide_group("work").unwrap();
+        assert!(!pool.get_instance("tab-1").unwrap().unwrap().is_visible);
+        assert!(!pool.get_instance("tab-2").unwrap().unwrap().is_visible);
+        assert!(pool.get_instance("tab-3").unwrap().unwrap().is_visible);
+        assert!(pool.get_instance("tab-4").unwrap().unwrap().is_visible);
+        assert!(pool.get_instance("tab-5").unwrap().unwrap().is_visible);
+
+        pool.show_group("work").unwrap();
+        assert_eq!(pool.get_visible_instance_count().unwrap(), 5);
+
+        pool.set_group("tab-2", None).unwrap();
+        assert_eq!(pool.get_instances_in_group("work").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_list_tabs_sorted() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-3".to_string(), "https://c.com".to_string()).unwrap();
+        manager.register_browser("tab-1".to_string(), "https://a.com".to_string()).unwrap();
+        manager.register_browser("tab-2".to_string(), "https://b.com".to_string()).unwrap();
+        manager.on_title_change("tab-1", "A".to_string()).unwrap();
+        manager.on_loading_state_change("tab-1", false).unwrap();
+        manager.on_url_change("tab-2", "https://b.com/next".to_string()).unwrap();
+
+        let tabs = manager.list_tabs().unwrap();
+        let ids: Vec<&str> = tabs.iter().map(|t| t.tab_id.as_str()).collect();
+        assert_eq!(ids, vec!["tab-1", "tab-2", "tab-3"]);
+
+        assert_eq!(tabs[0], TabSummary {
+            tab_id: "tab-1".to_string(),
+            url: "https://a.com".to_string(),
+            title: "A".to_string(),
+            is_loading: false,
+            can_go_back: false,
+            can_go_forward: false,
+        });
+        assert_eq!(tabs[1].url, "https://b.com/next");
+        assert!(tabs[1].can_go_back);
+        assert!(tabs[2].is_loading);
+    }
+
+    #[test]
+    fn test_load_duration() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        assert_eq!(manager.get_last_load_duration("tab-1").unwrap(), None);
+
+        manager.mark_load_started("tab-1").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        manager.on_loading_state_change("tab-1", false).unwrap();
+
+        let duration = manager.get_last_load_duration("tab-1").unwrap();
+        assert!(duration.is_some());
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert!(browser.load_finished_ms >= browser.load_started_ms);
+
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        manager.on_loading_state_change("tab-1", true).unwrap();
+        assert_eq!(manager.get_last_load_duration("tab-1").unwrap(), None);
+        assert_eq!(manager.get_last_load_duration("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_check_timeouts() {
+        let manager = CefBrowserManager::new().with_load_timeout(1_000);
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.register_browser("tab-2".to_string(), "https://google.com".to_string()).unwrap();
+        manager.on_loading_state_change("tab-2", false).unwrap();
+
+        let started = manager.get_browser("tab-1").unwrap().unwrap().load_started_ms;
+        assert!(manager.check_timeouts(started + 500).is_empty());
+
+        let timed_out = manager.check_timeouts(started + 5_000);
+        assert_eq!(timed_out, vec!["tab-1".to_string()]);
+        assert!(!manager.get_browser("tab-1").unwrap().unwrap().is_loading);
+
+        // Already stopped, so a second poll reports nothing
+        assert!(manager.check_timeouts(started + 10_000).is_empty());
+    }
+
+    #[test]
+    fn test_update_many_bounds() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-3".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+
+        let mut events = pool.subscribe();
+        let outcome = pool.update_many_bounds(vec![
+            ("tab-1".to_string(), 10.0, 20.0, 1024.0, 768.0),
+            ("tab-2".to_string(), 10.0, 20.0, 0.0, 768.0),
+            ("tab-3".to_string(), 0.0, 0.0, 800.0, 600.0),
+            ("missing".to_string(), 5.0, 5.0, 640.0, 480.0),
+        ]).unwrap();
+
+        assert_eq!(outcome.failed, vec!["tab-2".to_string(), "missing".to_string()]);
+        // tab-3 kept its bounds, so only tab-1 changed and was announced
+        assert_eq!(outcome.changed, vec!["tab-1".to_string()]);
+        assert!(matches!(events.try_recv(), Ok(CefEvent::BoundsChanged { tab_id, .. }) if tab_id == "tab-1"));
+        assert!(events.try_recv().is_err());
+
+        let tab1 = pool.get_instance("tab-1").unwrap().unwrap();
+        assert_eq!((tab1.x, tab1.y, tab1.width, tab1.height), (10.0, 20.0, 1024.0, 768.0));
+        let tab2 = pool.get_instance("tab-2").unwrap().unwrap();
+        assert_eq!((tab2.width, tab2.height), (800.0, 600.0));
+        let tab3 = pool.get_instance("tab-3").unwrap().unwrap();
+        assert_eq!((tab3.width, tab3.height), (800.0, 600.0));
+    }
+
+    #[test]
+    fn test_private_tab_keeps_single_history_entry() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser_private("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://rust-lang.org".to_string()).unwrap();
+
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert!(browser.is_private);
+        assert_eq!(browser.history.len(), 1);
+        assert_eq!(browser.history[0].url, "https://rust-lang.org");
+        assert_eq!(browser.url, "https://rust-lang.org");
+        assert!(!browser.can_go_back);
+
+        manager.unregister_browser("tab-1").unwrap();
+        assert!(manager.restore_last_closed().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_private_tab_excluded_from_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.register_browser_private("tab-2".to_string(), "https://secret.com".to_string()).unwrap();
+        manager.save_session(&path).unwrap();
+
+        let loaded = CefBrowserManager::new().load_session(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].tab_id, "tab-1");
+    }
+
+    #[test]
+    fn test_load_progress() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+
+        assert_eq!(manager.on_load_progress("tab-1", 0.0).unwrap(), Some(0.0));
+        assert_eq!(manager.on_load_progress("tab-1", 0.5).unwrap(), Some(0.5));
+        assert_eq!(manager.on_load_progress("tab-1", 1.0).unwrap(), Some(1.0));
+        assert_eq!(manager.on_load_progress("tab-1", 1.5).unwrap(), Some(1.0));
+        assert_eq!(manager.on_load_progress("tab-1", -0.2).unwrap(), Some(0.0));
+        assert_eq!(manager.on_load_progress("missing", 0.5).unwrap(), None);
+
+        manager.on_load_progress("tab-1", 0.4).unwrap();
+        manager.on_loading_state_change("tab-1", false).unwrap();
+        assert_eq!(manager.get_browser("tab-1").unwrap().unwrap().load_progress, 1.0);
+
+        manager.mark_load_started("tab-1").unwrap();
+        assert_eq!(manager.get_browser("tab-1").unwrap().unwrap().load_progress, 0.0);
+    }
+
+    #[test]
+    fn test_mute_and_audible_tabs() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://youtube.com".to_string()).unwrap();
+        manager.register_browser("tab-2".to_string(), "https://bilibili.com".to_string()).unwrap();
+        manager.register_browser("tab-3".to_string(), "https://example.com".to_string()).unwrap();
+
+        assert!(manager.set_muted("tab-1", true).unwrap());
+        assert!(manager.get_browser("tab-1").unwrap().unwrap().is_muted);
+        assert!(manager.set_muted("tab-1", false).unwrap());
+        assert!(!manager.get_browser("tab-1").unwrap().unwrap().is_muted);
+        assert!(!manager.set_muted("missing", true).unwrap());
+
+        manager.on_audio_state_change("tab-2", true).unwrap();
+        manager.on_audio_state_change("tab-1", true).unwrap();
+        assert_eq!(manager.list_audible_tabs().unwrap(), vec!["tab-1".to_string(), "tab-2".to_string()]);
+
+        manager.on_audio_state_change("tab-1", false).unwrap();
+        assert_eq!(manager.list_audible_tabs().unwrap(), vec!["tab-2".to_string()]);
+    }
+
+    #[test]
+    fn test_reorder_instance() {
+        let pool = CefInstancePool::new();
+        for tab_id in ["tab-a", "tab-b", "tab-c", "tab-d"] {
+            pool.register_instance(tab_id.to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        }
+
+        let ordered_ids = |pool: &CefInstancePool| -> Vec<String> {
+            pool.get_instances_ordered().unwrap().into_iter().map(|i| i.tab_id).collect()
+        };
+        let orders = |pool: &CefInstancePool| -> Vec<usize> {
+            pool.get_instances_ordered().unwrap().into_iter().map(|i| i.order).collect()
+        };
+
+        // Move tab-d from index 3 to index 1
+        pool.reorder_instance("tab-d", 1).unwrap();
+        assert_eq!(ordered_ids(&pool), vec!["tab-a", "tab-d", "tab-b", "tab-c"]);
+        assert_eq!(orders(&pool), vec![0, 1, 2, 3]);
+
+        // And back towards the end
+        pool.reorder_instance("tab-d", 10).unwrap();
+        assert_eq!(ordered_ids(&pool), vec!["tab-a", "tab-b", "tab-c", "tab-d"]);
+        assert_eq!(orders(&pool), vec![0, 1, 2, 3]);
+
+        pool.unregister_instance("tab-b").unwrap();
+        assert_eq!(ordered_ids(&pool), vec!["tab-a", "tab-c", "tab-d"]);
+        assert_eq!(orders(&pool), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_empty_history_does_not_panic() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+
+        let mut info = manager.get_browser("tab-1").unwrap().unwrap();
+        info.history.clear();
+        info.history_index = 3;
+        manager.update_browser("tab-1", info.clone()).unwrap();
+
+        assert!(manager.on_go_back("tab-1").unwrap().is_none());
+        assert!(manager.on_go_forward("tab-1").unwrap().is_none());
+
+        info.history_index = 0;
+        manager.update_browser("tab-1", info).unwrap();
+        assert!(manager.on_go_back("tab-1").unwrap().is_none());
+        assert!(manager.on_go_forward("tab-1").unwrap().is_none());
+
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.history.len(), 1);
+        assert_eq!(browser.history_index, 0);
+        assert_eq!(browser.url, "https://google.com");
+        assert!(!browser.can_go_back);
+    }
+
+    #[test]
+    fn test_rapid_title_changes_coalesce() {
+        let manager = CefBrowserManager::new().with_min_title_interval(1000);
+        manager.register_browser("tab-1".to_string(), "https://mail.example.com".to_string()).unwrap();
+
+        assert!(manager.on_title_change_at("tab-1", "(1) Inbox".to_string(), 10_000).unwrap());
+        assert!(!manager.on_title_change_at("tab-1", "(2) Inbox".to_string(), 10_100).unwrap());
+        assert!(!manager.on_title_change_at("tab-1", "(3) Inbox".to_string(), 10_200).unwrap());
+        assert_eq!(manager.get_browser("tab-1").unwrap().unwrap().title, "(1) Inbox");
+
+        let flushed = manager.on_loading_state_change("tab-1", false).unwrap();
+        assert_eq!(flushed, Some("(3) Inbox".to_string()));
+
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.title, "(3) Inbox");
+        assert_eq!(browser.history[browser.history_index].title, "(3) Inbox");
+        assert!(browser.pending_title.is_none());
+
+        // Once loading has settled every change applies
+        let now = browser.last_title_update_ms;
+        assert!(manager.on_title_change_at("tab-1", "(4) Inbox".to_string(), now + 1).unwrap());
+        assert_eq!(manager.on_loading_state_change("tab-1", false).unwrap(), None);
+    }
+
+    #[test]
+    fn test_load_error_set_and_cleared() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://missing.example".to_string()).unwrap();
+
+        assert!(manager.on_load_error("tab-1", -105, "ERR_NAME_NOT_RESOLVED".to_string(), "https://missing.example".to_string()).unwrap());
+        assert!(!manager.on_load_error("tab-2", -105, String::new(), String::new()).unwrap());
+
+        let expected = LoadError {
+            code: -105,
+            description: "ERR_NAME_NOT_RESOLVED".to_string(),
+            failed_url: "https://missing.example".to_string(),
+        };
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert!(!browser.is_loading);
+        assert_eq!(browser.last_error, Some(expected.clone()));
+
+        // The loading-finished notification of the failed load keeps the error
+        manager.on_loading_state_change("tab-1", false).unwrap();
+        assert_eq!(manager.get_browser("tab-1").unwrap().unwrap().last_error, Some(expected));
+
+        // The next successful load clears it
+        manager.on_loading_state_change("tab-1", true).unwrap();
+        manager.on_loading_state_change("tab-1", false).unwrap();
+        assert!(manager.get_browser("tab-1").unwrap().unwrap().last_error.is_none());
+    }
+
+    #[test]
+    fn test_instance_at_point() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 400.0, 300.0, 400.0, 300.0).unwrap();
+        pool.register_instance("tab-3".to_string(), 0.0, 0.0, 100.0, 100.0).unwrap();
+        pool.set_z_index("tab-1", 1).unwrap();
+        pool.set_z_index("tab-2", 2).unwrap();
+        pool.hide_instance("tab-3").unwrap();
+
+        assert_eq!(pool.instance_at_point(100.0, 100.0).unwrap(), Some("tab-1".to_string()));
+        assert_eq!(pool.instance_at_point(500.0, 400.0).unwrap(), Some("tab-2".to_string()));
+        // Shared boundary goes to the higher instance
+        assert_eq!(pool.instance_at_point(400.0, 300.0).unwrap(), Some("tab-2".to_string()));
+        // Hidden instances don't count
+        assert_eq!(pool.instance_at_point(50.0, 50.0).unwrap(), Some("tab-1".to_string()));
+
+        pool.set_z_index("tab-1", 3).unwrap();
+        assert_eq!(pool.instance_at_point(500.0, 400.0).unwrap(), Some("tab-1".to_string()));
+
+        assert_eq!(pool.instance_at_point(900.0, 50.0).unwrap(), None);
+        assert_eq!(pool.instance_at_point(-1.0, 50.0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_instance_bounds_validation() {
+        let pool = CefInstancePool::new();
+
+        let result = pool.register_instance("tab-1".to_string(), 0.0, 0.0, f64::NAN, 600.0);
+        assert!(matches!(result, Err(AppError::InvalidPath(_))));
+        assert!(pool.get_instance("tab-1").unwrap().is_none());
+
+        let result = pool.register_instance("tab-1".to_string(), f64::INFINITY, 0.0, 800.0, 600.0);
+        assert!(matches!(result, Err(AppError::InvalidPath(_))));
+        assert!(pool.register_instance("tab-1".to_string(), 0.0, 0.0, 0.0, 600.0).is_err());
+
+        // Off-screen positions are fine
+        pool.register_instance("tab-1".to_string(), -200.0, -50.0, 800.0, 600.0).unwrap();
+
+        assert!(pool.update_instance_bounds("tab-1", f64::NEG_INFINITY, 0.0, 800.0, 600.0).is_err());
+        assert!(pool.update_instance_bounds("tab-1", 0.0, 0.0, 800.0, -1.0).is_err());
+        let instance = pool.get_instance("tab-1").unwrap().unwrap();
+        assert_eq!((instance.x, instance.y, instance.width, instance.height), (-200.0, -50.0, 800.0, 600.0));
+
+        let outcome = pool.update_many_bounds(vec![("tab-1".to_string(), 0.0, f64::NAN, 800.0, 600.0)]).unwrap();
+        assert_eq!(outcome.failed, vec!["tab-1".to_string()]);
+    }
+
+    #[test]
+    fn test_navigate_offset() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://a.com".to_string()).unwrap();
+        for url in ["https://b.com", "https://c.com", "https://d.com", "https://e.com"] {
+            manager.on_url_change("tab-1", url.to_string()).unwrap();
+        }
+
+        assert_eq!(manager.navigate_offset("tab-1", -3).unwrap(), Some("https://b.com".to_string()));
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.history_index, 1);
+        assert!(browser.can_go_back);
+        assert!(browser.can_go_forward);
+
+        assert_eq!(manager.navigate_offset("tab-1", 2).unwrap(), Some("https://d.com".to_string()));
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.url, "https://d.com");
+        assert!(browser.can_go_back);
+        assert!(browser.can_go_forward);
+
+        // Clamped to the ends of history
+        assert_eq!(manager.navigate_offset("tab-1", 10).unwrap(), Some("https://e.com".to_string()));
+        assert!(!manager.get_browser("tab-1").unwrap().unwrap().can_go_forward);
+        assert_eq!(manager.navigate_offset("tab-1", 1).unwrap(), None);
+        assert_eq!(manager.navigate_offset("tab-1", 0).unwrap(), None);
+        assert_eq!(manager.navigate_offset("tab-1", i32::MIN).unwrap(), Some("https://a.com".to_string()));
+        assert!(!manager.get_browser("tab-1").unwrap().unwrap().can_go_back);
+
+        assert_eq!(manager.navigate_offset("missing", -1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_search_history_dedup_and_recency() {
+        let manager = CefBrowserManager::new();
+        let entry = |url: &str, title: &str, timestamp: u64| NavigationHistoryEntry {
+            url: url.to_string(),
+            title: title.to_string(),
+            timestamp,
+            scroll_x: 0.0,
+            scroll_y: 0.0,
+        };
+
+        manager.register_browser("tab-1".to_string(), "https://a.com".to_string()).unwrap();
+        let mut info = manager.get_browser("tab-1").unwrap().unwrap();
+        info.history = vec![
+            entry("https://docs.rs/tauri", "tauri - Rust", 100),
+            entry("https://example.com", "Example Domain", 200),
+        ];
+        manager.update_browser("tab-1", info).unwrap();
+
+        manager.register_browser("tab-2".to_string(), "https://b.com".to_string()).unwrap();
+        let mut info = manager.get_browser("tab-2").unwrap().unwrap();
+        info.history = vec![
+            entry("https://tauri.app", "Tauri Apps", 150),
+            entry("https://docs.rs/tauri", "tauri - Rust", 300),
+        ];
+        manager.update_browser("tab-2", info).unwrap();
+
+        let hits = manager.search_history("TAURI", 10).unwrap();
+        assert_eq!(hits, vec![
+            HistorySearchHit {
+                tab_id: "tab-2".to_string(),
+                url: "https://docs.rs/tauri".to_string(),
+                title: "tauri - Rust".to_string(),
+                timestamp: 300,
+            },
+            HistorySearchHit {
+                tab_id: "tab-2".to_string(),
+                url: "https://tauri.app".to_string(),
+                title: "Tauri Apps".to_string(),
+                timestamp: 150,
+            },
+        ]);
+
+        assert_eq!(manager.search_history("example domain", 10).unwrap().len(), 1);
+        assert_eq!(manager.search_history("tauri", 1).unwrap().len(), 1);
+        assert!(manager.search_history("", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_matches_registered_browsers() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-2".to_string(), "https://b.com".to_string()).unwrap();
+        manager.register_browser("tab-1".to_string(), "https://a.com".to_string()).unwrap();
+        manager.register_browser("tab-3".to_string(), "https://c.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://a.com/docs".to_string()).unwrap();
+        manager.on_url_change("tab-2", "https://b.com/blog".to_string()).unwrap();
+        manager.on_go_back("tab-2").unwrap();
+        manager.unregister_browser("tab-3").unwrap();
+
+        let snapshot = manager.snapshot().unwrap();
+
+        let ids: Vec<&str> = snapshot.iter().map(|b| b.tab_id.as_str()).collect();
+        assert_eq!(ids, vec!["tab-1", "tab-2"]);
+        for tab in &snapshot {
+            let current = manager.get_browser(&tab.tab_id).unwrap().unwrap();
+            assert_eq!(tab.url, current.url);
+            assert_eq!(tab.history_index, current.history_index);
+            assert_eq!(tab.history.len(), current.history.len());
+        }
+        assert_eq!(snapshot[0].url, "https://a.com/docs");
+        assert_eq!(snapshot[1].url, "https://b.com");
+        assert!(snapshot[1].can_go_forward);
+    }
+
+    #[test]
+    fn test_empty_url_uses_home_page() {
+        let config = BrowserConfig::new();
+        assert_eq!(config.resolve_url("  ").unwrap(), DEFAULT_HOME_URL);
+
+        assert_eq!(config.set_home_url("Example.com/start").unwrap(), "https://example.com/start");
+        assert_eq!(config.resolve_url("").unwrap(), "https://example.com/start");
+        assert_eq!(config.resolve_url("rust-lang.org").unwrap(), "https://rust-lang.org");
+
+        assert!(config.set_home_url("javascript:alert(1)").is_err());
+        assert_eq!(config.home_url(), "https://example.com/start");
+        assert_eq!(config.set_home_url("").unwrap(), DEFAULT_HOME_URL);
+    }
+
+    #[test]
+    fn test_internal_pages_pass_validation() {
+        let config = BrowserConfig::new();
+        assert_eq!(config.resolve_url("about:newtab").unwrap(), "about:newtab");
+        assert_eq!(config.resolve_url(" About:Blank ").unwrap(), "about:blank");
+        assert_eq!(config.resolve_navigation_url("about:newtab").unwrap(), "about:newtab");
+        assert_eq!(config.set_home_url("about:newtab").unwrap(), "about:newtab");
+
+        assert_eq!(internal_page_name("about:newtab"), Some("newtab".to_string()));
+        assert_eq!(internal_page_name("https://example.com/about:newtab"), None);
+        assert_eq!(internal_page_name("about:"), None);
+        assert_eq!(internal_page_name("about:<script>"), None);
+        assert!(config.resolve_url("about:<script>").is_err());
+    }
+
+    #[test]
+    fn test_go_home_records_history() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+
+        assert!(manager.go_home("tab-1", "https://home.example.com").unwrap());
+        assert!(!manager.go_home("missing", "https://home.example.com").unwrap());
+
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert_eq!(browser.url, "https://home.example.com");
+        assert_eq!(browser.history.len(), 2);
+        assert_eq!(browser.history[1].url, "https://home.example.com");
+        assert!(browser.can_go_back);
+        assert!(browser.is_loading);
+    }
+
+    #[test]
+    fn test_set_visible_keeps_other_instances() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 400.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 400.0, 0.0, 400.0, 600.0).unwrap();
+        pool.register_instance("tab-3".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        for tab_id in ["tab-1", "tab-2", "tab-3"] {
+            pool.set_visible(tab_id, false).unwrap();
+        }
+        assert_eq!(pool.get_visible_instance_count().unwrap(), 0);
+
+        assert!(pool.set_visible("tab-1", true).unwrap());
+        assert!(pool.set_visible("tab-2", true).unwrap());
+        assert!(!pool.set_visible("missing", true).unwrap());
+        assert_eq!(pool.get_visible_instance_count().unwrap(), 2);
+
+        pool.set_visible("tab-1", false).unwrap();
+        assert_eq!(pool.get_visible_instance_count().unwrap(), 1);
+
+        // The exclusive variant still hides everything else
+        pool.set_visible("tab-1", true).unwrap();
+        pool.show_instance("tab-3").unwrap();
+        assert_eq!(pool.get_visible_instance_count().unwrap(), 1);
+        assert!(pool.get_instance("tab-3").unwrap().unwrap().is_visible);
+    }
+
+    #[test]
+    fn test_find_tab_by_url() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://a.com".to_string()).unwrap();
+        manager.register_browser("tab-2".to_string(), "https://b.com/".to_string()).unwrap();
+
+        assert_eq!(manager.find_tab_by_url("a.com").unwrap(), Some("tab-1".to_string()));
+        assert_eq!(manager.find_tab_by_url("https://b.com").unwrap(), Some("tab-2".to_string()));
+        assert_eq!(manager.find_tab_by_url("http://A.com/").unwrap(), Some("tab-1".to_string()));
+        assert_eq!(manager.find_tab_by_url("https://a.com/page").unwrap(), None);
+        assert_eq!(manager.find_tab_by_url("").unwrap(), None);
+    }
+
+    #[test]
+    fn test_unregister_visible_activates_neighbour() {
+        let pool = CefInstancePool::new();
+        for tab_id in ["tab-a", "tab-b", "tab-c"] {
+            pool.register_instance(tab_id.to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        }
+
+        // Closing the active middle tab activates the one before it
+        pool.show_instance("tab-b").unwrap();
+        assert_eq!(pool.unregister_and_activate_next("tab-b").unwrap(), Some("tab-a".to_string()));
+        assert!(pool.get_instance("tab-a").unwrap().unwrap().is_visible);
+        assert_eq!(pool.get_visible_instance_count().unwrap(), 1);
+
+        // Closing the active first tab activates the one after it
+        assert_eq!(pool.unregister_and_activate_next("tab-a").unwrap(), Some("tab-c".to_string()));
+
+        // Closing a hidden tab leaves visibility alone
+        pool.register_instance("tab-d".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        pool.show_instance("tab-c").unwrap();
+        assert_eq!(pool.unregister_and_activate_next("tab-d").unwrap(), None);
+        assert_eq!(pool.unregister_and_activate_next("tab-c").unwrap(), None);
+        assert_eq!(pool.get_instance_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_resolve_explicit_bounds() {
+        let config = BrowserConfig::new();
+        let bounds = config.resolve_bounds(Some(10.0), Some(20.0), Some(300.0), Some(200.0)).unwrap();
+
+        assert_eq!(bounds, BrowserBounds { x: 10.0, y: 20.0, width: 300.0, height: 200.0 });
+        assert!(config.resolve_bounds(Some(0.0), Some(0.0), Some(0.0), Some(200.0)).is_err());
+    }
+
+    #[test]
+    fn test_resolve_defaulted_bounds() {
+        let config = BrowserConfig::new();
+        assert_eq!(config.resolve_bounds(None, None, None, None).unwrap(), DEFAULT_BROWSER_BOUNDS);
+
+        let custom = BrowserBounds { x: 50.0, y: 40.0, width: 640.0, height: 480.0 };
+        config.set_default_bounds(custom).unwrap();
+        assert_eq!(config.resolve_bounds(None, None, None, None).unwrap(), custom);
+        assert!(config.set_default_bounds(BrowserBounds { width: -1.0, ..custom }).is_err());
+        assert_eq!(config.default_bounds(), custom);
+    }
+
+    #[test]
+    fn test_resolve_partial_bounds() {
+        let config = BrowserConfig::new();
+        config.set_default_bounds(BrowserBounds { x: 0.0, y: 0.0, width: 800.0, height: 600.0 }).unwrap();
+
+        let bounds = config.resolve_bounds(Some(100.0), None, Some(400.0), None).unwrap();
+        assert_eq!(bounds, BrowserBounds { x: 100.0, y: 0.0, width: 400.0, height: 600.0 });
+
+        // Validation runs on the resolved values
+        assert!(config.resolve_bounds(None, None, None, Some(f64::NAN)).is_err());
     }
 
     #[test]
-    fn test_on_loading_state_change() {
+    fn test_get_browser_repairs_nav_flags() {
         let manager = CefBrowserManager::new();
-        manager.register_browser(
-            "tab-1".to_string(),
-            "https://example.com".to_string(),
-        ).unwrap();
-        
-        let result = manager.on_loading_state_change("tab-1", false);
-        assert!(result.is_ok());
-        
+        manager.register_browser("tab-1".to_string(), "https://a.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://b.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://c.com".to_string()).unwrap();
+        manager.on_go_back("tab-1").unwrap();
+
+        let mut info = manager.get_browser("tab-1").unwrap().unwrap();
+        info.can_go_back = false;
+        info.can_go_forward = false;
+        manager.update_browser("tab-1", info).unwrap();
+
         let browser = manager.get_browser("tab-1").unwrap().unwrap();
-        assert!(!browser.is_loading);
-    }
+        assert_eq!(browser.history_index, 1);
+        assert!(browser.can_go_back);
+        assert!(browser.can_go_forward);
 
-    #[test]
-    fn test_on_go_back() {
-        let manager = CefBrowserManager::new();
-        manager.register_browser(
-            "tab-1".to_string(),
-            "https://example.com".to_string(),
-        ).unwrap();
-        
-        // Navigate to a new URL
-        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
-        
-        // Go back
-        let result = manager.on_go_back("tab-1").unwrap();
-        assert!(result.is_some());
-        assert_eq!(result.unwrap(), "https://example.com");
-        
+        let mut info = browser;
+        info.history_index = 0;
+        info.can_go_back = true;
+        manager.update_browser("tab-1", info).unwrap();
         let browser = manager.get_browser("tab-1").unwrap().unwrap();
-        assert_eq!(browser.url, "https://example.com");
         assert!(!browser.can_go_back);
         assert!(browser.can_go_forward);
     }
 
     #[test]
-    fn test_on_go_forward() {
+    fn test_clear_all_history_keeps_current_page() {
         let manager = CefBrowserManager::new();
-        manager.register_browser(
-            "tab-1".to_string(),
-            "https://example.com".to_string(),
-        ).unwrap();
-        
-        // Navigate to a new URL
-        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
-        
-        // Go back
+        manager.register_browser("tab-1".to_string(), "https://a.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://a.com/1".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://a.com/2".to_string()).unwrap();
         manager.on_go_back("tab-1").unwrap();
-        
-        // Go forward
-        let result = manager.on_go_forward("tab-1").unwrap();
-        assert!(result.is_some());
-        assert_eq!(result.unwrap(), "https://google.com");
-        
-        let browser = manager.get_browser("tab-1").unwrap().unwrap();
-        assert_eq!(browser.url, "https://google.com");
-        assert!(browser.can_go_back);
-        assert!(!browser.can_go_forward);
+        manager.register_browser("tab-2".to_string(), "https://b.com".to_string()).unwrap();
+        manager.on_url_change("tab-2", "https://b.com/1".to_string()).unwrap();
+        manager.register_browser("tab-3".to_string(), "https://c.com".to_string()).unwrap();
+
+        assert_eq!(manager.clear_all_history().unwrap(), 3);
+
+        for (tab_id, url) in [("tab-1", "https://a.com/1"), ("tab-2", "https://b.com/1"), ("tab-3", "https://c.com")] {
+            let browser = manager.get_browser(tab_id).unwrap().unwrap();
+            assert_eq!(browser.history.len(), 1);
+            assert_eq!(browser.history[0].url, url);
+            assert_eq!(browser.history_index, 0);
+            assert_eq!(browser.url, url);
+            assert!(!browser.can_go_back);
+            assert!(!browser.can_go_forward);
+        }
+        assert_eq!(manager.clear_all_history().unwrap(), 0);
     }
 
     #[test]
-    fn test_navigation_history_consistency() {
-        // Property 1: Navigation History Back/Forward Consistency
-        // For any sequence of URL navigations, clicking back should return to the previous URL,
-        // and clicking forward after back should return to the URL we came from.
-        
+    fn test_top_sites_counts_navigations() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
         let manager = CefBrowserManager::new();
-        manager.register_browser(
-            "tab-1".to_string(),
-            "https://example.com".to_string(),
-        ).unwrap();
-        
-        // Navigate through multiple URLs
-        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
-        manager.on_url_change("tab-1", "https://github.com".to_string()).unwrap();
-        
-        let browser = manager.get_browser("tab-1").unwrap().unwrap();
-        assert_eq!(browser.url, "https://github.com");
-        assert_eq!(browser.history.len(), 3);
-        
-        // Go back twice
-        let back1 = manager.on_go_back("tab-1").unwrap();
-        assert_eq!(back1, Some("https://google.com".to_string()));
-        
-        let back2 = manager.on_go_back("tab-1").unwrap();
-        assert_eq!(back2, Some("https://example.com".to_string()));
-        
-        // Go forward twice
-        let forward1 = manager.on_go_forward("tab-1").unwrap();
-        assert_eq!(forward1, Some("https://google.com".to_string()));
-        
-        let forward2 = manager.on_go_forward("tab-1").unwrap();
-        assert_eq!(forward2, Some("https://github.com".to_string()));
+        manager.register_browser("tab-1".to_string(), "https://docs.rs".to_string()).unwrap();
+        for url in [
+            "https://docs.rs/serde",
+            "https://github.com/tauri-apps",
+            "https://docs.rs:443/tokio?search=spawn",
+            "https://docs.rs/regex#examples",
+            "https://github.com/serde-rs",
+        ] {
+            manager.on_url_change("tab-1", url.to_string()).unwrap();
+        }
+        manager.register_browser_private("tab-2".to_string(), "https://github.com".to_string()).unwrap();
+        manager.on_url_change("tab-2", "https://github.com/private".to_string()).unwrap();
+
+        let expected = vec![("docs.rs".to_string(), 3), ("github.com".to_string(), 2)];
+        assert_eq!(manager.top_hosts(10).unwrap(), expected);
+        assert_eq!(manager.top_hosts(1).unwrap(), expected[..1].to_vec());
+
+        manager.save_session(&path).unwrap();
+        let restored = CefBrowserManager::new();
+        restored.load_session(&path).unwrap();
+        assert_eq!(restored.top_hosts(10).unwrap(), expected);
     }
 
     #[test]
-    fn test_cef_instance_pool_register() {
+    fn test_contains_and_is_empty() {
+        let manager = CefBrowserManager::new();
         let pool = CefInstancePool::new();
-        let result = pool.register_instance(
-            "tab-1".to_string(),
-            0.0,
-            0.0,
-            800.0,
-            600.0,
-        );
-        assert!(result.is_ok());
+        assert!(manager.is_empty().unwrap());
+        assert!(pool.is_empty().unwrap());
+
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        assert!(manager.contains("tab-1").unwrap());
+        assert!(pool.contains("tab-1").unwrap());
+        assert!(!manager.contains("tab-2").unwrap());
+        assert!(!pool.contains("tab-2").unwrap());
+        assert!(!manager.is_empty().unwrap());
+        assert!(!pool.is_empty().unwrap());
+
+        manager.unregister_browser("tab-1").unwrap();
+        pool.unregister_instance("tab-1").unwrap();
+        assert!(!manager.contains("tab-1").unwrap());
+        assert!(manager.is_empty().unwrap());
+        assert!(pool.is_empty().unwrap());
     }
 
     #[test]
-    fn test_cef_instance_pool_get() {
-        let pool = CefInstancePool::new();
-        pool.register_instance(
-            "tab-1".to_string(),
-            0.0,
-            0.0,
-            800.0,
-            600.0,
-        ).unwrap();
-        
-        let instance = pool.get_instance("tab-1").unwrap();
-        assert!(instance.is_some());
-        assert_eq!(instance.unwrap().tab_id, "tab-1");
+    fn test_find_settings_persist_across_searches() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+
+        let first = manager.begin_find("tab-1", "Rust", Some(true)).unwrap().unwrap();
+        assert_eq!(first, FindState { query: "Rust".to_string(), match_case: true, active_match: 0, total_matches: 0 });
+        manager.on_find_result("tab-1", 1, 4).unwrap();
+
+        // An empty query repeats the last search with the same settings
+        let repeated = manager.begin_find("tab-1", "", None).unwrap().unwrap();
+        assert_eq!(repeated.query, "Rust");
+        assert!(repeated.match_case);
+        assert_eq!(repeated.total_matches, 4);
+
+        // A new query keeps the match_case preference
+        let second = manager.begin_find("tab-1", "cargo", None).unwrap().unwrap();
+        assert!(second.match_case);
+        assert_eq!(second.total_matches, 0);
+        assert!(manager.begin_find("missing", "x", None).unwrap().is_none());
     }
 
     #[test]
-    fn test_cef_instance_pool_unregister() {
-        let pool = CefInstancePool::new();
-        pool.register_instance(
-            "tab-1".to_string(),
-            0.0,
-            0.0,
-            800.0,
-            600.0,
-        ).unwrap();
-        
-        let result = pool.unregister_instance("tab-1");
-        assert!(result.is_ok());
-        
-        let instance = pool.get_instance("tab-1").unwrap();
-        assert!(instance.is_none());
+    fn test_find_state_cleared_on_navigation() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.begin_find("tab-1", "example", Some(false)).unwrap();
+        manager.on_find_result("tab-1", 2, 3).unwrap();
+
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+
+        assert!(manager.get_browser("tab-1").unwrap().unwrap().find_state.is_none());
+        assert!(manager.on_find_result("tab-1", 1, 1).unwrap().is_none());
+        assert!(matches!(manager.begin_find("tab-1", "", None), Err(AppError::InvalidPath(_))));
     }
 
     #[test]
-    fn test_cef_instance_pool_show_instance() {
-        let pool = CefInstancePool::new();
-        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
-        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
-        
-        // Show tab-2 (should hide tab-1)
-        pool.show_instance("tab-2").unwrap();
-        
-        let tab1 = pool.get_instance("tab-1").unwrap().unwrap();
-        let tab2 = pool.get_instance("tab-2").unwrap().unwrap();
-        
-        assert!(!tab1.is_visible);
-        assert!(tab2.is_visible);
+    fn test_duplicate_register_preserves_history() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+
+        let result = manager.register_browser("tab-1".to_string(), "https://rust-lang.org".to_string());
+        assert!(matches!(result, Err(AppError::AlreadyExists(_))));
+        assert!(manager.register_browser_private("tab-1".to_string(), "about:blank".to_string()).is_err());
+
+        let existing = manager.register_or_get("tab-1".to_string(), "https://rust-lang.org".to_string()).unwrap();
+        assert_eq!(existing.url, "https://google.com");
+        assert_eq!(existing.history.len(), 2);
+        assert!(existing.can_go_back);
+
+        let created = manager.register_or_get("tab-2".to_string(), "https://rust-lang.org".to_string()).unwrap();
+        assert_eq!(created.history.len(), 1);
+        assert_eq!(manager.get_browser("tab-2").unwrap().unwrap().url, "https://rust-lang.org");
     }
 
     #[test]
-    fn test_cef_instance_pool_hide_instance() {
+    fn test_duplicate_register_instance_rejected() {
         let pool = CefInstancePool::new();
         pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
-        
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
         pool.hide_instance("tab-1").unwrap();
-        
+
+        let result = pool.register_instance("tab-1".to_string(), 10.0, 10.0, 400.0, 300.0);
+        assert!(matches!(result, Err(AppError::AlreadyExists(_))));
+
         let instance = pool.get_instance("tab-1").unwrap().unwrap();
         assert!(!instance.is_visible);
+        assert_eq!((instance.width, instance.height, instance.order), (800.0, 600.0, 0));
+    }
+
+    /// Drain a subscriber's `VisibilityChanged` events, sorted by tab id
+    fn visibility_changes(events: &mut broadcast::Receiver<CefEvent>) -> Vec<(String, bool)> {
+        let mut changes = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let CefEvent::VisibilityChanged { tab_id, is_visible } = event {
+                changes.push((tab_id, is_visible));
+            }
+        }
+        changes.sort();
+        changes
     }
 
     #[test]
-    fn test_cef_instance_pool_update_bounds() {
+    fn test_bulk_visibility_changes_published_per_instance() {
         let pool = CefInstancePool::new();
-        pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
-        
-        pool.update_instance_bounds("tab-1", 100.0, 100.0, 1024.0, 768.0).unwrap();
-        
-        let instance = pool.get_instance("tab-1").unwrap().unwrap();
-        assert_eq!(instance.x, 100.0);
-        assert_eq!(instance.y, 100.0);
-        assert_eq!(instance.width, 1024.0);
-        assert_eq!(instance.height, 768.0);
+        for id in ["tab-1", "tab-2", "tab-3"] {
+            pool.register_instance(id.to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        }
+        pool.set_group("tab-1", Some("docs".to_string())).unwrap();
+        pool.set_group("tab-2", Some("docs".to_string())).unwrap();
+        let mut events = pool.subscribe();
+
+        // All three start visible, so only the other two are announced as hidden
+        pool.show_instance("tab-3").unwrap();
+        assert_eq!(visibility_changes(&mut events), vec![("tab-1".to_string(), false), ("tab-2".to_string(), false)]);
+
+        pool.show_group("docs").unwrap();
+        assert_eq!(visibility_changes(&mut events), vec![("tab-1".to_string(), true), ("tab-2".to_string(), true)]);
+        pool.show_group("docs").unwrap();
+        assert!(visibility_changes(&mut events).is_empty());
+
+        pool.hide_group("docs").unwrap();
+        assert_eq!(visibility_changes(&mut events), vec![("tab-1".to_string(), false), ("tab-2".to_string(), false)]);
+
+        // Closing the last visible tab brings its neighbour back
+        pool.unregister_instance("tab-3").unwrap();
+        assert_eq!(visibility_changes(&mut events), vec![("tab-2".to_string(), true)]);
     }
 
     #[test]
-    fn test_cef_instance_pool_get_all_instances() {
+    fn test_activate_instance_updates_bounds_and_visibility() {
         let pool = CefInstancePool::new();
         pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
         pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
-        
-        let instances = pool.get_all_instances().unwrap();
-        assert_eq!(instances.len(), 2);
+        pool.show_instance("tab-1").unwrap();
+
+        assert!(pool.activate_instance("tab-2", 10.0, 20.0, 1024.0, 768.0).unwrap());
+
+        let active = pool.get_instance("tab-2").unwrap().unwrap();
+        assert!(active.is_visible);
+        assert_eq!((active.x, active.y, active.width, active.height), (10.0, 20.0, 1024.0, 768.0));
+        let background = pool.get_instance("tab-1").unwrap().unwrap();
+        assert!(!background.is_visible);
+        assert_eq!((background.width, background.height), (800.0, 600.0));
+
+        assert!(!pool.activate_instance("missing", 0.0, 0.0, 800.0, 600.0).unwrap());
+        assert!(pool.get_instance("tab-2").unwrap().unwrap().is_visible);
+        assert!(matches!(pool.activate_instance("tab-1", 0.0, 0.0, 0.0, 600.0), Err(AppError::InvalidPath(_))));
+        assert!(!pool.get_instance("tab-1").unwrap().unwrap().is_visible);
     }
 
     #[test]
-    fn test_cef_instance_pool_get_visible_count() {
+    fn test_url_change_published_to_subscribers() {
+        let manager = CefBrowserManager::new();
+        let mut events = manager.subscribe();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+
+        manager.on_url_change("tab-1", "https://google.com".to_string()).unwrap();
+        manager.on_url_change("missing", "https://rust-lang.org".to_string()).unwrap();
+
+        assert_eq!(events.try_recv().unwrap(), CefEvent::BrowserCreated {
+            tab_id: "tab-1".to_string(),
+            url: "https://example.com".to_string(),
+        });
+        assert_eq!(events.try_recv().unwrap(), CefEvent::UrlChanged {
+            tab_id: "tab-1".to_string(),
+            url: "https://google.com".to_string(),
+        });
+        // Unknown tabs don't produce events
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_state_changes_published_to_subscribers() {
         let pool = CefInstancePool::new();
         pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
-        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
-        
-        // Initially both are visible
-        assert_eq!(pool.get_visible_instance_count().unwrap(), 2);
-        
-        // Show tab-1 (hides tab-2)
-        pool.show_instance("tab-1").unwrap();
-        assert_eq!(pool.get_visible_instance_count().unwrap(), 1);
+        let mut pool_events = pool.subscribe();
+
+        pool.hide_instance("tab-1").unwrap();
+        pool.hide_instance("tab-1").unwrap();
+        assert!(pool.set_visible("tab-1", true).unwrap());
+        assert_eq!(pool_events.try_recv().unwrap(), CefEvent::VisibilityChanged { tab_id: "tab-1".to_string(), is_visible: false });
+        assert_eq!(pool_events.try_recv().unwrap(), CefEvent::VisibilityChanged { tab_id: "tab-1".to_string(), is_visible: true });
+        assert!(pool_events.try_recv().is_err());
+
+        let manager = CefBrowserManager::new().with_load_timeout(1_000);
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        let started = manager.get_browser("tab-1").unwrap().unwrap().load_started_ms;
+        let mut events = manager.subscribe();
+
+        assert!(manager.set_muted("tab-1", true).unwrap());
+        manager.set_zoom("tab-1", 100.0).unwrap();
+        manager.check_timeouts(started + 5_000);
+        assert_eq!(events.try_recv().unwrap(), CefEvent::MuteChanged { tab_id: "tab-1".to_string(), is_muted: true });
+        assert!(matches!(events.try_recv().unwrap(), CefEvent::ZoomChanged { zoom_level, .. } if zoom_level < 100.0));
+        assert_eq!(events.try_recv().unwrap(), CefEvent::LoadTimedOut { tab_id: "tab-1".to_string() });
+        assert!(events.try_recv().is_err());
     }
 
     #[test]
-    fn test_cef_instance_pool_get_count() {
+    fn test_timestamps_use_injected_clock() {
+        let manager = CefBrowserManager::new().with_clock(|| 1_700_000_000_000);
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://example.com/docs".to_string()).unwrap();
+        manager.on_loading_state_change("tab-1", false).unwrap();
+
+        let browser = manager.get_browser("tab-1").unwrap().unwrap();
+        assert!(browser.history.iter().all(|entry| entry.timestamp == 1_700_000_000_000));
+        assert_eq!((browser.load_started_ms, browser.load_finished_ms), (1_700_000_000_000, 1_700_000_000_000));
+    }
+
+    #[test]
+    fn test_unchanged_bounds_not_rewritten() {
         let pool = CefInstancePool::new();
         pool.register_instance("tab-1".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
-        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
-        
-        assert_eq!(pool.get_instance_count().unwrap(), 2);
+        let mut events = pool.subscribe();
+
+        assert!(pool.update_instance_bounds("tab-1", 10.0, 20.0, 1024.0, 768.0).unwrap());
+        assert!(!pool.update_instance_bounds("tab-1", 10.0, 20.0, 1024.0, 768.0).unwrap());
+        // Float noise from DPI scaling is not a change
+        assert!(!pool.update_instance_bounds("tab-1", 10.0, 20.0 + BOUNDS_EPSILON / 10.0, 1024.0, 768.0).unwrap());
+        assert!(pool.update_instance_bounds("tab-1", 10.0, 20.0, 1024.0, 769.0).unwrap());
+        assert!(!pool.update_instance_bounds("missing", 10.0, 20.0, 1024.0, 768.0).unwrap());
+
+        assert!(matches!(events.try_recv().unwrap(), CefEvent::BoundsChanged { .. }));
+        assert!(matches!(events.try_recv().unwrap(), CefEvent::BoundsChanged { .. }));
+        assert!(events.try_recv().is_err());
     }
 
     #[test]
-    fn test_tab_independence() {
-        // Property 2: Tab Independence
-        // For any set of browser tabs, navigating in one tab should not affect the URL or state of other tabs.
-        
+    fn test_frecent_urls_rank_repeat_visits_first() {
         let manager = CefBrowserManager::new();
-        
-        // Create two tabs
         manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
-        manager.register_browser("tab-2".to_string(), "https://google.com".to_string()).unwrap();
-        
-        // Navigate in tab-1
-        manager.on_url_change("tab-1", "https://github.com".to_string()).unwrap();
+        manager.register_browser_private("tab-2".to_string(), "about:blank".to_string()).unwrap();
+
+        for _ in 0..3 {
+            manager.on_url_change("tab-1", "https://docs.rs".to_string()).unwrap();
+        }
+        manager.on_url_change("tab-1", "https://crates.io".to_string()).unwrap();
+        for _ in 0..5 {
+            manager.on_url_change("tab-2", "https://secret.com".to_string()).unwrap();
+        }
+
+        let frecent = manager.frecent_urls(10).unwrap();
+        assert_eq!(frecent.len(), 2);
+        assert_eq!((frecent[0].0.as_str(), frecent[0].1), ("https://docs.rs", 3));
+        assert_eq!((frecent[1].0.as_str(), frecent[1].1), ("https://crates.io", 1));
+        assert!(frecent[0].2 > 0);
+    }
+
+    #[test]
+    fn test_rename_tab_preserves_history() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://a.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://b.com".to_string()).unwrap();
+        manager.register_browser("tab-2".to_string(), "https://c.com".to_string()).unwrap();
+        let mut events = manager.subscribe();
+        
+        assert!(matches!(manager.rename_tab("tab-1", "tab-2"), Err(AppError::AlreadyExists(_))));
+        assert!(matches!(manager.rename_tab("missing", "tab-3"), Err(AppError::NotFound(_))));
+        
+        manager.rename_tab("tab-1", "tab-3").unwrap();
+        assert_eq!(events.try_recv().unwrap(), CefEvent::TabRenamed { old_id: "tab-1".to_string(), new_id: "tab-3".to_string() });
+        assert!(events.try_recv().is_err());
+        assert!(manager.get_browser("tab-1").unwrap().is_none());
+        let browser = manager.get_browser("tab-3").unwrap().unwrap();
+        assert_eq!(browser.tab_id, "tab-3");
+        assert_eq!(browser.url, "https://b.com");
+        assert_eq!(browser.history.len(), 2);
+        assert!(browser.can_go_back);
+        assert_eq!(manager.on_go_back("tab-3").unwrap().unwrap().url, "https://a.com");
+    }
+
+    #[test]
+    fn test_rename_instance() {
+        let pool = CefInstancePool::new();
+        pool.register_instance("tab-1".to_string(), 10.0, 20.0, 800.0, 600.0).unwrap();
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
         
-        // Check that tab-2 is unaffected
-        let tab1 = manager.get_browser("tab-1").unwrap().unwrap();
-        let tab2 = manager.get_browser("tab-2").unwrap().unwrap();
+        assert!(matches!(pool.rename_instance("tab-1", "tab-2"), Err(AppError::AlreadyExists(_))));
+        assert!(matches!(pool.rename_instance("missing", "tab-3"), Err(AppError::NotFound(_))));
         
-        assert_eq!(tab1.url, "https://github.com");
-        assert_eq!(tab2.url, "https://google.com");
+        pool.rename_instance("tab-1", "tab-3").unwrap();
+        assert!(!pool.contains("tab-1").unwrap());
+        let instance = pool.get_instance("tab-3").unwrap().unwrap();
+        assert_eq!(instance.tab_id, "tab-3");
+        assert_eq!((instance.x, instance.y, instance.order), (10.0, 20.0, 0));
     }
 }
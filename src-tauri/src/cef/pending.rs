@@ -0,0 +1,90 @@
+/// Pending results for emit/report round-trips with the webview
+///
+/// A command emits a request event, the webview answers through a `cef_report_*`
+/// command that parks the result here, and the original command picks it up.
+
+use crate::error::AppError;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often waiters check for a parked result
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Results parked by key (tab id or request id) until they are taken
+pub struct PendingResults<T> {
+    results: Mutex<HashMap<String, T>>,
+}
+
+impl<T> PendingResults<T> {
+    pub fn new() -> Self {
+        PendingResults {
+            results: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Park a result, replacing any previous one for the same key
+    pub fn put(&self, key: String, value: T) -> Result<(), AppError> {
+        let mut results = self.results.lock().unwrap_or_else(|e| e.into_inner());
+
+        results.insert(key, value);
+        Ok(())
+    }
+
+    /// Take the parked result for a key, if any
+    pub fn take(&self, key: &str) -> Result<Option<T>, AppError> {
+        let mut results = self.results.lock().unwrap_or_else(|e| e.into_inner());
+
+        Ok(results.remove(key))
+    }
+
+    /// Wait until a result is parked for `key`, or return None after `timeout`
+    pub async fn wait_for(&self, key: &str, timeout: Duration) -> Result<Option<T>, AppError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(value) = self.take(key)? {
+                return Ok(Some(value));
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl<T> Default for PendingResults<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_and_take() {
+        let pending = PendingResults::new();
+        pending.put("tab-1".to_string(), 42).unwrap();
+
+        assert_eq!(pending.take("tab-1").unwrap(), Some(42));
+        assert_eq!(pending.take("tab-1").unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_timeout() {
+        let pending: PendingResults<u32> = PendingResults::new();
+        let result = pending.wait_for("tab-1", Duration::from_millis(50)).await.unwrap();
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_parked_result() {
+        let pending = PendingResults::new();
+        pending.put("tab-1".to_string(), "done".to_string()).unwrap();
+
+        let result = pending.wait_for("tab-1", Duration::from_millis(50)).await.unwrap();
+        assert_eq!(result, Some("done".to_string()));
+    }
+}
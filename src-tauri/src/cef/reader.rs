@@ -0,0 +1,127 @@
+/// Reader-mode extraction for the AI Browser
+///
+/// Strips page chrome (scripts, styles, nav, header/footer, asides, forms) from page HTML
+/// and returns the readable text, preferring the `<article>`/`<main>` element when present.
+
+use once_cell::sync::Lazy;
+use scraper::{ElementRef, Html, Node, Selector};
+
+/// Elements whose whole content is dropped in reader mode
+const NOISE_TAGS: &[&str] = &[
+    "head", "script", "style", "noscript", "template", "svg", "nav", "header", "footer", "aside", "form",
+];
+
+/// Block elements that end a paragraph in the extracted text
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "section", "article", "main", "h1", "h2", "h3", "h4", "h5", "h6",
+    "li", "ul", "ol", "blockquote", "pre", "tr", "table", "br", "hr",
+];
+
+/// Where the readable content is looked for, best first
+static CONTENT_ROOTS: Lazy<Vec<Selector>> = Lazy::new(|| {
+    ["article", "main", "body"]
+        .iter()
+        .map(|selector| Selector::parse(selector).unwrap())
+        .collect()
+});
+
+/// Extracted reader-mode text
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReaderContent {
+    pub text: String,
+    pub word_count: usize,
+}
+
+/// Append the text under `element`, skipping noise elements and breaking lines at block elements
+fn collect_text(element: ElementRef, out: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(el) => {
+                let name = el.name();
+                if NOISE_TAGS.contains(&name) {
+                    out.push(' ');
+                    continue;
+                }
+                let is_block = BLOCK_TAGS.contains(&name);
+                if is_block {
+                    out.push('\n');
+                }
+                if let Some(child) = ElementRef::wrap(child) {
+                    collect_text(child, out);
+                }
+                if is_block {
+                    out.push('\n');
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Extract clean article text from page HTML
+/// The page is parsed as a browser would, so unclosed or oddly nested tags don't leak markup
+pub fn extract_readable(html: &str) -> ReaderContent {
+    let document = Html::parse_document(html);
+    let root = CONTENT_ROOTS
+        .iter()
+        .find_map(|selector| document.select(selector).next())
+        .unwrap_or_else(|| document.root_element());
+
+    let mut body = String::new();
+    collect_text(root, &mut body);
+
+    let text = body
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let word_count = crate::fs::compute_stats(&text).words;
+
+    ReaderContent { text, word_count }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><head><title>T</title>
+        <style>body { color: red; }</style>
+        <script>var tracking = "ads";</script></head>
+        <body>
+        <nav><a href="/">Home</a> <a href="/about">About</a></nav>
+        <article>
+            <h1>Hello &amp; welcome</h1>
+            <p>This is the <b>first</b> paragraph.</p>
+            <script>alert("inline");</script>
+            <p>Second   paragraph here.</p>
+        </article>
+        <footer>Copyright</footer>
+        </body></html>"#;
+
+    #[test]
+    fn test_extract_strips_script_and_nav() {
+        let content = extract_readable(PAGE);
+
+        assert_eq!(content.text, "Hello & welcome\n\nThis is the first paragraph.\n\nSecond paragraph here.");
+        assert!(!content.text.contains("tracking"));
+        assert!(!content.text.contains("alert"));
+        assert!(!content.text.contains("About"));
+        assert!(!content.text.contains("Copyright"));
+        assert_eq!(content.word_count, 10);
+    }
+
+    #[test]
+    fn test_extract_without_article_uses_body() {
+        let content = extract_readable("<body><nav>Menu</nav><div>Just text</div></body>");
+        assert_eq!(content.text, "Just text");
+        assert_eq!(content.word_count, 2);
+    }
+
+    #[test]
+    fn test_extract_unclosed_and_nested_tags() {
+        let content = extract_readable("<main><p>One<p>Two <div><div>nested &lt;b&gt;</div></div><script>var x = '</p>';");
+        assert_eq!(content.text, "One\n\nTwo\n\nnested <b>");
+    }
+}
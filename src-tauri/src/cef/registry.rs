@@ -0,0 +1,173 @@
+/// Tab lifecycle across the instance pool and the browser manager
+///
+/// A tab has geometry in `CefInstancePool` and navigation state in `CefBrowserManager`.
+/// `TabRegistry` creates and removes both together so neither is left behind; the
+/// granular methods on each remain available for finer control.
+
+use super::{validate_bounds, BrowserBounds, CefBrowserInfo, CefBrowserManager, CefInstance, CefInstancePool};
+use crate::error::AppError;
+
+/// A tab as seen by both managers
+#[derive(Debug, Clone)]
+pub struct Tab {
+    pub browser: CefBrowserInfo,
+    pub instance: CefInstance,
+}
+
+/// Facade keeping a tab's pool instance and browser entry in sync
+pub struct TabRegistry<'a> {
+    manager: &'a CefBrowserManager,
+    pool: &'a CefInstancePool,
+}
+
+impl<'a> TabRegistry<'a> {
+    pub fn new(manager: &'a CefBrowserManager, pool: &'a CefInstancePool) -> Self {
+        TabRegistry { manager, pool }
+    }
+
+    /// Create a tab in both managers
+    /// Fails without registering anything if the bounds are invalid or either manager
+    /// already knows the tab id; a failed pool registration rolls the browser entry back
+    pub fn create_tab(&self, tab_id: String, url: String, bounds: BrowserBounds, is_private: bool) -> Result<(), AppError> {
+        let BrowserBounds { x, y, width, height } = bounds;
+        validate_bounds(x, y, width, height)?;
+        if self.manager.contains(&tab_id)? || self.pool.contains(&tab_id)? {
+            return Err(AppError::AlreadyExists(format!("tab {}", tab_id)));
+        }
+
+        if is_private {
+            self.manager.register_browser_private(tab_id.clone(), url)?;
+        } else {
+            self.manager.register_browser(tab_id.clone(), url)?;
+        }
+        if let Err(e) = self.pool.register_instance(tab_id.clone(), x, y, width, height) {
+            self.manager.discard_browser(&tab_id)?;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Close a tab in both managers
+    /// A tab only one of them knows (created through the granular methods) is removed from it;
+    /// returns `AppError::NotFound` if neither knows the tab
+    pub fn close_tab(&self, tab_id: &str) -> Result<(), AppError> {
+        let in_manager = self.manager.contains(tab_id)?;
+        let in_pool = self.pool.contains(tab_id)?;
+        if !in_manager && !in_pool {
+            return Err(AppError::NotFound(format!("tab {}", tab_id)));
+        }
+
+        if in_pool {
+            self.pool.unregister_instance(tab_id)?;
+        }
+        if in_manager {
+            self.manager.unregister_browser(tab_id)?;
+        }
+        Ok(())
+    }
+
+    /// Rename a tab in both managers, keeping its history, geometry and position
+    /// Checks both sides before changing either: `AppError::NotFound` if neither knows `old_id`,
+    /// `AppError::AlreadyExists` if either already has `new_id`
+    pub fn rename_tab(&self, old_id: &str, new_id: &str) -> Result<(), AppError> {
+        let in_manager = self.manager.contains(old_id)?;
+        let in_pool = self.pool.contains(old_id)?;
+        if !in_manager && !in_pool {
+            return Err(AppError::NotFound(format!("tab {}", old_id)));
+        }
+        if old_id == new_id {
+            return Ok(());
+        }
+        if self.manager.contains(new_id)? || self.pool.contains(new_id)? {
+            return Err(AppError::AlreadyExists(format!("tab {}", new_id)));
+        }
+
+        if in_manager {
+            self.manager.rename_tab(old_id, new_id)?;
+        }
+        if in_pool {
+            if let Err(e) = self.pool.rename_instance(old_id, new_id) {
+                if in_manager {
+                    self.manager.rename_tab(new_id, old_id)?;
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Get a tab, or None unless both managers know it
+    pub fn get_tab(&self, tab_id: &str) -> Result<Option<Tab>, AppError> {
+        let browser = self.manager.get_browser(tab_id)?;
+        let instance = self.pool.get_instance(tab_id)?;
+        Ok(browser.zip(instance).map(|(browser, instance)| Tab { browser, instance }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: BrowserBounds = BrowserBounds { x: 0.0, y: 0.0, width: 800.0, height: 600.0 };
+
+    #[test]
+    fn test_close_tab_removes_from_both() {
+        let manager = CefBrowserManager::new();
+        let pool = CefInstancePool::new();
+        let registry = TabRegistry::new(&manager, &pool);
+
+        registry.create_tab("tab-1".to_string(), "https://example.com".to_string(), BOUNDS, false).unwrap();
+        let tab = registry.get_tab("tab-1").unwrap().unwrap();
+        assert_eq!(tab.browser.url, "https://example.com");
+        assert_eq!(tab.instance.width, 800.0);
+
+        registry.close_tab("tab-1").unwrap();
+        assert!(!manager.contains("tab-1").unwrap());
+        assert!(!pool.contains("tab-1").unwrap());
+        assert!(registry.get_tab("tab-1").unwrap().is_none());
+        assert!(matches!(registry.close_tab("tab-1"), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_rename_tab_in_both() {
+        let manager = CefBrowserManager::new();
+        let pool = CefInstancePool::new();
+        let registry = TabRegistry::new(&manager, &pool);
+        registry.create_tab("tab-1".to_string(), "https://example.com".to_string(), BOUNDS, false).unwrap();
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+
+        // Taken on the pool side only; nothing is renamed
+        assert!(matches!(registry.rename_tab("tab-1", "tab-2"), Err(AppError::AlreadyExists(_))));
+        assert!(manager.contains("tab-1").unwrap());
+
+        registry.rename_tab("tab-1", "tab-3").unwrap();
+        let tab = registry.get_tab("tab-3").unwrap().unwrap();
+        assert_eq!((tab.browser.tab_id.as_str(), tab.instance.tab_id.as_str()), ("tab-3", "tab-3"));
+        assert!(registry.get_tab("tab-1").unwrap().is_none());
+        assert!(matches!(registry.rename_tab("tab-1", "tab-4"), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_no_half_open_tabs() {
+        let manager = CefBrowserManager::new();
+        let pool = CefInstancePool::new();
+        let registry = TabRegistry::new(&manager, &pool);
+
+        // Invalid bounds register nothing
+        let bad = BrowserBounds { width: 0.0, ..BOUNDS };
+        assert!(registry.create_tab("tab-1".to_string(), "https://example.com".to_string(), bad, false).is_err());
+        assert!(!manager.contains("tab-1").unwrap());
+        assert!(!pool.contains("tab-1").unwrap());
+
+        // An id known to only one manager is refused rather than half-created
+        pool.register_instance("tab-2".to_string(), 0.0, 0.0, 800.0, 600.0).unwrap();
+        let result = registry.create_tab("tab-2".to_string(), "https://example.com".to_string(), BOUNDS, false);
+        assert!(matches!(result, Err(AppError::AlreadyExists(_))));
+        assert!(!manager.contains("tab-2").unwrap());
+        assert!(registry.get_tab("tab-2").unwrap().is_none());
+
+        // ...and closing it cleans up the side that exists
+        registry.close_tab("tab-2").unwrap();
+        assert!(pool.is_empty().unwrap());
+    }
+}
@@ -0,0 +1,168 @@
+/// Portable browser session bundles
+///
+/// A bundle carries all tabs with their navigation history plus the bookmarks as one
+/// versioned JSON document, so a session can be moved between machines.
+
+use super::bookmarks::{Bookmark, BookmarkStore};
+use super::{CefBrowserInfo, CefBrowserManager};
+use crate::error::AppError;
+use serde::{Serialize, Deserialize};
+
+/// Format version written by `export_session`; imports of other versions are rejected
+pub const SESSION_BUNDLE_VERSION: u32 = 1;
+
+/// All tabs (with history) and bookmarks of a browser session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBundle {
+    pub version: u32,
+    pub tabs: Vec<CefBrowserInfo>,
+    pub bookmarks: Vec<Bookmark>,
+}
+
+/// Serialize the current tabs and bookmarks as a session bundle
+/// Private tabs are left out
+pub fn export_session(manager: &CefBrowserManager, store: &BookmarkStore) -> Result<String, AppError> {
+    let bundle = SessionBundle {
+        version: SESSION_BUNDLE_VERSION,
+        tabs: manager.export_tabs()?,
+        bookmarks: store.list()?,
+    };
+
+    serde_json::to_string_pretty(&bundle)
+        .map_err(|e| AppError::Parse(format!("Failed to serialize session bundle: {}", e)))
+}
+
+/// Parse a session bundle, checking its version before reading the rest
+pub fn parse_session(json: &str) -> Result<SessionBundle, AppError> {
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| AppError::Parse(format!("Invalid session bundle: {}", e)))?;
+
+    let version = value.get("version").and_then(|v| v.as_u64());
+    if version != Some(SESSION_BUNDLE_VERSION as u64) {
+        let found = version.map(|v| v.to_string()).unwrap_or_else(|| "none".to_string());
+        return Err(AppError::InvalidState(format!(
+            "Unsupported session bundle version {} (expected {})",
+            found, SESSION_BUNDLE_VERSION
+        )));
+    }
+
+    serde_json::from_value(value)
+        .map_err(|e| AppError::Parse(format!("Invalid session bundle: {}", e)))
+}
+
+/// Import a session bundle, replacing the current tabs and bookmarks or merging into them
+/// Both halves are staged first, so a bundle that fails to import leaves tabs and bookmarks as they were.
+/// Returns the imported tabs
+pub fn import_session(
+    manager: &CefBrowserManager,
+    store: &BookmarkStore,
+    json: &str,
+    merge: bool,
+) -> Result<Vec<CefBrowserInfo>, AppError> {
+    let bundle = parse_session(json)?;
+
+    let bookmarks = store.stage_import(bundle.bookmarks, merge)?;
+    let tabs = manager.import_tabs(bundle.tabs, merge)?;
+    store.replace_all(bookmarks)?;
+
+    Ok(tabs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_state() -> (CefBrowserManager, BookmarkStore) {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        manager.on_url_change("tab-1", "https://example.com/docs".to_string()).unwrap();
+        manager.register_browser("tab-2".to_string(), "https://rust-lang.org".to_string()).unwrap();
+        manager.register_browser_private("tab-3".to_string(), "https://secret.com".to_string()).unwrap();
+
+        let store = BookmarkStore::new();
+        store.add("https://example.com".to_string(), "Example".to_string()).unwrap();
+
+        (manager, store)
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let (manager, store) = sample_state();
+        let exported = export_session(&manager, &store).unwrap();
+
+        let bundle = parse_session(&exported).unwrap();
+        assert_eq!(bundle.tabs.len(), 2);
+        assert!(bundle.tabs.iter().all(|t| !t.is_private));
+
+        let imported_manager = CefBrowserManager::new();
+        let imported_store = BookmarkStore::new();
+        import_session(&imported_manager, &imported_store, &exported, false).unwrap();
+
+        assert_eq!(export_session(&imported_manager, &imported_store).unwrap(), exported);
+    }
+
+    #[test]
+    fn test_import_replace_and_merge() {
+        let (manager, store) = sample_state();
+        let exported = export_session(&manager, &store).unwrap();
+
+        let target = CefBrowserManager::new();
+        target.register_browser("tab-9".to_string(), "https://local.dev".to_string()).unwrap();
+        let target_store = BookmarkStore::new();
+        target_store.add("https://local.dev".to_string(), "Local".to_string()).unwrap();
+
+        import_session(&target, &target_store, &exported, true).unwrap();
+        assert_eq!(target.list_tabs().unwrap().len(), 3);
+        assert_eq!(target_store.list().unwrap().len(), 2);
+
+        // Merging again doesn't duplicate bookmarks
+        import_session(&target, &target_store, &exported, true).unwrap();
+        assert_eq!(target_store.list().unwrap().len(), 2);
+
+        import_session(&target, &target_store, &exported, false).unwrap();
+        assert!(target.get_browser("tab-9").unwrap().is_none());
+        assert_eq!(target.list_tabs().unwrap().len(), 2);
+        assert_eq!(target_store.list().unwrap(), store.list().unwrap());
+    }
+
+    #[test]
+    fn test_import_version_mismatch() {
+        let manager = CefBrowserManager::new();
+        manager.register_browser("tab-1".to_string(), "https://example.com".to_string()).unwrap();
+        let store = BookmarkStore::new();
+
+        let json = r#"{"version": 2, "tabs": [], "bookmarks": []}"#;
+        let err = import_session(&manager, &store, json, false).unwrap_err();
+        assert!(matches!(err, AppError::InvalidState(_)));
+        assert_eq!(err.code(), "invalid_state");
+        assert!(err.to_string().contains("Unsupported session bundle version 2"));
+
+        let err = import_session(&manager, &store, r#"{"version": 1, "tabs": 3}"#, false).unwrap_err();
+        assert!(matches!(err, AppError::Parse(_)));
+
+        let err = import_session(&manager, &store, r#"{"tabs": []}"#, false).unwrap_err();
+        assert!(err.to_string().contains("version none"));
+
+        // Nothing was touched
+        assert!(manager.get_browser("tab-1").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_failed_tab_import_keeps_bookmarks() {
+        let (manager, store) = sample_state();
+        let mut bundle = parse_session(&export_session(&manager, &store).unwrap()).unwrap();
+        bundle.tabs[1].tab_id = bundle.tabs[0].tab_id.clone();
+        bundle.bookmarks.push(Bookmark { url: "https://new.dev".to_string(), ..bundle.bookmarks[0].clone() });
+        let json = serde_json::to_string(&bundle).unwrap();
+
+        let target = CefBrowserManager::new();
+        target.register_browser("tab-9".to_string(), "https://local.dev".to_string()).unwrap();
+        let target_store = BookmarkStore::new();
+        target_store.add("https://local.dev".to_string(), "Local".to_string()).unwrap();
+
+        let err = import_session(&target, &target_store, &json, false).unwrap_err();
+        assert!(matches!(err, AppError::Parse(_)));
+        assert!(target.get_browser("tab-9").unwrap().is_some());
+        assert_eq!(target_store.list().unwrap().len(), 1);
+    }
+}
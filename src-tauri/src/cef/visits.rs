@@ -0,0 +1,143 @@
+/// Visit statistics for the AI Browser
+///
+/// Counts how often each host was navigated to, for a "most visited sites" view,
+/// and how often and how recently each URL was visited, for frecency-ranked suggestions.
+
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+
+/// Visit counts per host
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VisitStats {
+    counts: HashMap<String, u64>,
+}
+
+impl VisitStats {
+    /// Create empty statistics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one visit to a host
+    pub fn record(&mut self, host: &str) {
+        *self.counts.entry(host.to_string()).or_insert(0) += 1;
+    }
+
+    /// Number of recorded visits to a host
+    pub fn visits(&self, host: &str) -> u64 {
+        self.counts.get(host).copied().unwrap_or(0)
+    }
+
+    /// The `n` most visited hosts with their counts, most visited first (ties by host name)
+    pub fn top_hosts(&self, n: usize) -> Vec<(String, u64)> {
+        let mut hosts: Vec<(String, u64)> = self.counts
+            .iter()
+            .map(|(host, count)| (host.clone(), *count))
+            .collect();
+        hosts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        hosts.truncate(n);
+        hosts
+    }
+}
+
+/// Visit count and most recent visit of one URL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct UrlVisits {
+    pub visit_count: u64,
+    pub last_visited_ms: u64,
+}
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Weight of a visit by its age, in the spirit of Firefox's frecency buckets
+fn recency_weight(age_ms: u64) -> u64 {
+    match age_ms / DAY_MS {
+        0..=3 => 100,
+        4..=13 => 70,
+        14..=30 => 50,
+        31..=90 => 30,
+        _ => 10,
+    }
+}
+
+/// Visit counts and last-visit times per URL
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VisitIndex {
+    urls: HashMap<String, UrlVisits>,
+}
+
+impl VisitIndex {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one visit to a URL at `now_ms`
+    pub fn record(&mut self, url: &str, now_ms: u64) {
+        let visits = self.urls.entry(url.to_string()).or_default();
+        visits.visit_count += 1;
+        visits.last_visited_ms = visits.last_visited_ms.max(now_ms);
+    }
+
+    /// Visits recorded for a URL
+    pub fn visits(&self, url: &str) -> Option<UrlVisits> {
+        self.urls.get(url).copied()
+    }
+
+    /// Frecency score at `now_ms`: visit count weighted by how recent the last visit was
+    fn score(visits: &UrlVisits, now_ms: u64) -> u64 {
+        visits.visit_count * recency_weight(now_ms.saturating_sub(visits.last_visited_ms))
+    }
+
+    /// The `n` highest-scoring URLs as (url, visit count, last visit ms), best first
+    /// Ties go to the more recent visit, then the URL
+    pub fn frecent(&self, n: usize, now_ms: u64) -> Vec<(String, u64, u64)> {
+        let mut ranked: Vec<(&String, &UrlVisits)> = self.urls.iter().collect();
+        ranked.sort_by(|a, b| {
+            Self::score(b.1, now_ms).cmp(&Self::score(a.1, now_ms))
+                .then_with(|| b.1.last_visited_ms.cmp(&a.1.last_visited_ms))
+                .then_with(|| a.0.cmp(b.0))
+        });
+        ranked.into_iter()
+            .take(n)
+            .map(|(url, visits)| (url.clone(), visits.visit_count, visits.last_visited_ms))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_hosts_order() {
+        let mut stats = VisitStats::new();
+        for host in ["b.com", "a.com", "c.com", "b.com", "c.com"] {
+            stats.record(host);
+        }
+
+        assert_eq!(stats.top_hosts(2), vec![("b.com".to_string(), 2), ("c.com".to_string(), 2)]);
+        assert_eq!(stats.visits("a.com"), 1);
+        assert!(stats.top_hosts(0).is_empty());
+    }
+
+    #[test]
+    fn test_frecent_urls_weigh_count_and_recency() {
+        let now = 100 * DAY_MS;
+        let mut index = VisitIndex::new();
+        index.record("https://a.com", now - 3 * DAY_MS);
+        index.record("https://a.com", now - 2 * DAY_MS);
+        index.record("https://b.com", now - DAY_MS);
+        // Visited often, but long ago
+        for _ in 0..5 {
+            index.record("https://old.com", now - 95 * DAY_MS);
+        }
+
+        let ranked: Vec<String> = index.frecent(3, now).into_iter().map(|(url, _, _)| url).collect();
+        assert_eq!(ranked, vec!["https://a.com", "https://b.com", "https://old.com"]);
+        assert_eq!(index.visits("https://a.com"), Some(UrlVisits { visit_count: 2, last_visited_ms: now - 2 * DAY_MS }));
+        assert!(index.frecent(0, now).is_empty());
+    }
+}
@@ -1,9 +1,12 @@
 use crate::error::AppError;
-use crate::fs::{self, FileEntry, watcher};
-use tauri::{AppHandle, Manager, WebviewWindowBuilder, WebviewBuilder, LogicalPosition, LogicalSize, Position, Size};
+use crate::fs::{self, Breadcrumb, DeletePreview, DeleteResult, FileEntry, FileLines, FrontMatter, LinkGraph, NoteStats, RecentEntry, RecentFiles, SearchHit, SortOrder, WorkspaceGuard, watcher};
+use crate::fs::watcher::WatcherState;
+use tauri::{AppHandle, Manager, State, WebviewWindowBuilder, WebviewBuilder, LogicalPosition, LogicalSize, Position, Size};
 use tauri::WebviewUrl;
 use tauri::webview::NewWindowResponse;
 use tauri::Emitter;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::io::Read;
 
 // Browser / WebView 调试日志，写入与前端相同的 debug-logs 目录，方便统一排查
@@ -15,21 +18,163 @@ fn browser_debug_log(app: &AppHandle, message: String) {
     });
 }
 
+/// Location of the persisted recent-files list in the app data dir
+pub fn recent_files_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let app_dir = app.path().app_data_dir()
+        .map_err(|e| AppError::InvalidPath(format!("Failed to get app dir: {}", e)))?;
+    Ok(app_dir.join("recent-files.json"))
+}
+
+/// Confine file commands to a workspace root
+/// Returns the canonical root
+#[tauri::command]
+pub async fn set_workspace_root(guard: State<'_, WorkspaceGuard>, root: String) -> Result<String, AppError> {
+    Ok(guard.set_root(&root)?.to_string_lossy().into_owned())
+}
+
 /// Read file content
+/// Successfully opened files are recorded in the recent-files list
+#[tauri::command]
+pub async fn read_file(
+    app: AppHandle,
+    recent: State<'_, RecentFiles>,
+    guard: State<'_, WorkspaceGuard>,
+    path: String,
+) -> Result<String, AppError> {
+    let path = guard.confine(&path)?;
+    let content = fs::read_file_content(&path)?;
+
+    recent.push(path);
+    if let Ok(recent_path) = recent_files_path(&app) {
+        let _ = recent.save(&recent_path);
+    }
+
+    Ok(content)
+}
+
+/// Read file content even if it looks binary, replacing invalid UTF-8, when `force` is set
+#[tauri::command]
+pub async fn read_file_lossy(guard: State<'_, WorkspaceGuard>, path: String, force: bool) -> Result<String, AppError> {
+    let path = guard.confine(&path)?;
+    fs::read_file_lossy(&path, force)
+}
+
+/// Read a text file in an explicit encoding (e.g. `utf-16le`, `windows-1252`)
+#[tauri::command]
+pub async fn read_file_encoded(guard: State<'_, WorkspaceGuard>, path: String, encoding: String) -> Result<String, AppError> {
+    let path = guard.confine(&path)?;
+    fs::read_file_with_encoding(&path, &encoding)
+}
+
+/// Guess a file's text encoding, returning a label usable with `read_file_encoded`
 #[tauri::command]
-pub async fn read_file(path: String) -> Result<String, AppError> {
-    fs::read_file_content(&path)
+pub async fn detect_file_encoding(guard: State<'_, WorkspaceGuard>, path: String) -> Result<String, AppError> {
+    let path = guard.confine(&path)?;
+    fs::detect_encoding(&path)
+}
+
+/// Read a line range of a file (0-based, end exclusive) along with its total line count
+/// Suited to large files such as logs that shouldn't be loaded whole
+#[tauri::command]
+pub async fn read_file_lines(
+    guard: State<'_, WorkspaceGuard>,
+    path: String,
+    start_line: usize,
+    end_line: usize,
+) -> Result<FileLines, AppError> {
+    let path = guard.confine(&path)?;
+    fs::read_file_lines(&path, start_line, end_line)
+}
+
+/// Get recently opened files, most recent first
+#[tauri::command]
+pub async fn get_recent_files(recent: State<'_, RecentFiles>) -> Result<Vec<RecentEntry>, AppError> {
+    Ok(recent.list())
+}
+
+/// Clear the recently opened files list
+#[tauri::command]
+pub async fn clear_recent_files(app: AppHandle, recent: State<'_, RecentFiles>) -> Result<(), AppError> {
+    recent.clear();
+    recent.save(&recent_files_path(&app)?)
 }
 
 /// Save file content
+/// If `expected_modified_ms` is given and the file changed on disk since then,
+/// returns `AppError::Conflict` instead of overwriting
+#[tauri::command]
+pub async fn save_file(
+    guard: State<'_, WorkspaceGuard>,
+    path: String,
+    content: String,
+    expected_modified_ms: Option<u64>,
+) -> Result<(), AppError> {
+    let path = guard.confine(&path)?;
+    fs::write_file_content_checked(&path, &content, expected_modified_ms)
+}
+
+/// Save file content, first backing up the previous contents into `.lumina-backups`
+/// Keeps at most `keep` (default 5) backups per file
+#[tauri::command]
+pub async fn save_file_with_backup(
+    guard: State<'_, WorkspaceGuard>,
+    path: String,
+    content: String,
+    keep: Option<usize>,
+) -> Result<(), AppError> {
+    let path = guard.confine(&path)?;
+    fs::write_file_with_backup(&path, &content, keep.unwrap_or(fs::DEFAULT_BACKUP_KEEP))
+}
+
+/// Append text to a note (quick capture), creating it if needed
 #[tauri::command]
-pub async fn save_file(path: String, content: String) -> Result<(), AppError> {
-    fs::write_file_content(&path, &content)
+pub async fn append_note(guard: State<'_, WorkspaceGuard>, path: String, content: String) -> Result<(), AppError> {
+    let path = guard.confine(&path)?;
+    fs::append_to_file(&path, &content)
+}
+
+/// Get a hex SHA-256 checksum of a file, for change detection
+#[tauri::command]
+pub async fn get_file_checksum(guard: State<'_, WorkspaceGuard>, path: String) -> Result<String, AppError> {
+    let path = guard.confine(&path)?;
+    fs::file_checksum(&path)
+}
+
+/// Get the parsed YAML front matter of a note, or None if it has none (or it is malformed)
+#[tauri::command]
+pub async fn get_front_matter(guard: State<'_, WorkspaceGuard>, path: String) -> Result<Option<FrontMatter>, AppError> {
+    let path = guard.confine(&path)?;
+    let content = fs::read_file_content(&path)?;
+    Ok(fs::parse_front_matter(&content).0)
+}
+
+/// Get the total size in bytes of a folder's contents
+/// Entries that can't be read are skipped
+#[tauri::command]
+pub async fn get_dir_size(guard: State<'_, WorkspaceGuard>, path: String) -> Result<u64, AppError> {
+    let path = guard.confine(&path)?;
+    fs::dir_size(&path)
+}
+
+/// Get the breadcrumb trail for a path, from the workspace root (or filesystem root) down
+#[tauri::command]
+pub async fn get_breadcrumbs(guard: State<'_, WorkspaceGuard>, path: String) -> Result<Vec<Breadcrumb>, AppError> {
+    let path = guard.confine(&path)?;
+    fs::list_ancestors_within(&path, guard.root().as_deref())
+}
+
+/// Get word count and reading time statistics for a note
+#[tauri::command]
+pub async fn get_note_stats(guard: State<'_, WorkspaceGuard>, path: String) -> Result<NoteStats, AppError> {
+    let path = guard.confine(&path)?;
+    let content = fs::read_file_content(&path)?;
+    Ok(fs::compute_stats(&content))
 }
 
 /// Write binary file (for images, etc.)
 #[tauri::command]
-pub async fn write_binary_file(path: String, data: Vec<u8>) -> Result<(), AppError> {
+pub async fn write_binary_file(guard: State<'_, WorkspaceGuard>, path: String, data: Vec<u8>) -> Result<(), AppError> {
+    let path = guard.confine(&path)?;
     let path = std::path::Path::new(&path);
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
@@ -39,24 +184,80 @@ pub async fn write_binary_file(path: String, data: Vec<u8>) -> Result<(), AppErr
 
 /// Read binary file and return as base64
 #[tauri::command]
-pub async fn read_binary_file_base64(path: String) -> Result<String, AppError> {
+pub async fn read_binary_file_base64(guard: State<'_, WorkspaceGuard>, path: String) -> Result<String, AppError> {
     use base64::{Engine as _, engine::general_purpose::STANDARD};
+    let path = guard.confine(&path)?;
     let data = std::fs::read(&path)?;
     Ok(STANDARD.encode(&data))
 }
 
 /// List directory with file tree
+/// `ignore` takes gitignore-style patterns; defaults to `.git`, `node_modules`, `target`
+/// `sort` orders each level (directories first); defaults to natural name order
+#[tauri::command]
+pub async fn list_directory(
+    guard: State<'_, WorkspaceGuard>,
+    path: String,
+    ignore: Option<Vec<String>>,
+    sort: Option<SortOrder>,
+) -> Result<Vec<FileEntry>, AppError> {
+    let path = guard.confine(&path)?;
+    fs::list_dir_recursive(&path, ignore.as_deref(), sort.unwrap_or_default())
+}
+
+/// Full-text search across a notes directory
 #[tauri::command]
-pub async fn list_directory(path: String) -> Result<Vec<FileEntry>, AppError> {
-    fs::list_dir_recursive(&path)
+pub async fn search_notes(
+    guard: State<'_, WorkspaceGuard>,
+    root: String,
+    query: String,
+    case_sensitive: Option<bool>,
+) -> Result<Vec<SearchHit>, AppError> {
+    let root = guard.confine(&root)?;
+    fs::search_in_dir(&root, &query, case_sensitive.unwrap_or(false))
+}
+
+/// Build an index from tag (lowercased) to the notes declaring it
+/// Tags come from front matter `tags` and inline `#tags`
+#[tauri::command]
+pub async fn get_tag_index(
+    guard: State<'_, WorkspaceGuard>,
+    root: String,
+) -> Result<HashMap<String, Vec<String>>, AppError> {
+    let root = guard.confine(&root)?;
+    fs::build_tag_index(&root)
+}
+
+/// Build the graph of wiki and Markdown links between the notes of a vault
+/// Dangling links point to `missing:<target>` placeholder nodes
+#[tauri::command]
+pub async fn get_link_graph(guard: State<'_, WorkspaceGuard>, root: String) -> Result<LinkGraph, AppError> {
+    let root = guard.confine(&root)?;
+    fs::build_link_graph(&root)
+}
+
+/// List files in a notes directory matching a glob pattern such as `**/*.md`
+#[tauri::command]
+pub async fn glob_notes(
+    guard: State<'_, WorkspaceGuard>,
+    root: String,
+    pattern: String,
+) -> Result<Vec<String>, AppError> {
+    let root = guard.confine(&root)?;
+    fs::glob_files(&root, &pattern)
 }
 
 /// List directory tree as formatted string (for Agent context)
 #[tauri::command]
-pub async fn list_directory_tree(path: String, max_depth: Option<usize>) -> Result<String, AppError> {
+pub async fn list_directory_tree(
+    guard: State<'_, WorkspaceGuard>,
+    path: String,
+    max_depth: Option<usize>,
+) -> Result<String, AppError> {
     use walkdir::WalkDir;
     use std::path::Path;
     
+    let path = guard.confine(&path)?;
     let max_depth = max_depth.unwrap_or(3);
     let base_path = Path::new(&path);
     let mut result = Vec::new();
@@ -97,45 +298,152 @@ pub async fn list_directory_tree(path: String, max_depth: Option<usize>) -> Resu
 
 /// Create a new file
 #[tauri::command]
-pub async fn create_file(path: String) -> Result<(), AppError> {
+pub async fn create_file(guard: State<'_, WorkspaceGuard>, path: String) -> Result<(), AppError> {
+    let path = guard.confine(&path)?;
     fs::create_new_file(&path)
 }
 
-/// Create a new directory
+/// Create a new file with initial content (e.g. a note from a template)
+#[tauri::command]
+pub async fn create_file_with_content(
+    guard: State<'_, WorkspaceGuard>,
+    path: String,
+    content: String,
+) -> Result<(), AppError> {
+    let path = guard.confine(&path)?;
+    fs::create_new_file_with_content(&path, &content)
+}
+
+/// Create a new note from a template, substituting `{{key}}` placeholders
+#[tauri::command]
+pub async fn create_note_from_template(
+    guard: State<'_, WorkspaceGuard>,
+    target_path: String,
+    template_path: String,
+    vars: Option<HashMap<String, String>>,
+) -> Result<(), AppError> {
+    let target_path = guard.confine(&target_path)?;
+    let template_path = guard.confine(&template_path)?;
+    fs::create_from_template(&target_path, &template_path, vars.unwrap_or_default())
+}
+
+/// Export a Markdown note to a standalone HTML file
+/// `html_path` comes from the save dialog and may lie outside the workspace
 #[tauri::command]
-pub async fn create_dir(path: String) -> Result<(), AppError> {
-    fs::create_new_dir(&path)
+pub async fn export_note_html(
+    guard: State<'_, WorkspaceGuard>,
+    md_path: String,
+    html_path: String,
+    title: Option<String>,
+) -> Result<(), AppError> {
+    let md_path = guard.confine(&md_path)?;
+    fs::export_markdown_to_html(&md_path, &html_path, title)
+}
+
+/// Create a new directory; `create_dir_all` also creates missing parents
+#[tauri::command]
+pub async fn create_dir(
+    guard: State<'_, WorkspaceGuard>,
+    path: String,
+    create_dir_all: Option<bool>,
+) -> Result<(), AppError> {
+    let path = guard.confine(&path)?;
+    fs::create_new_dir(&path, create_dir_all.unwrap_or(false))
 }
 
 /// Delete a file or directory
+/// Moves to the trash unless `permanent` is true
+#[tauri::command]
+pub async fn delete_file(
+    guard: State<'_, WorkspaceGuard>,
+    path: String,
+    permanent: Option<bool>,
+) -> Result<(), AppError> {
+    let path = guard.confine(&path)?;
+    fs::delete_entry(&path, permanent.unwrap_or(false))
+}
+
+/// Preview what deleting a file or directory would remove, without deleting anything
 #[tauri::command]
-pub async fn delete_file(path: String) -> Result<(), AppError> {
-    fs::delete_entry(&path)
+pub async fn preview_delete(guard: State<'_, WorkspaceGuard>, path: String) -> Result<DeletePreview, AppError> {
+    let path = guard.confine(&path)?;
+    fs::list_deletable(&path)
+}
+
+/// Delete several files or directories, reporting success or failure per path
+/// Moves to the trash unless `permanent` is true; one failure doesn't stop the rest
+#[tauri::command]
+pub async fn delete_files(
+    guard: State<'_, WorkspaceGuard>,
+    paths: Vec<String>,
+    permanent: Option<bool>,
+) -> Result<Vec<DeleteResult>, AppError> {
+    let confined: Vec<Result<String, AppError>> = paths.iter().map(|path| guard.confine(path)).collect();
+    let allowed: Vec<String> = confined.iter().filter_map(|c| c.as_ref().ok().cloned()).collect();
+    let mut deleted = fs::delete_many(&allowed, permanent.unwrap_or(false)).into_iter();
+
+    // Paths outside the workspace fail in place, keeping results in request order
+    Ok(paths
+        .into_iter()
+        .zip(confined)
+        .filter_map(|(path, confined)| match confined {
+            Ok(_) => deleted.next().map(|(path, result)| DeleteResult::new(path, &result)),
+            Err(e) => Some(DeleteResult::new(path, &Err(e))),
+        })
+        .collect())
 }
 
 /// Rename/move a file
+/// Refuses to replace an existing destination unless `overwrite` is true
 #[tauri::command]
-pub async fn rename_file(old_path: String, new_path: String) -> Result<(), AppError> {
-    fs::rename_entry(&old_path, &new_path)
+pub async fn rename_file(
+    guard: State<'_, WorkspaceGuard>,
+    old_path: String,
+    new_path: String,
+    overwrite: Option<bool>,
+) -> Result<(), AppError> {
+    let old_path = guard.confine(&old_path)?;
+    let new_path = guard.confine(&new_path)?;
+    fs::rename_entry(&old_path, &new_path, overwrite.unwrap_or(false))
+}
+
+/// Copy a file or directory
+/// Refuses to overwrite an existing destination unless `overwrite` is true
+#[tauri::command]
+pub async fn copy_file(
+    guard: State<'_, WorkspaceGuard>,
+    src: String,
+    dst: String,
+    overwrite: Option<bool>,
+) -> Result<(), AppError> {
+    let src = guard.confine(&src)?;
+    let dst = guard.confine(&dst)?;
+    fs::copy_entry(&src, &dst, overwrite.unwrap_or(false))
 }
 
 /// Move a file to a target folder
 /// Returns the new path of the moved file
 #[tauri::command]
-pub async fn move_file(source: String, target_folder: String) -> Result<String, AppError> {
+pub async fn move_file(guard: State<'_, WorkspaceGuard>, source: String, target_folder: String) -> Result<String, AppError> {
+    let source = guard.confine(&source)?;
+    let target_folder = guard.confine(&target_folder)?;
     fs::move_file_to_folder(&source, &target_folder)
 }
 
 /// Move a folder to a target folder
 /// Returns the new path of the moved folder
 #[tauri::command]
-pub async fn move_folder(source: String, target_folder: String) -> Result<String, AppError> {
+pub async fn move_folder(guard: State<'_, WorkspaceGuard>, source: String, target_folder: String) -> Result<String, AppError> {
+    let source = guard.confine(&source)?;
+    let target_folder = guard.confine(&target_folder)?;
     fs::move_folder_to_folder(&source, &target_folder)
 }
 
 /// Show file/folder in system file explorer
 #[tauri::command]
-pub async fn show_in_explorer(path: String) -> Result<(), AppError> {
+pub async fn show_in_explorer(guard: State<'_, WorkspaceGuard>, path: String) -> Result<(), AppError> {
+    let path = guard.confine(&path)?;
+
     #[cfg(target_os = "windows")]
     {
         std::process::Command::new("explorer")
@@ -165,6 +473,46 @@ pub async fn show_in_explorer(path: String) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Only http(s) URLs may be handed to the system browser
+fn validate_external_url(url: &str) -> Result<(), AppError> {
+    let lower = url.trim().to_ascii_lowercase();
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(AppError::InvalidPath(format!("Unsupported URL scheme: {}", url)))
+    }
+}
+
+/// Open a URL in the system default browser
+#[tauri::command]
+pub async fn open_external_url(url: String) -> Result<(), AppError> {
+    validate_external_url(&url)?;
+    let url = url.trim();
+
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg(url)
+            .spawn()?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(url)
+            .spawn()?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(url)
+            .spawn()?;
+    }
+
+    Ok(())
+}
+
 /// 在主窗口内创建内嵌 WebView
 #[tauri::command]
 pub async fn create_embedded_webview(
@@ -576,11 +924,41 @@ pub struct VideoTimeInfo {
 /// Start file system watcher
 /// Emits "fs:change" events when files are created, modified, or deleted
 #[tauri::command]
-pub async fn start_file_watcher(app: AppHandle, watch_path: String) -> Result<(), AppError> {
+pub async fn start_file_watcher(
+    app: AppHandle,
+    guard: State<'_, WorkspaceGuard>,
+    watch_path: String,
+) -> Result<(), AppError> {
+    let watch_path = guard.confine(&watch_path)?;
     watcher::start_watcher(app, watch_path)
         .map_err(|e| AppError::InvalidPath(e))
 }
 
+/// Start watching a workspace root
+/// Emits debounced "fs:changed" events; watchers of a previous workspace are torn down
+#[tauri::command]
+pub async fn start_watching(
+    app: AppHandle,
+    guard: State<'_, WorkspaceGuard>,
+    state: tauri::State<'_, WatcherState>,
+    root: String,
+) -> Result<(), AppError> {
+    let root = guard.confine(&root)?;
+    state.start(&root, app)
+}
+
+/// Stop watching a workspace root
+/// Returns false if the root wasn't being watched
+#[tauri::command]
+pub async fn stop_watching(
+    guard: State<'_, WorkspaceGuard>,
+    state: tauri::State<'_, WatcherState>,
+    root: String,
+) -> Result<bool, AppError> {
+    let root = guard.confine(&root)?;
+    state.stop(&root)
+}
+
 #[derive(serde::Serialize, Clone)]
 pub struct BrowserNewTabEventPayload {
     pub parent_tab_id: String,
@@ -873,3 +1251,42 @@ pub async fn browser_webview_exists(app: AppHandle, tab_id: String) -> Result<bo
     let webview_id = format!("browser-{}", tab_id);
     Ok(app.get_webview(&webview_id).is_some())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_external_url() {
+        assert!(validate_external_url("https://example.com").is_ok());
+        assert!(validate_external_url("http://example.com/path?q=1").is_ok());
+        assert!(validate_external_url("HTTPS://EXAMPLE.COM").is_ok());
+
+        assert!(matches!(validate_external_url("file:///etc/passwd"), Err(AppError::InvalidPath(_))));
+        assert!(matches!(validate_external_url("javascript:alert(1)"), Err(AppError::InvalidPath(_))));
+        assert!(validate_external_url("example.com").is_err());
+        assert!(validate_external_url("").is_err());
+    }
+
+    #[test]
+    fn test_list_directory_tree_rejects_paths_outside_workspace() {
+        let workspace = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let app = tauri::test::mock_app();
+        app.manage(WorkspaceGuard::with_root(workspace.path().to_str().unwrap()).unwrap());
+
+        let result = tauri::async_runtime::block_on(list_directory_tree(
+            app.state::<WorkspaceGuard>(),
+            outside.path().to_string_lossy().into_owned(),
+            None,
+        ));
+        assert!(matches!(result, Err(AppError::OutsideWorkspace(_))));
+
+        let result = tauri::async_runtime::block_on(list_directory_tree(
+            app.state::<WorkspaceGuard>(),
+            workspace.path().to_string_lossy().into_owned(),
+            None,
+        ));
+        assert!(result.is_ok());
+    }
+}
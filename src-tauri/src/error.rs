@@ -1,19 +1,26 @@
+use serde::ser::SerializeStruct;
 use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+    Io(#[source] std::io::Error),
 
-    #[error("File not found: {0}")]
-    FileNotFound(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
 
     #[error("Invalid path: {0}")]
     InvalidPath(String),
 
-    #[error("File already exists: {0}")]
-    FileExists(String),
+    #[error("Already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("Lock poisoned: {0}")]
+    LockPoisoned(String),
 
     #[error("Database error: {0}")]
     Database(String),
@@ -23,13 +30,124 @@ pub enum AppError {
 
     #[error("WebDAV error: {0}")]
     WebDAV(String),
+
+    #[error("File was modified externally: {0}")]
+    Conflict(String),
+
+    #[error("Navigation blocked: {0}")]
+    Blocked(String),
+
+    #[error("Path is outside the workspace: {0}")]
+    OutsideWorkspace(String),
+
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    #[error("Parse error: {0}")]
+    Parse(String),
+
+    #[error("Invalid state: {0}")]
+    InvalidState(String),
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("File looks binary ({size} bytes): {path}")]
+    BinaryFile { path: String, size: u64 },
 }
 
+impl AppError {
+    /// Machine-readable error code for the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Io(_) => "io",
+            AppError::NotFound(_) => "not_found",
+            AppError::PermissionDenied(_) => "permission_denied",
+            AppError::InvalidPath(_) => "invalid_path",
+            AppError::AlreadyExists(_) => "already_exists",
+            AppError::LockPoisoned(_) => "lock_poisoned",
+            AppError::Database(_) => "database",
+            AppError::Trash(_) => "trash",
+            AppError::WebDAV(_) => "webdav",
+            AppError::Conflict(_) => "conflict",
+            AppError::Blocked(_) => "blocked",
+            AppError::OutsideWorkspace(_) => "outside_workspace",
+            AppError::Timeout(_) => "timeout",
+            AppError::Parse(_) => "parse",
+            AppError::InvalidState(_) => "invalid_state",
+            AppError::InvalidArgument(_) => "invalid_argument",
+            AppError::BinaryFile { .. } => "binary_file",
+        }
+    }
+}
+
+/// Map IO errors onto the dedicated variants where the kind is meaningful
+impl From<std::io::Error> for AppError {
+    fn from(err: std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::NotFound => AppError::NotFound(err.to_string()),
+            std::io::ErrorKind::PermissionDenied => AppError::PermissionDenied(err.to_string()),
+            std::io::ErrorKind::AlreadyExists => AppError::AlreadyExists(err.to_string()),
+            _ => AppError::Io(err),
+        }
+    }
+}
+
+/// Serialized as `{ code, message }`, plus `size` for `BinaryFile`
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        let size = match self {
+            AppError::BinaryFile { size, .. } => Some(*size),
+            _ => None,
+        };
+        let mut state = serializer.serialize_struct("AppError", if size.is_some() { 3 } else { 2 })?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        if let Some(size) = size {
+            state.serialize_field("size", &size)?;
+        }
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_error_kind_mapping() {
+        let err: AppError = std::io::Error::new(std::io::ErrorKind::NotFound, "missing").into();
+        assert!(matches!(err, AppError::NotFound(_)));
+
+        let err: AppError = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied").into();
+        assert!(matches!(err, AppError::PermissionDenied(_)));
+
+        let err: AppError = std::io::Error::new(std::io::ErrorKind::AlreadyExists, "exists").into();
+        assert!(matches!(err, AppError::AlreadyExists(_)));
+
+        let err: AppError = std::io::Error::new(std::io::ErrorKind::Other, "boom").into();
+        assert_eq!(err.code(), "io");
+    }
+
+    #[test]
+    fn test_serialize_code_and_message() {
+        let err = AppError::NotFound("/tmp/missing.md".to_string());
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(json["code"], "not_found");
+        assert_eq!(json["message"], "Not found: /tmp/missing.md");
+    }
+
+    #[test]
+    fn test_serialize_binary_file_size() {
+        let err = AppError::BinaryFile { path: "/tmp/photo.png".to_string(), size: 2048 };
+        let json = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(json["code"], "binary_file");
+        assert_eq!(json["size"], 2048);
+        assert!(serde_json::to_value(AppError::Timeout("x".into())).unwrap().get("size").is_none());
     }
 }
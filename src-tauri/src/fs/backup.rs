@@ -0,0 +1,123 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+use super::manager::write_file_content;
+
+/// Sibling directory holding backups; hidden, so directory listings skip it
+pub const BACKUP_DIR_NAME: &str = ".lumina-backups";
+
+/// Number of backups kept per file when the caller doesn't say
+pub const DEFAULT_BACKUP_KEEP: usize = 5;
+
+/// Backups of `path` (`<filename>.<timestamp>.bak`), oldest first
+pub fn list_backups(path: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Err(AppError::InvalidPath(path.display().to_string()));
+    };
+    let backup_dir = parent.join(BACKUP_DIR_NAME);
+    if !backup_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let prefix = format!("{}.", file_name.to_string_lossy());
+    let mut backups: Vec<(u64, PathBuf)> = fs::read_dir(&backup_dir)?
+        .filter_map(|e| e.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let timestamp = name.strip_prefix(&prefix)?.strip_suffix(".bak")?.parse().ok()?;
+            Some((timestamp, entry.path()))
+        })
+        .collect();
+    backups.sort();
+
+    Ok(backups.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Copy the current contents of `path` into the backup directory, then prune to `keep` backups
+fn backup_existing(path: &Path, keep: usize) -> Result<(), AppError> {
+    let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Err(AppError::InvalidPath(path.display().to_string()));
+    };
+    let backup_dir = parent.join(BACKUP_DIR_NAME);
+    fs::create_dir_all(&backup_dir)?;
+
+    let mut timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    // Saves within the same millisecond still get distinct, ordered names
+    let backup = loop {
+        let candidate = backup_dir.join(format!("{}.{}.bak", file_name.to_string_lossy(), timestamp));
+        if !candidate.exists() {
+            break candidate;
+        }
+        timestamp += 1;
+    };
+    fs::copy(path, &backup)?;
+
+    let backups = list_backups(path)?;
+    let excess = backups.len().saturating_sub(keep);
+    for old in &backups[..excess] {
+        fs::remove_file(old)?;
+    }
+
+    Ok(())
+}
+
+/// Write content to a file, first backing up the contents it replaces
+/// At most `keep` backups are kept per file, the oldest being deleted first;
+/// `keep == 0` writes without a backup.
+pub fn write_file_with_backup(path: &str, content: &str, keep: usize) -> Result<(), AppError> {
+    let target = Path::new(path);
+    if keep > 0 && target.is_file() {
+        backup_existing(target, keep)?;
+    }
+    write_file_content(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::{list_dir_recursive, SortOrder};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_backups_are_capped() {
+        let dir = tempdir().unwrap();
+        let note = dir.path().join("note.md");
+        let path = note.to_str().unwrap();
+
+        for i in 0..8 {
+            write_file_with_backup(path, &format!("version {}", i), 3).unwrap();
+        }
+
+        let backups = list_backups(&note).unwrap();
+        assert_eq!(backups.len(), 3);
+        assert_eq!(fs::read_to_string(&note).unwrap(), "version 7");
+        assert_eq!(fs::read_to_string(backups.last().unwrap()).unwrap(), "version 6");
+        assert_eq!(fs::read_to_string(&backups[0]).unwrap(), "version 4");
+    }
+
+    #[test]
+    fn test_new_file_has_no_backup() {
+        let dir = tempdir().unwrap();
+        let note = dir.path().join("new.md");
+
+        write_file_with_backup(note.to_str().unwrap(), "first", DEFAULT_BACKUP_KEEP).unwrap();
+        assert!(list_backups(&note).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_backup_dir_hidden_from_listing() {
+        let dir = tempdir().unwrap();
+        let note = dir.path().join("note.md");
+        fs::write(&note, "old").unwrap();
+        write_file_with_backup(note.to_str().unwrap(), "new", DEFAULT_BACKUP_KEEP).unwrap();
+        assert!(dir.path().join(BACKUP_DIR_NAME).is_dir());
+
+        let entries = list_dir_recursive(dir.path().to_str().unwrap(), None, SortOrder::default()).unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["note.md"]);
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use super::guard::normalize_components;
+use super::manager::resolve_lexically;
+
+/// One level of a breadcrumb bar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Breadcrumb {
+    pub name: String,
+    pub full_path: String,
+}
+
+fn breadcrumb(path: &Path) -> Breadcrumb {
+    let full_path = path.to_string_lossy().into_owned();
+    let name = path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        // The filesystem root (or a drive prefix) has no file name of its own
+        .unwrap_or_else(|| full_path.clone());
+    Breadcrumb { name, full_path }
+}
+
+/// List the ancestors of `path`, from the filesystem root down to the path itself
+pub fn list_ancestors(path: &str) -> Result<Vec<Breadcrumb>, AppError> {
+    list_ancestors_within(path, None)
+}
+
+/// List the ancestors of `path` from `root` (when given) down to the path itself
+/// Levels above the root are not exposed; a path outside it returns `AppError::OutsideWorkspace`.
+pub fn list_ancestors_within(path: &str, root: Option<&Path>) -> Result<Vec<Breadcrumb>, AppError> {
+    if path.is_empty() {
+        return Err(AppError::InvalidPath("path cannot be empty".into()));
+    }
+    let resolved = normalize_components(&resolve_lexically(Path::new(path)));
+    let root: Option<PathBuf> = root.map(|root| normalize_components(&resolve_lexically(root)));
+    if let Some(root) = &root {
+        if !resolved.starts_with(root) {
+            return Err(AppError::OutsideWorkspace(path.to_string()));
+        }
+    }
+
+    let mut crumbs: Vec<Breadcrumb> = resolved.ancestors()
+        .take_while(|ancestor| root.as_ref().map_or(true, |root| ancestor.starts_with(root)))
+        .filter(|ancestor| !ancestor.as_os_str().is_empty())
+        .map(breadcrumb)
+        .collect();
+    crumbs.reverse();
+    Ok(crumbs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_ancestors_without_root() {
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("vault").join("notes").join("daily");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let crumbs = list_ancestors(nested.to_str().unwrap()).unwrap();
+        let names: Vec<&str> = crumbs.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.ends_with(&["vault", "notes", "daily"]));
+        // Walks all the way up to the filesystem root
+        assert!(Path::new(&crumbs[0].full_path).parent().is_none());
+        assert_eq!(Path::new(&crumbs.last().unwrap().full_path), nested.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_ancestors_stop_at_workspace_root() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("vault");
+        let nested = root.join("notes").join("daily");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let crumbs = list_ancestors_within(nested.to_str().unwrap(), Some(&root)).unwrap();
+        let names: Vec<&str> = crumbs.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["vault", "notes", "daily"]);
+        assert_eq!(Path::new(&crumbs[1].full_path), root.canonicalize().unwrap().join("notes"));
+
+        let outside = list_ancestors_within(dir.path().to_str().unwrap(), Some(&root));
+        assert!(matches!(outside, Err(AppError::OutsideWorkspace(_))));
+    }
+}
@@ -0,0 +1,153 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use chardetng::EncodingDetector;
+use encoding_rs::Encoding;
+
+use crate::error::AppError;
+
+/// Look up a decoder by its WHATWG label (`utf-8`, `utf-16le`, `windows-1252`, `latin1`, ...)
+fn encoding_for_label(label: &str) -> Result<&'static Encoding, AppError> {
+    Encoding::for_label(label.trim().as_bytes())
+        // The "replacement" encoding decodes everything to U+FFFD, which is never what a reader wants
+        .filter(|encoding| *encoding != encoding_rs::REPLACEMENT)
+        .ok_or_else(|| AppError::InvalidPath(format!("Unknown encoding: {}", label)))
+}
+
+/// Read a text file in the given encoding
+/// A byte order mark matching the encoding is stripped; malformed sequences become U+FFFD.
+pub fn read_file_with_encoding(path: &str, encoding: &str) -> Result<String, AppError> {
+    let encoding = encoding_for_label(encoding)?;
+    let path = Path::new(path);
+    if !path.exists() {
+        return Err(AppError::NotFound(path.display().to_string()));
+    }
+    let bytes = fs::read(path)?;
+    let (text, _had_errors) = encoding.decode_with_bom_removal(&bytes);
+    Ok(text.into_owned())
+}
+
+/// How much of a file is sniffed when detecting its encoding
+const DETECT_SAMPLE_LEN: u64 = 64 * 1024;
+
+/// Guess UTF-16 without a BOM from where the zero bytes fall
+/// Mostly-ASCII UTF-16 text has a zero in every other byte; which half they sit in gives the byte order.
+fn sniff_utf16(sample: &[u8]) -> Option<&'static Encoding> {
+    if sample.len() < 4 {
+        return None;
+    }
+    let pairs = sample.len() / 2;
+    let zeros_at = |offset: usize| sample.iter().skip(offset).step_by(2).take(pairs).filter(|b| **b == 0).count();
+    let (even, odd) = (zeros_at(0), zeros_at(1));
+    // Require zeros in at least 40% of one half and hardly any in the other
+    if odd * 5 >= pairs * 2 && even * 10 < pairs {
+        Some(encoding_rs::UTF_16LE)
+    } else if even * 5 >= pairs * 2 && odd * 10 < pairs {
+        Some(encoding_rs::UTF_16BE)
+    } else {
+        None
+    }
+}
+
+/// Guess the text encoding of a sample, returning its label (e.g. `"UTF-8"`, `"UTF-16LE"`, `"windows-1252"`)
+fn detect_sample_encoding(sample: &[u8], truncated: bool) -> &'static str {
+    if let Some((encoding, _bom_len)) = Encoding::for_bom(sample) {
+        return encoding.name();
+    }
+    if let Some(encoding) = sniff_utf16(sample) {
+        return encoding.name();
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => return encoding_rs::UTF_8.name(),
+        // The sample may end in the middle of a multi-byte sequence
+        Err(e) if truncated && e.error_len().is_none() => return encoding_rs::UTF_8.name(),
+        Err(_) => {}
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(sample, !truncated);
+    detector.guess(None, true).name()
+}
+
+/// Detect the text encoding of a file from its byte order mark or content
+/// Only the first 64 KiB are examined; the returned label can be passed to `read_file_with_encoding`.
+pub fn detect_encoding(path: &str) -> Result<String, AppError> {
+    let path = Path::new(path);
+    if !path.exists() {
+        return Err(AppError::NotFound(path.display().to_string()));
+    }
+    let mut sample = Vec::new();
+    let file = fs::File::open(path)?;
+    let total = file.metadata()?.len();
+    file.take(DETECT_SAMPLE_LEN).read_to_end(&mut sample)?;
+    Ok(detect_sample_encoding(&sample, total > sample.len() as u64).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(text.encode_utf16().flat_map(|unit| unit.to_le_bytes()));
+        bytes
+    }
+
+    #[test]
+    fn test_read_utf16le() {
+        let dir = tempdir().unwrap();
+        let note = dir.path().join("windows.txt");
+        fs::write(&note, utf16le("# Title\r\nnaïve café ☕")).unwrap();
+
+        let text = read_file_with_encoding(note.to_str().unwrap(), "UTF-16LE").unwrap();
+        assert_eq!(text, "# Title\r\nnaïve café ☕");
+    }
+
+    #[test]
+    fn test_read_windows_1252() {
+        let dir = tempdir().unwrap();
+        let note = dir.path().join("latin1.txt");
+        fs::write(&note, b"caf\xe9 \x80").unwrap();
+
+        assert_eq!(read_file_with_encoding(note.to_str().unwrap(), "windows-1252").unwrap(), "café €");
+    }
+
+    #[test]
+    fn test_rejects_unknown_encoding() {
+        let dir = tempdir().unwrap();
+        let note = dir.path().join("note.txt");
+        fs::write(&note, "text").unwrap();
+
+        let result = read_file_with_encoding(note.to_str().unwrap(), "klingon-8");
+        assert!(matches!(result, Err(AppError::InvalidPath(_))));
+    }
+
+    #[test]
+    fn test_detect_utf8_bom() {
+        let dir = tempdir().unwrap();
+        let note = dir.path().join("bom.md");
+        fs::write(&note, b"\xEF\xBB\xBFnaive caf\xC3\xA9").unwrap();
+
+        assert_eq!(detect_encoding(note.to_str().unwrap()).unwrap(), "UTF-8");
+    }
+
+    #[test]
+    fn test_detect_utf16le() {
+        let dir = tempdir().unwrap();
+        let with_bom = dir.path().join("bom.txt");
+        fs::write(&with_bom, utf16le("Meeting notes from Monday")).unwrap();
+        assert_eq!(detect_encoding(with_bom.to_str().unwrap()).unwrap(), "UTF-16LE");
+
+        let without_bom = dir.path().join("plain.txt");
+        fs::write(&without_bom, &utf16le("Meeting notes from Monday")[2..]).unwrap();
+        assert_eq!(detect_encoding(without_bom.to_str().unwrap()).unwrap(), "UTF-16LE");
+    }
+
+    #[test]
+    fn test_detect_plain_and_legacy_text() {
+        assert_eq!(detect_sample_encoding("# Title\nnaïve café".as_bytes(), false), "UTF-8");
+        assert_eq!(detect_sample_encoding(b"Le caf\xe9 est tr\xe8s bon, d\xe9j\xe0 vu.", false), "windows-1252");
+    }
+}
@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use pulldown_cmark::{html, Event, Options, Parser};
+
+use crate::error::AppError;
+use super::manager::atomic_write;
+
+/// Minimal stylesheet embedded in exported notes
+const EXPORT_CSS: &str = r#"
+body { max-width: 760px; margin: 2rem auto; padding: 0 1rem; font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; line-height: 1.6; color: #222; }
+h1, h2, h3, h4, h5, h6 { line-height: 1.25; margin-top: 1.5em; }
+pre { background: #f6f8fa; padding: 0.75rem 1rem; border-radius: 6px; overflow-x: auto; }
+code { font-family: "SF Mono", Consolas, monospace; font-size: 0.9em; }
+blockquote { margin: 0; padding-left: 1rem; border-left: 4px solid #ddd; color: #555; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ddd; padding: 0.25rem 0.5rem; }
+img { max-width: 100%; }
+"#;
+
+/// Escape text for use in HTML element content
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render Markdown into a standalone HTML document
+/// Raw HTML in the source is escaped rather than passed through
+pub(crate) fn render_markdown_document(markdown: &str, title: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let parser = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        other => other,
+    });
+
+    let mut body = String::new();
+    html::push_html(&mut body, parser);
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        EXPORT_CSS,
+        body
+    )
+}
+
+/// Export a Markdown note to a standalone HTML file
+/// `title` defaults to the note's file stem
+pub fn export_markdown_to_html(md_path: &str, html_path: &str, title: Option<String>) -> Result<(), AppError> {
+    let source = Path::new(md_path);
+    if !source.is_file() {
+        return Err(AppError::NotFound(md_path.to_string()));
+    }
+    let markdown = std::fs::read_to_string(source)?;
+
+    let title = title.unwrap_or_else(|| {
+        source
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+    });
+
+    atomic_write(Path::new(html_path), render_markdown_document(&markdown, &title).as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_render_heading_and_code_block() {
+        let html = render_markdown_document("# Hello\n\n```rust\nlet x = 1 < 2;\n```\n", "Note");
+
+        assert!(html.contains("<title>Note</title>"));
+        assert!(html.contains("<h1>Hello</h1>"));
+        assert!(html.contains("<pre><code class=\"language-rust\">let x = 1 &lt; 2;\n</code></pre>"));
+    }
+
+    #[test]
+    fn test_render_escapes_raw_html() {
+        let html = render_markdown_document("<script>alert(1)</script>\n\nText <b>bold</b>\n", "<x>");
+
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("&lt;b&gt;"));
+        assert!(html.contains("<title>&lt;x&gt;</title>"));
+    }
+
+    #[test]
+    fn test_export_defaults_title_to_file_stem() {
+        let dir = tempdir().unwrap();
+        let md = dir.path().join("Weekly Review.md");
+        std::fs::write(&md, "## Done\n").unwrap();
+        let out = dir.path().join("export").join("review.html");
+
+        export_markdown_to_html(md.to_str().unwrap(), out.to_str().unwrap(), None).unwrap();
+
+        let html = std::fs::read_to_string(&out).unwrap();
+        assert!(html.contains("<title>Weekly Review</title>"));
+        assert!(html.contains("<h2>Done</h2>"));
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_yaml::Value;
+
+/// Metadata from a note's YAML front matter block
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct FrontMatter {
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    /// Every other top-level key; list values are joined with ", ", nested maps kept as YAML
+    pub extra: HashMap<String, String>,
+}
+
+/// A scalar as text; lists are joined with ", " and nested maps are written back as YAML
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Sequence(items) => items.iter().map(value_to_string).collect::<Vec<_>>().join(", "),
+        Value::Tagged(tagged) => value_to_string(&tagged.value),
+        Value::Mapping(_) => serde_yaml::to_string(value)
+            .map(|yaml| yaml.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Tags from a list, or from a comma-separated scalar (`tags: a, b`)
+fn tags_from(value: &Value) -> Vec<String> {
+    let tags: Vec<String> = match value {
+        Value::Sequence(items) => items.iter().map(value_to_string).collect(),
+        Value::String(s) => s.split(',').map(str::to_string).collect(),
+        other => vec![value_to_string(other)],
+    };
+    tags.into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Parse a front matter block; None unless it is valid YAML holding a map (or nothing)
+fn parse_block(yaml: &str) -> Option<FrontMatter> {
+    let mapping = match serde_yaml::from_str::<Value>(yaml).ok()? {
+        Value::Null => return Some(FrontMatter::default()),
+        Value::Mapping(mapping) => mapping,
+        _ => return None,
+    };
+
+    let mut front_matter = FrontMatter::default();
+    for (key, value) in &mapping {
+        let key = value_to_string(key);
+        match key.as_str() {
+            "title" => front_matter.title = Some(value_to_string(value)),
+            "tags" => front_matter.tags = tags_from(value),
+            _ => {
+                front_matter.extra.insert(key, value_to_string(value));
+            }
+        }
+    }
+
+    Some(front_matter)
+}
+
+/// Split a note into its front matter and body
+/// Content without a front matter block, or with a malformed one, yields `(None, content)`.
+pub fn parse_front_matter(content: &str) -> (Option<FrontMatter>, String) {
+    let unchanged = || (None, content.to_string());
+
+    let text = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let Some(rest) = text.strip_prefix("---") else {
+        return unchanged();
+    };
+    let Some(rest) = rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n')) else {
+        return unchanged();
+    };
+
+    // The block ends at the first line that is exactly `---` or `...`
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let marker = line.trim_end_matches(['\r', '\n']);
+        if marker == "---" || marker == "..." {
+            let body = &rest[offset + line.len()..];
+            return match parse_block(&rest[..offset]) {
+                Some(front_matter) => (Some(front_matter), body.to_string()),
+                None => unchanged(),
+            };
+        }
+        offset += line.len();
+    }
+
+    unchanged()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_front_matter_well_formed() {
+        let content = "---\ntitle: \"My Note\"\ntags: [rust, 'tauri app']\nauthor: Ada\naliases:\n  - first\n  - second\n---\n# Body\n";
+        let (front_matter, body) = parse_front_matter(content);
+        let front_matter = front_matter.unwrap();
+
+        assert_eq!(front_matter.title.as_deref(), Some("My Note"));
+        assert_eq!(front_matter.tags, vec!["rust", "tauri app"]);
+        assert_eq!(front_matter.extra.get("author").map(String::as_str), Some("Ada"));
+        assert_eq!(front_matter.extra.get("aliases").map(String::as_str), Some("first, second"));
+        assert_eq!(body, "# Body\n");
+    }
+
+    #[test]
+    fn test_parse_front_matter_block_tags() {
+        let content = "---\r\ntags:\r\n  - a\r\n  - b\r\n---\r\nText";
+        let (front_matter, body) = parse_front_matter(content);
+
+        assert_eq!(front_matter.unwrap().tags, vec!["a", "b"]);
+        assert_eq!(body, "Text");
+    }
+
+    #[test]
+    fn test_parse_front_matter_nested_values() {
+        let content = "---\ntitle: Note\nauthor:\n  name: Ada\nsummary: >\n  folded\n  text\ntags: [\"a, b\", c]\n---\nBody";
+        let (front_matter, body) = parse_front_matter(content);
+        let front_matter = front_matter.unwrap();
+
+        assert_eq!(front_matter.title.as_deref(), Some("Note"));
+        assert_eq!(front_matter.tags, vec!["a, b", "c"]);
+        assert_eq!(front_matter.extra.get("author").map(String::as_str), Some("name: Ada"));
+        assert_eq!(front_matter.extra.get("summary").map(String::as_str), Some("folded text\n"));
+        assert_eq!(body, "Body");
+    }
+
+    #[test]
+    fn test_parse_front_matter_absent() {
+        let content = "# Just a note\n\n---\n\nwith a rule";
+        assert_eq!(parse_front_matter(content), (None, content.to_string()));
+
+        let unterminated = "---\ntitle: Draft\n";
+        assert_eq!(parse_front_matter(unterminated), (None, unterminated.to_string()));
+    }
+
+    #[test]
+    fn test_parse_front_matter_malformed() {
+        for content in [
+            "---\ntitle: Note\nthis is not yaml\n---\nBody",
+            "---\ntags: [a, b\n---\nBody",
+            "---\ntitle: \"unterminated\n---\nBody",
+            "---\n- orphan item\n---\nBody",
+        ] {
+            assert_eq!(parse_front_matter(content), (None, content.to_string()), "{}", content);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::error::AppError;
+use super::manager::resolve_lexically;
+
+/// Confines file operations to a workspace root
+/// Until a root is set every path is allowed, matching the behaviour before a vault is opened.
+pub struct WorkspaceGuard {
+    root: Mutex<Option<WorkspaceRoot>>,
+}
+
+struct WorkspaceRoot {
+    /// Root as given by the frontend, used to build paths handed back to it
+    original: PathBuf,
+    /// Canonical root (symlinks resolved), used for the containment check
+    canonical: PathBuf,
+}
+
+/// Drop `.` and fold `..` components without touching the filesystem
+pub(crate) fn normalize_components(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+impl WorkspaceGuard {
+    /// Create a guard with no workspace root (all paths allowed)
+    pub fn new() -> Self {
+        WorkspaceGuard {
+            root: Mutex::new(None),
+        }
+    }
+
+    /// Create a guard confined to `root`
+    pub fn with_root(root: &str) -> Result<Self, AppError> {
+        let guard = Self::new();
+        guard.set_root(root)?;
+        Ok(guard)
+    }
+
+    /// Set the workspace root; it must be an existing directory
+    /// Returns the canonical root
+    pub fn set_root(&self, root: &str) -> Result<PathBuf, AppError> {
+        let original = PathBuf::from(root);
+        if !original.is_dir() {
+            return Err(AppError::NotFound(root.to_string()));
+        }
+        let canonical = original.canonicalize()?;
+
+        let mut current = self.root.lock().unwrap_or_else(|e| e.into_inner());
+        *current = Some(WorkspaceRoot { original, canonical: canonical.clone() });
+        Ok(canonical)
+    }
+
+    /// Current canonical workspace root, if set
+    pub fn root(&self) -> Option<PathBuf> {
+        let current = self.root.lock().unwrap_or_else(|e| e.into_inner());
+        current.as_ref().map(|root| root.canonical.clone())
+    }
+
+    /// Resolve a path relative to (or inside) the workspace root
+    /// Symlinks in the existing part of the path are resolved; anything that ends up
+    /// outside the root returns `AppError::OutsideWorkspace`.
+    pub fn resolve(&self, rel_or_abs: &str) -> Result<PathBuf, AppError> {
+        let current = self.root.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(root) = current.as_ref() else {
+            return Ok(PathBuf::from(rel_or_abs));
+        };
+
+        let path = Path::new(rel_or_abs);
+        let joined = if path.is_absolute() { path.to_path_buf() } else { root.canonical.join(path) };
+        let resolved = normalize_components(&resolve_lexically(&joined));
+
+        if !resolved.starts_with(&root.canonical) {
+            return Err(AppError::OutsideWorkspace(rel_or_abs.to_string()));
+        }
+        Ok(resolved)
+    }
+
+    /// Check a path against the workspace and return the path to operate on
+    /// Absolute paths are returned as given and relative ones joined onto the root
+    /// as the frontend supplied it, so returned paths keep the frontend's spelling.
+    pub fn confine(&self, rel_or_abs: &str) -> Result<String, AppError> {
+        self.resolve(rel_or_abs)?;
+
+        let path = Path::new(rel_or_abs);
+        let current = self.root.lock().unwrap_or_else(|e| e.into_inner());
+        match current.as_ref() {
+            Some(root) if !path.is_absolute() => Ok(root.original.join(path).to_string_lossy().into_owned()),
+            _ => Ok(rel_or_abs.to_string()),
+        }
+    }
+}
+
+impl Default for WorkspaceGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_rejects_parent_escape() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("vault");
+        std::fs::create_dir(&root).unwrap();
+        let guard = WorkspaceGuard::with_root(root.to_str().unwrap()).unwrap();
+
+        assert!(matches!(guard.resolve("../escape"), Err(AppError::OutsideWorkspace(_))));
+        assert!(matches!(guard.resolve("notes/../../escape.md"), Err(AppError::OutsideWorkspace(_))));
+        let outside = dir.path().join("other.md");
+        assert!(guard.resolve(outside.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_allows_nested_path() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("notes")).unwrap();
+        let guard = WorkspaceGuard::with_root(dir.path().to_str().unwrap()).unwrap();
+        let root = guard.root().unwrap();
+
+        assert_eq!(guard.resolve("notes/daily/today.md").unwrap(), root.join("notes/daily/today.md"));
+        let absolute = dir.path().join("notes").join("a.md");
+        assert_eq!(guard.resolve(absolute.to_str().unwrap()).unwrap(), root.join("notes").join("a.md"));
+        assert_eq!(
+            guard.confine("notes/a.md").unwrap(),
+            dir.path().join("notes/a.md").to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn test_no_root_allows_everything() {
+        let guard = WorkspaceGuard::new();
+        assert_eq!(guard.resolve("../anywhere").unwrap(), PathBuf::from("../anywhere"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rejects_symlink_escape() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("vault");
+        let outside = dir.path().join("outside");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::create_dir(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+        let guard = WorkspaceGuard::with_root(root.to_str().unwrap()).unwrap();
+
+        assert!(matches!(guard.resolve("link/secret.md"), Err(AppError::OutsideWorkspace(_))));
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::error::AppError;
+use super::guard::normalize_components;
+use super::manager::is_skipped_name;
+
+/// Prefix of the placeholder node a dangling link points to, e.g. `missing:Some Note`
+pub const MISSING_NODE_PREFIX: &str = "missing:";
+
+/// `[[target]]`, `[[target#heading]]` and `[[target|alias]]`
+static WIKI_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[\[([^\[\]]+)\]\]").unwrap());
+
+/// `[text](target)` and `![alt](target)`; group 1 is `!` for embeds
+static MARKDOWN_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"(!?)\[[^\]]*\]\(([^)\s]+)\)").unwrap());
+
+/// Links between the notes of a vault
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct LinkGraph {
+    /// Note paths, sorted, followed by the placeholder nodes of dangling links
+    pub nodes: Vec<String>,
+    /// (source, target) pairs, sorted and deduplicated
+    pub edges: Vec<(String, String)>,
+}
+
+/// Collect all `.md` files under a directory
+fn collect_notes(dir: &Path, notes: &mut Vec<PathBuf>) -> Result<(), AppError> {
+    for entry in fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_skipped_name(&name) {
+            continue;
+        }
+
+        let path = entry.path();
+        if path.is_dir() {
+            collect_notes(&path, notes)?;
+        } else if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("md")) {
+            notes.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Raw link targets in a note, skipping fenced code blocks
+/// Returns (wiki link targets, Markdown link targets)
+fn extract_links(content: &str) -> (Vec<String>, Vec<String>) {
+    let mut wiki_links = Vec::new();
+    let mut markdown_links = Vec::new();
+    let mut in_fence = false;
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        for caps in WIKI_LINK.captures_iter(line) {
+            // [[target#heading|alias]] -> target
+            let target = caps[1].split(['|', '#']).next().unwrap_or("").trim();
+            if !target.is_empty() {
+                wiki_links.push(target.to_string());
+            }
+        }
+        for caps in MARKDOWN_LINK.captures_iter(line) {
+            let target = &caps[2];
+            let is_external = target.contains("://") || target.starts_with("mailto:");
+            if !caps[1].is_empty() || is_external {
+                continue;
+            }
+            let target = target.split('#').next().unwrap_or("").replace("%20", " ");
+            if target.to_ascii_lowercase().ends_with(".md") {
+                markdown_links.push(target);
+            }
+        }
+    }
+
+    (wiki_links, markdown_links)
+}
+
+/// Resolve a wiki link target: a path relative to the root, or a note name
+/// Among notes sharing a name, one in the linking note's folder wins.
+fn resolve_wiki_link(
+    target: &str,
+    source: &Path,
+    root: &Path,
+    notes: &BTreeSet<PathBuf>,
+    by_stem: &HashMap<String, Vec<PathBuf>>,
+) -> Option<PathBuf> {
+    let with_ext = if target.to_ascii_lowercase().ends_with(".md") {
+        target.to_string()
+    } else {
+        format!("{}.md", target)
+    };
+
+    if target.contains('/') {
+        let path = normalize_components(&root.join(with_ext));
+        return notes.contains(&path).then_some(path);
+    }
+
+    let stem = with_ext[..with_ext.len() - 3].to_lowercase();
+    let candidates = by_stem.get(&stem)?;
+    candidates
+        .iter()
+        .find(|c| c.parent() == source.parent())
+        .or_else(|| candidates.first())
+        .cloned()
+}
+
+/// Build the link graph of the `.md` notes under `root`
+/// Both `[[wiki links]]` and relative Markdown links to `.md` files are followed;
+/// links that resolve to no note point to a `missing:<target>` placeholder node.
+pub fn build_link_graph(root: &str) -> Result<LinkGraph, AppError> {
+    let root_path = Path::new(root);
+    if !root_path.exists() {
+        return Err(AppError::NotFound(root.to_string()));
+    }
+    if !root_path.is_dir() {
+        return Err(AppError::InvalidPath("Path is not a directory".to_string()));
+    }
+
+    let mut found = Vec::new();
+    collect_notes(root_path, &mut found)?;
+    let notes: BTreeSet<PathBuf> = found.into_iter().map(|p| normalize_components(&p)).collect();
+
+    let mut by_stem: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for note in &notes {
+        if let Some(stem) = note.file_stem() {
+            by_stem.entry(stem.to_string_lossy().to_lowercase()).or_default().push(note.clone());
+        }
+    }
+
+    let mut edges = BTreeSet::new();
+    let mut missing = BTreeSet::new();
+    for note in &notes {
+        let Ok(content) = fs::read_to_string(note) else {
+            continue;
+        };
+        let source = note.to_string_lossy().to_string();
+        let (wiki_links, markdown_links) = extract_links(&content);
+
+        let resolved_wiki = wiki_links.into_iter().map(|target| {
+            let path = resolve_wiki_link(&target, note, root_path, &notes, &by_stem);
+            (target, path)
+        });
+        let resolved_markdown = markdown_links.into_iter().map(|target| {
+            let base = note.parent().unwrap_or(root_path);
+            let path = normalize_components(&base.join(&target));
+            let path = notes.contains(&path).then_some(path);
+            (target, path)
+        });
+
+        for (target, path) in resolved_wiki.chain(resolved_markdown) {
+            let target_node = match path {
+                Some(path) => path.to_string_lossy().to_string(),
+                None => {
+                    let node = format!("{}{}", MISSING_NODE_PREFIX, target);
+                    missing.insert(node.clone());
+                    node
+                }
+            };
+            edges.insert((source.clone(), target_node));
+        }
+    }
+
+    let mut nodes: Vec<String> = notes.iter().map(|p| p.to_string_lossy().to_string()).collect();
+    nodes.extend(missing);
+
+    Ok(LinkGraph {
+        nodes,
+        edges: edges.into_iter().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn node(dir: &Path, rel: &str) -> String {
+        normalize_components(&dir.join(rel)).to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_wiki_link_resolution() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("topics")).unwrap();
+        fs::write(dir.path().join("index.md"), "See [[Rust]] and [[topics/tauri|Tauri]] and [[rust#traits]]").unwrap();
+        fs::write(dir.path().join("topics/rust.md"), "# Rust").unwrap();
+        fs::write(dir.path().join("topics/tauri.md"), "# Tauri").unwrap();
+
+        let graph = build_link_graph(dir.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges, vec![
+            (node(dir.path(), "index.md"), node(dir.path(), "topics/rust.md")),
+            (node(dir.path(), "index.md"), node(dir.path(), "topics/tauri.md")),
+        ]);
+    }
+
+    #[test]
+    fn test_relative_markdown_link() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("a")).unwrap();
+        fs::create_dir_all(dir.path().join("b")).unwrap();
+        fs::write(
+            dir.path().join("a/one.md"),
+            "[two](../b/two%20notes.md#intro) [web](https://example.com/x.md) ![img](pic.md)",
+        ).unwrap();
+        fs::write(dir.path().join("b/two notes.md"), "").unwrap();
+
+        let graph = build_link_graph(dir.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(graph.edges, vec![
+            (node(dir.path(), "a/one.md"), node(dir.path(), "b/two notes.md")),
+        ]);
+    }
+
+    #[test]
+    fn test_dangling_link() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("note.md"), "[[Nowhere]] and [gone](gone.md)\n```\n[[InCode]]\n```").unwrap();
+
+        let graph = build_link_graph(dir.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(graph.nodes, vec![
+            node(dir.path(), "note.md"),
+            "missing:Nowhere".to_string(),
+            "missing:gone.md".to_string(),
+        ]);
+        assert_eq!(graph.edges.len(), 2);
+        assert!(graph.edges.iter().all(|(source, _)| *source == node(dir.path(), "note.md")));
+    }
+}
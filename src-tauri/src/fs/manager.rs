@@ -1,45 +1,358 @@
+use std::cmp::Ordering;
 use std::fs;
-use std::path::Path;
-use serde::Serialize;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
 
 use crate::error::AppError;
+use super::search::BINARY_SNIFF_LEN;
 
 #[derive(Debug, Serialize, Clone)]
 pub struct FileEntry {
     pub name: String,
     pub path: String,
     pub is_dir: bool,
+    /// File size in bytes (always 0 for directories)
+    pub size_bytes: u64,
+    /// Last modification time in ms since the Unix epoch (0 if unavailable)
+    pub modified_ms: u64,
     pub children: Option<Vec<FileEntry>>,
 }
 
+/// Modification time in ms since the Unix epoch, or 0 if the platform doesn't expose it
+fn modified_ms(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Whether the start of a file's bytes looks binary: a null byte or invalid UTF-8 in the first 8KB
+/// A multi-byte character cut off by the 8KB boundary doesn't count as invalid.
+fn sniff_binary(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(BINARY_SNIFF_LEN)];
+    if head.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => false,
+        Err(e) => e.error_len().is_some() || head.len() == bytes.len(),
+    }
+}
+
 /// Read file content as UTF-8 string
+/// Files that look binary are rejected with `AppError::BinaryFile` instead of being decoded.
 pub fn read_file_content(path: &str) -> Result<String, AppError> {
     let path = Path::new(path);
     if !path.exists() {
-        return Err(AppError::FileNotFound(path.display().to_string()));
+        return Err(AppError::NotFound(path.display().to_string()));
+    }
+    let bytes = fs::read(path)?;
+    let binary = AppError::BinaryFile { path: path.display().to_string(), size: bytes.len() as u64 };
+    if sniff_binary(&bytes) {
+        return Err(binary);
+    }
+    String::from_utf8(bytes).map_err(|_| binary)
+}
+
+/// Read file content, replacing invalid UTF-8 when `force` is set
+/// Without `force` this is `read_file_content`, so binary files are still rejected.
+pub fn read_file_lossy(path: &str, force: bool) -> Result<String, AppError> {
+    if !force {
+        return read_file_content(path);
+    }
+    let path = Path::new(path);
+    if !path.exists() {
+        return Err(AppError::NotFound(path.display().to_string()));
     }
-    fs::read_to_string(path).map_err(AppError::from)
+    let bytes = fs::read(path)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// A slice of a file's lines plus the file's total line count
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FileLines {
+    pub content: String,
+    pub total_lines: usize,
+}
+
+/// Read lines `start_line..end_line` (0-based, end exclusive) without loading the whole file
+/// Also counts the total number of lines; line endings are kept as in the file.
+pub fn read_file_lines(path: &str, start_line: usize, end_line: usize) -> Result<FileLines, AppError> {
+    use std::io::BufRead;
+
+    let path = Path::new(path);
+    if !path.is_file() {
+        return Err(AppError::NotFound(path.display().to_string()));
+    }
+
+    let mut reader = std::io::BufReader::new(fs::File::open(path)?);
+    let mut content = String::new();
+    let mut line = String::new();
+    let mut total_lines = 0;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        if total_lines >= start_line && total_lines < end_line {
+            content.push_str(&line);
+        }
+        total_lines += 1;
+    }
+
+    Ok(FileLines { content, total_lines })
+}
+
+/// Read only lines `start_line..end_line` (0-based, end exclusive) of a file
+/// `end_line` is clamped to the file length; a start past the end yields an empty string.
+pub fn read_file_range(path: &str, start_line: usize, end_line: usize) -> Result<String, AppError> {
+    read_file_lines(path, start_line, end_line).map(|lines| lines.content)
+}
+
+/// Hex-encoded SHA-256 of a file's contents, read in fixed-size chunks
+pub fn file_checksum(path: &str) -> Result<String, AppError> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    let path = Path::new(path);
+    if !path.is_file() {
+        return Err(AppError::NotFound(path.display().to_string()));
+    }
+
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
 }
 
 /// Write content to file, creating parent directories if needed
+/// The content is written to a temporary sibling file and renamed over the target,
+/// so a crash mid-write never leaves a truncated note behind.
 pub fn write_file_content(path: &str, content: &str) -> Result<(), AppError> {
-    let path = Path::new(path);
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+    atomic_write(Path::new(path), content.as_bytes())
+}
+
+/// Write content to file unless it was modified on disk after `expected_mtime`
+/// Returns `AppError::Conflict` when the on-disk mtime is newer than expected;
+/// `None` skips the check (same as `write_file_content`).
+pub fn write_file_content_checked(path: &str, content: &str, expected_mtime: Option<u64>) -> Result<(), AppError> {
+    if let Some(expected) = expected_mtime {
+        if let Ok(metadata) = fs::metadata(path) {
+            if modified_ms(&metadata) > expected {
+                return Err(AppError::Conflict(path.to_string()));
+            }
+        }
+    }
+    write_file_content(path, content)
+}
+
+/// Atomically replace `path` with `data`, preserving the original permissions where possible
+pub(crate) fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
+    use std::io::Write;
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)?;
+
+    let file_name = path.file_name()
+        .ok_or_else(|| AppError::InvalidPath(path.display().to_string()))?
+        .to_string_lossy();
+    let tmp_path = parent.join(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4()));
+
+    let result = (|| -> Result<(), AppError> {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        drop(file);
+
+        if let Ok(metadata) = fs::metadata(path) {
+            let _ = fs::set_permissions(&tmp_path, metadata.permissions());
+        }
+
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// Default ignore patterns for directory listings
+pub const DEFAULT_IGNORE_PATTERNS: &[&str] = &[".git", "node_modules", "target"];
+
+/// Whether a directory entry is excluded from recursive walks
+pub(crate) fn is_skipped_name(name: &str) -> bool {
+    // Skip hidden files and directories
+    if name.starts_with('.') {
+        return true;
+    }
+    // Skip node_modules and other common non-user directories
+    DEFAULT_IGNORE_PATTERNS.contains(&name)
+}
+
+/// Match an entry name against a gitignore-style pattern
+/// Supports `*` and `?` wildcards; a trailing `/` restricts the pattern to directories.
+/// Patterns apply to names at any depth, so leading `/` and `**/` are ignored.
+pub(crate) fn matches_ignore_pattern(pattern: &str, name: &str, is_dir: bool) -> bool {
+    let mut pattern = pattern.trim();
+    if pattern.is_empty() || pattern.starts_with('#') {
+        return false;
+    }
+    if let Some(dir_pattern) = pattern.strip_suffix('/') {
+        if !is_dir {
+            return false;
+        }
+        pattern = dir_pattern;
+    }
+    while let Some(rest) = pattern.strip_prefix("**/") {
+        pattern = rest;
+    }
+    let pattern = pattern.trim_start_matches('/');
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    wildcard_match(&pattern, &name)
+}
+
+/// Glob match with `*` (any run of characters) and `?` (any single character)
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            // Let the last `*` swallow one more character
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
     }
-    fs::write(path, content).map_err(AppError::from)
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Order of the entries within each level of a directory listing
+/// Directories always come before files; ties fall back to ascending name order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    NameAsc,
+    NameDesc,
+    ModifiedAsc,
+    ModifiedDesc,
+    SizeAsc,
+    SizeDesc,
+}
+
+/// Case-insensitive natural ordering: runs of digits compare by value, so `file2` < `file10`
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let a = a.to_lowercase();
+    let b = b.to_lowercase();
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let mut a_num = String::new();
+                while let Some(c) = a_chars.next_if(|c| c.is_ascii_digit()) {
+                    a_num.push(c);
+                }
+                let mut b_num = String::new();
+                while let Some(c) = b_chars.next_if(|c| c.is_ascii_digit()) {
+                    b_num.push(c);
+                }
+
+                // Compare by value without overflowing: strip leading zeros, then length, then digits
+                let a_value = a_num.trim_start_matches('0');
+                let b_value = b_num.trim_start_matches('0');
+                let ordering = a_value.len().cmp(&b_value.len())
+                    .then_with(|| a_value.cmp(b_value))
+                    .then_with(|| a_num.len().cmp(&b_num.len()));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+/// Sort one level of a listing: directories first, then by `order`
+fn sort_entries(entries: &mut [FileEntry], order: SortOrder) {
+    entries.sort_by(|a, b| {
+        let by_name = || natural_cmp(&a.name, &b.name).then_with(|| a.name.cmp(&b.name));
+        let ordering = match order {
+            SortOrder::NameAsc => by_name(),
+            SortOrder::NameDesc => by_name().reverse(),
+            SortOrder::ModifiedAsc => a.modified_ms.cmp(&b.modified_ms).then_with(by_name),
+            SortOrder::ModifiedDesc => b.modified_ms.cmp(&a.modified_ms).then_with(by_name),
+            SortOrder::SizeAsc => a.size_bytes.cmp(&b.size_bytes).then_with(by_name),
+            SortOrder::SizeDesc => b.size_bytes.cmp(&a.size_bytes).then_with(by_name),
+        };
+        b.is_dir.cmp(&a.is_dir).then(ordering)
+    });
 }
 
 /// List directory contents recursively (all files)
-pub fn list_dir_recursive(path: &str) -> Result<Vec<FileEntry>, AppError> {
+/// Entries matching any `ignore` pattern are skipped (matched directories are not descended).
+/// `None` uses `DEFAULT_IGNORE_PATTERNS`; hidden entries are always skipped.
+/// Each level is ordered by `sort`, directories first.
+pub fn list_dir_recursive(path: &str, ignore: Option<&[String]>, sort: SortOrder) -> Result<Vec<FileEntry>, AppError> {
     let root = Path::new(path);
     if !root.exists() {
-        return Err(AppError::FileNotFound(path.to_string()));
+        return Err(AppError::NotFound(path.to_string()));
     }
     if !root.is_dir() {
         return Err(AppError::InvalidPath("Path is not a directory".to_string()));
     }
 
+    let defaults: Vec<String>;
+    let ignore = match ignore {
+        Some(patterns) => patterns,
+        None => {
+            defaults = DEFAULT_IGNORE_PATTERNS.iter().map(|p| p.to_string()).collect();
+            &defaults
+        }
+    };
+
+    list_dir_filtered(root, ignore, sort)
+}
+
+fn list_dir_filtered(root: &Path, ignore: &[String], sort: SortOrder) -> Result<Vec<FileEntry>, AppError> {
     let mut entries = Vec::new();
 
     for entry in fs::read_dir(root)? {
@@ -51,19 +364,23 @@ pub fn list_dir_recursive(path: &str) -> Result<Vec<FileEntry>, AppError> {
         if name.starts_with('.') {
             continue;
         }
-        
-        // Skip node_modules and other common non-user directories
-        if name == "node_modules" || name == "target" || name == ".git" {
+
+        // Fall back to the link itself for broken symlinks
+        let metadata = fs::metadata(&path).or_else(|_| fs::symlink_metadata(&path))?;
+
+        if ignore.iter().any(|p| matches_ignore_pattern(p, &name, metadata.is_dir())) {
             continue;
         }
 
-        if path.is_dir() {
-            let children = list_dir_recursive(&path.to_string_lossy())?;
+        if metadata.is_dir() {
+            let children = list_dir_filtered(&path, ignore, sort)?;
             // Include all directories (including empty ones)
             entries.push(FileEntry {
                 name,
                 path: path.to_string_lossy().to_string(),
                 is_dir: true,
+                size_bytes: 0,
+                modified_ms: modified_ms(&metadata),
                 children: Some(children),
             });
         } else {
@@ -72,28 +389,51 @@ pub fn list_dir_recursive(path: &str) -> Result<Vec<FileEntry>, AppError> {
                 name,
                 path: path.to_string_lossy().to_string(),
                 is_dir: false,
+                size_bytes: metadata.len(),
+                modified_ms: modified_ms(&metadata),
                 children: None,
             });
         }
     }
 
-    // Sort: directories first, then files, alphabetically
-    entries.sort_by(|a, b| {
-        match (a.is_dir, b.is_dir) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-        }
-    });
+    sort_entries(&mut entries, sort);
 
     Ok(entries)
 }
 
+/// Total size in bytes of all files under a directory (or the size of a single file)
+/// Symlinks are not followed, and entries that can't be read are skipped so the
+/// result is a partial total rather than an error.
+pub fn dir_size(path: &str) -> Result<u64, AppError> {
+    let path = Path::new(path);
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+    Ok(sum_dir_size(path))
+}
+
+fn sum_dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        // DirEntry::metadata doesn't traverse symlinks, so links count as neither file nor dir
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => sum_dir_size(&entry.path()),
+            Ok(metadata) if metadata.is_file() => metadata.len(),
+            _ => 0,
+        })
+        .sum()
+}
+
 /// Create a new .md file
 pub fn create_new_file(path: &str) -> Result<(), AppError> {
     let path = Path::new(path);
     if path.exists() {
-        return Err(AppError::FileExists(path.display().to_string()));
+        return Err(AppError::AlreadyExists(path.display().to_string()));
     }
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
@@ -101,40 +441,414 @@ pub fn create_new_file(path: &str) -> Result<(), AppError> {
     fs::write(path, "").map_err(AppError::from)
 }
 
-/// Delete a file or directory (move to trash/recycle bin)
-pub fn delete_entry(path: &str) -> Result<(), AppError> {
+/// Append content to a file, creating it if it doesn't exist
+/// A newline is inserted first when the existing content doesn't already end with one.
+pub fn append_to_file(path: &str, content: &str) -> Result<(), AppError> {
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    let path = Path::new(path);
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)?;
+
+    let needs_newline = if file.metadata()?.len() > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last)?;
+        last[0] != b'\n'
+    } else {
+        false
+    };
+
+    if needs_newline {
+        file.write_all(b"\n")?;
+    }
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Create a new file with initial content in one step
+/// Fails with `AppError::AlreadyExists` if the file exists; the path is reserved with
+/// `create_new` before the content is written atomically, so concurrent creators can't clobber it.
+pub fn create_new_file_with_content(path: &str, content: &str) -> Result<(), AppError> {
+    let path = Path::new(path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => AppError::AlreadyExists(path.display().to_string()),
+            _ => AppError::from(e),
+        })?;
+
+    atomic_write(path, content.as_bytes()).inspect_err(|_| {
+        let _ = fs::remove_file(path);
+    })
+}
+
+/// Delete a file or directory
+/// Moves it to the trash/recycle bin unless `permanent` is set.
+/// If trashing fails, returns `AppError::Trash` instead of falling back to a hard delete.
+pub fn delete_entry(path: &str, permanent: bool) -> Result<(), AppError> {
     let path = Path::new(path);
     if !path.exists() {
-        return Err(AppError::FileNotFound(path.display().to_string()));
+        return Err(AppError::NotFound(path.display().to_string()));
+    }
+    if permanent {
+        if path.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+        return Ok(());
     }
     // 移动到回收站而非永久删除
     trash::delete(path)?;
     Ok(())
 }
 
+/// Outcome of deleting one path in a bulk delete
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DeleteResult {
+    pub path: String,
+    pub success: bool,
+    /// Error message when the deletion failed
+    pub error: Option<String>,
+}
+
+impl DeleteResult {
+    pub fn new(path: String, result: &Result<(), AppError>) -> Self {
+        DeleteResult {
+            path,
+            success: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        }
+    }
+}
+
+/// Delete several files or directories, continuing past failures
+/// Returns each path with its own result, in the order given.
+pub fn delete_many(paths: &[String], permanent: bool) -> Vec<(String, Result<(), AppError>)> {
+    paths
+        .iter()
+        .map(|path| (path.clone(), delete_entry(path, permanent)))
+        .collect()
+}
+
+/// Maximum number of paths listed in a `DeletePreview`
+pub const DELETE_PREVIEW_SAMPLE_LEN: usize = 50;
+
+/// What deleting a path would remove
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DeletePreview {
+    /// Files (and symlinks) that would be removed
+    pub file_count: usize,
+    /// Directories that would be removed, including the path itself
+    pub dir_count: usize,
+    pub total_bytes: u64,
+    /// The first `DELETE_PREVIEW_SAMPLE_LEN` paths, in sorted traversal order
+    pub sample_paths: Vec<String>,
+}
+
+/// List what deleting `path` would remove, without deleting anything
+/// Symlinks are counted as entries but not followed; unreadable directories are skipped.
+pub fn list_deletable(path: &str) -> Result<DeletePreview, AppError> {
+    let root = Path::new(path);
+    let metadata = fs::symlink_metadata(root)
+        .map_err(|_| AppError::NotFound(path.to_string()))?;
+
+    let mut preview = DeletePreview {
+        file_count: 0,
+        dir_count: 0,
+        total_bytes: 0,
+        sample_paths: Vec::new(),
+    };
+    if metadata.is_dir() {
+        preview.dir_count = 1;
+        collect_deletable(root, &mut preview);
+    } else {
+        preview.file_count = 1;
+        preview.total_bytes = metadata.len();
+        preview.sample_paths.push(path.to_string());
+    }
+    Ok(preview)
+}
+
+fn collect_deletable(dir: &Path, preview: &mut DeletePreview) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if preview.sample_paths.len() < DELETE_PREVIEW_SAMPLE_LEN {
+            preview.sample_paths.push(entry.path().to_string_lossy().into_owned());
+        }
+        if metadata.is_dir() {
+            preview.dir_count += 1;
+            collect_deletable(&entry.path(), preview);
+        } else {
+            preview.file_count += 1;
+            preview.total_bytes += metadata.len();
+        }
+    }
+}
+
 /// Create a new directory
-pub fn create_new_dir(path: &str) -> Result<(), AppError> {
+/// An existing directory is left as is; an existing file at the path is `AlreadyExists`.
+/// Missing parents are created only with `create_parents`, otherwise they are `NotFound`.
+pub fn create_new_dir(path: &str, create_parents: bool) -> Result<(), AppError> {
     let path = Path::new(path);
+    if path.is_dir() {
+        return Ok(());
+    }
     if path.exists() {
-        return Err(AppError::FileExists(path.display().to_string()));
+        return Err(AppError::AlreadyExists(path.display().to_string()));
+    }
+    if create_parents {
+        fs::create_dir_all(path).map_err(AppError::from)
+    } else {
+        fs::create_dir(path).map_err(AppError::from)
     }
-    fs::create_dir_all(path).map_err(AppError::from)
 }
 
 /// Rename/move a file or directory
-pub fn rename_entry(old_path: &str, new_path: &str) -> Result<(), AppError> {
+/// Refuses to replace an existing destination unless `overwrite` is set;
+/// renaming an entry onto itself is a no-op.
+/// A replaced destination is only removed once the move has succeeded.
+pub fn rename_entry(old_path: &str, new_path: &str, overwrite: bool) -> Result<(), AppError> {
+    rename_entry_with(old_path, new_path, overwrite, |from, to| fs::rename(from, to))
+}
+
+fn rename_entry_with<F>(old_path: &str, new_path: &str, overwrite: bool, rename: F) -> Result<(), AppError>
+where
+    F: Fn(&Path, &Path) -> std::io::Result<()>,
+{
     let old = Path::new(old_path);
     let new = Path::new(new_path);
     if !old.exists() {
-        return Err(AppError::FileNotFound(old_path.to_string()));
+        return Err(AppError::NotFound(old_path.to_string()));
     }
+    let mut replaces_dir = false;
     if new.exists() {
-        return Err(AppError::FileExists(new_path.to_string()));
+        if old.canonicalize()? == new.canonicalize()? {
+            // Same entry; on case-insensitive file systems a case-only change still needs the rename
+            if old.file_name() == new.file_name() {
+                return Ok(());
+            }
+            return fs::rename(old, new).map_err(AppError::from);
+        }
+        if !overwrite {
+            return Err(AppError::AlreadyExists(new_path.to_string()));
+        }
+        // A file replaces a file atomically; anything involving a directory can't be renamed over
+        replaces_dir = new.is_dir() || old.is_dir();
     }
     if let Some(parent) = new.parent() {
         fs::create_dir_all(parent)?;
     }
-    fs::rename(old, new).map_err(AppError::from)
+    if replaces_dir {
+        return with_destination_aside(new, || move_with_fallback(old, new, rename));
+    }
+    move_with_fallback(old, new, rename)
+}
+
+/// An unused hidden sibling of `path` for staging or parking an entry
+fn sibling_temp_path(path: &Path, tag: &str) -> Result<PathBuf, AppError> {
+    let name = path.file_name()
+        .ok_or_else(|| AppError::InvalidPath(path.display().to_string()))?
+        .to_string_lossy();
+    let parent = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut n = 0;
+    loop {
+        let candidate = parent.join(format!(".{}.{}-{}", name, tag, n));
+        if fs::symlink_metadata(&candidate).is_err() {
+            return Ok(candidate);
+        }
+        n += 1;
+    }
+}
+
+fn remove_entry(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// Run `replace` with the existing entry at `dst` parked on a sibling
+/// The parked entry is deleted once `replace` succeeds and put back if it fails,
+/// so a failed replacement leaves the original destination in place
+fn with_destination_aside<F>(dst: &Path, replace: F) -> Result<(), AppError>
+where
+    F: FnOnce() -> Result<(), AppError>,
+{
+    let aside = sibling_temp_path(dst, "replaced")?;
+    fs::rename(dst, &aside)?;
+    match replace() {
+        Ok(()) => {
+            let _ = remove_entry(&aside);
+            Ok(())
+        }
+        Err(e) => {
+            if fs::symlink_metadata(dst).is_ok() {
+                let _ = remove_entry(dst);
+            }
+            fs::rename(&aside, dst)?;
+            Err(e)
+        }
+    }
+}
+
+/// Whether a rename failed only because source and destination are on different file systems
+fn is_cross_device(err: &std::io::Error) -> bool {
+    // EXDEV on Unix, ERROR_NOT_SAME_DEVICE on Windows
+    #[cfg(unix)]
+    const CROSS_DEVICE: i32 = 18;
+    #[cfg(windows)]
+    const CROSS_DEVICE: i32 = 17;
+    #[cfg(not(any(unix, windows)))]
+    const CROSS_DEVICE: i32 = -1;
+    err.raw_os_error() == Some(CROSS_DEVICE)
+}
+
+/// Move `old` to `new` with `rename`, falling back to copy-then-delete across file systems
+/// The source is only removed once the copy has fully succeeded; a failed copy is cleaned up
+/// and leaves the source untouched
+fn move_with_fallback<F>(old: &Path, new: &Path, rename: F) -> Result<(), AppError>
+where
+    F: Fn(&Path, &Path) -> std::io::Result<()>,
+{
+    match rename(old, new) {
+        Ok(()) => return Ok(()),
+        Err(e) if !is_cross_device(&e) => return Err(e.into()),
+        Err(_) => {}
+    }
+
+    let is_dir = old.is_dir();
+    if is_dir {
+        if let Err(e) = copy_dir_recursive(old, new) {
+            let _ = fs::remove_dir_all(new);
+            return Err(e);
+        }
+    } else {
+        // Stage the copy beside the destination so an existing file is only replaced by a complete one
+        let staged = sibling_temp_path(new, "moving")?;
+        let copied = fs::copy(old, &staged).and_then(|_| fs::rename(&staged, new));
+        if let Err(e) = copied {
+            let _ = fs::remove_file(&staged);
+            return Err(e.into());
+        }
+    }
+
+    if is_dir {
+        fs::remove_dir_all(old)?;
+    } else {
+        fs::remove_file(old)?;
+    }
+    Ok(())
+}
+
+/// Resolve a possibly non-existent path against its nearest existing ancestor
+pub(crate) fn resolve_lexically(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => {
+            resolve_lexically(parent).join(name)
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Recursively copy a directory
+fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), AppError> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy a file or directory (recursively)
+/// Refuses to overwrite an existing destination unless `overwrite` is set
+pub fn copy_entry(src: &str, dst: &str, overwrite: bool) -> Result<(), AppError> {
+    let src_path = Path::new(src);
+    let dst_path = Path::new(dst);
+    if !src_path.exists() {
+        return Err(AppError::NotFound(src.to_string()));
+    }
+
+    // Refuse to copy a directory into itself or its subdirectory
+    if src_path.is_dir() {
+        let src_canonical = src_path.canonicalize()
+            .map_err(|_| AppError::InvalidPath("Cannot resolve source path".to_string()))?;
+        if resolve_lexically(dst_path).starts_with(&src_canonical) {
+            return Err(AppError::InvalidPath("Cannot copy folder into itself or its subdirectory".to_string()));
+        }
+    }
+
+    let replaces = dst_path.exists();
+    if replaces && !overwrite {
+        return Err(AppError::AlreadyExists(dst.to_string()));
+    }
+
+    if let Some(parent) = dst_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if !replaces {
+        return copy_into(src_path, dst_path);
+    }
+
+    // Copy beside the destination first, then swap the finished copy into place
+    let staged = sibling_temp_path(dst_path, "copying")?;
+    if let Err(e) = copy_into(src_path, &staged) {
+        let _ = remove_entry(&staged);
+        return Err(e);
+    }
+    let swapped = if src_path.is_dir() || dst_path.is_dir() {
+        with_destination_aside(dst_path, || fs::rename(&staged, dst_path).map_err(AppError::from))
+    } else {
+        fs::rename(&staged, dst_path).map_err(AppError::from)
+    };
+    if swapped.is_err() {
+        let _ = remove_entry(&staged);
+    }
+    swapped
+}
+
+fn copy_into(src: &Path, dst: &Path) -> Result<(), AppError> {
+    if src.is_dir() {
+        copy_dir_recursive(src, dst)
+    } else {
+        fs::copy(src, dst)?;
+        Ok(())
+    }
 }
 
 /// Move a file to a target folder
@@ -145,7 +859,7 @@ pub fn move_file_to_folder(source: &str, target_folder: &str) -> Result<String,
     
     // Check source exists and is a file
     if !source_path.exists() {
-        return Err(AppError::FileNotFound(source.to_string()));
+        return Err(AppError::NotFound(source.to_string()));
     }
     if source_path.is_dir() {
         return Err(AppError::InvalidPath("Source is a directory, use move_folder instead".to_string()));
@@ -153,7 +867,7 @@ pub fn move_file_to_folder(source: &str, target_folder: &str) -> Result<String,
     
     // Check target folder exists and is a directory
     if !target_folder_path.exists() {
-        return Err(AppError::FileNotFound(target_folder.to_string()));
+        return Err(AppError::NotFound(target_folder.to_string()));
     }
     if !target_folder_path.is_dir() {
         return Err(AppError::InvalidPath("Target is not a directory".to_string()));
@@ -166,7 +880,7 @@ pub fn move_file_to_folder(source: &str, target_folder: &str) -> Result<String,
     
     // Check if target already exists
     if new_path.exists() {
-        return Err(AppError::FileExists(new_path.display().to_string()));
+        return Err(AppError::AlreadyExists(new_path.display().to_string()));
     }
     
     // Move the file
@@ -183,7 +897,7 @@ pub fn move_folder_to_folder(source: &str, target_folder: &str) -> Result<String
     
     // Check source exists and is a directory
     if !source_path.exists() {
-        return Err(AppError::FileNotFound(source.to_string()));
+        return Err(AppError::NotFound(source.to_string()));
     }
     if !source_path.is_dir() {
         return Err(AppError::InvalidPath("Source is not a directory".to_string()));
@@ -191,7 +905,7 @@ pub fn move_folder_to_folder(source: &str, target_folder: &str) -> Result<String
     
     // Check target folder exists and is a directory
     if !target_folder_path.exists() {
-        return Err(AppError::FileNotFound(target_folder.to_string()));
+        return Err(AppError::NotFound(target_folder.to_string()));
     }
     if !target_folder_path.is_dir() {
         return Err(AppError::InvalidPath("Target is not a directory".to_string()));
@@ -214,7 +928,7 @@ pub fn move_folder_to_folder(source: &str, target_folder: &str) -> Result<String
     
     // Check if target already exists
     if new_path.exists() {
-        return Err(AppError::FileExists(new_path.display().to_string()));
+        return Err(AppError::AlreadyExists(new_path.display().to_string()));
     }
     
     // Move the folder
@@ -222,3 +936,662 @@ pub fn move_folder_to_folder(source: &str, target_folder: &str) -> Result<String
     
     Ok(new_path.to_string_lossy().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_delete_entry_trash() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("note.md");
+        fs::write(&file, "# Note").unwrap();
+
+        match delete_entry(file.to_str().unwrap(), false) {
+            Ok(()) => assert!(!file.exists()),
+            // No trash available on this platform: must not hard-delete
+            Err(AppError::Trash(_)) => assert!(file.exists()),
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_delete_entry_permanent() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("note.md");
+        fs::write(&file, "# Note").unwrap();
+        let sub = dir.path().join("folder");
+        fs::create_dir_all(sub.join("nested")).unwrap();
+        fs::write(sub.join("nested").join("a.md"), "a").unwrap();
+
+        delete_entry(file.to_str().unwrap(), true).unwrap();
+        delete_entry(sub.to_str().unwrap(), true).unwrap();
+
+        assert!(!file.exists());
+        assert!(!sub.exists());
+    }
+
+    #[test]
+    fn test_copy_entry_file() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("a.md");
+        let dst = dir.path().join("copies").join("b.md");
+        fs::write(&src, "hello").unwrap();
+
+        copy_entry(src.to_str().unwrap(), dst.to_str().unwrap(), false).unwrap();
+
+        assert!(src.exists());
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_copy_entry_no_overwrite() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("a.md");
+        let dst = dir.path().join("b.md");
+        fs::write(&src, "new").unwrap();
+        fs::write(&dst, "old").unwrap();
+
+        let result = copy_entry(src.to_str().unwrap(), dst.to_str().unwrap(), false);
+        assert!(matches!(result, Err(AppError::AlreadyExists(_))));
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "old");
+
+        copy_entry(src.to_str().unwrap(), dst.to_str().unwrap(), true).unwrap();
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "new");
+    }
+
+    #[test]
+    fn test_copy_entry_directory() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("nested")).unwrap();
+        fs::write(src.join("a.md"), "a").unwrap();
+        fs::write(src.join("nested").join("b.md"), "b").unwrap();
+        let dst = dir.path().join("dst");
+
+        copy_entry(src.to_str().unwrap(), dst.to_str().unwrap(), false).unwrap();
+
+        assert_eq!(fs::read_to_string(dst.join("a.md")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(dst.join("nested").join("b.md")).unwrap(), "b");
+        assert!(src.join("nested").join("b.md").exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_copy_entry_failed_overwrite_keeps_destination() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        let dst = dir.path().join("dst");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(&dst).unwrap();
+        fs::write(dst.join("keep.md"), "keep").unwrap();
+        // A dangling symlink makes the copy fail partway through
+        fs::write(src.join("a.md"), "a").unwrap();
+        std::os::unix::fs::symlink(dir.path().join("missing"), src.join("z.md")).unwrap();
+
+        assert!(copy_entry(src.to_str().unwrap(), dst.to_str().unwrap(), true).is_err());
+        assert_eq!(fs::read_to_string(dst.join("keep.md")).unwrap(), "keep");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+
+        fs::remove_file(src.join("z.md")).unwrap();
+        copy_entry(src.to_str().unwrap(), dst.to_str().unwrap(), true).unwrap();
+        assert_eq!(fs::read_to_string(dst.join("a.md")).unwrap(), "a");
+        assert!(!dst.join("keep.md").exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_copy_entry_into_itself() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        let dst = src.join("inner").join("copy");
+
+        let result = copy_entry(src.to_str().unwrap(), dst.to_str().unwrap(), false);
+        assert!(matches!(result, Err(AppError::InvalidPath(_))));
+        assert!(!src.join("inner").exists());
+    }
+
+    #[test]
+    fn test_list_dir_recursive_metadata() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.md"), "hello").unwrap();
+        fs::create_dir_all(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub").join("b.md"), "hello world").unwrap();
+
+        let entries = list_dir_recursive(dir.path().to_str().unwrap(), None, SortOrder::default()).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let sub = &entries[0];
+        assert!(sub.is_dir);
+        assert_eq!(sub.size_bytes, 0);
+        let children = sub.children.as_ref().unwrap();
+        assert_eq!(children[0].name, "b.md");
+        assert_eq!(children[0].size_bytes, 11);
+
+        let file = &entries[1];
+        assert!(!file.is_dir);
+        assert_eq!(file.name, "a.md");
+        assert_eq!(file.size_bytes, 5);
+        assert!(file.modified_ms > 0);
+    }
+
+    #[test]
+    fn test_write_file_content_atomic() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("note.md");
+
+        write_file_content(file.to_str().unwrap(), "first").unwrap();
+        write_file_content(file.to_str().unwrap(), "second").unwrap();
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), "second");
+        let stray: Vec<_> = fs::read_dir(dir.path()).unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(stray.is_empty());
+    }
+
+    #[test]
+    fn test_write_file_content_empty() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("nested").join("empty.md");
+
+        write_file_content(file.to_str().unwrap(), "").unwrap();
+
+        assert!(file.is_file());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_file_content_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("script.md");
+        fs::write(&file, "old").unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o600)).unwrap();
+
+        write_file_content(file.to_str().unwrap(), "new").unwrap();
+
+        let mode = fs::metadata(&file).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+    }
+
+    #[test]
+    fn test_write_file_content_checked_conflict() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("note.md");
+        fs::write(&file, "external edit").unwrap();
+        let on_disk = modified_ms(&fs::metadata(&file).unwrap());
+
+        // The editor loaded the file before the external edit happened
+        let result = write_file_content_checked(file.to_str().unwrap(), "mine", Some(on_disk - 1000));
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+        assert_eq!(fs::read_to_string(&file).unwrap(), "external edit");
+
+        write_file_content_checked(file.to_str().unwrap(), "mine", Some(on_disk)).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "mine");
+    }
+
+    #[test]
+    fn test_write_file_content_checked_new_file() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("new.md");
+
+        write_file_content_checked(file.to_str().unwrap(), "hello", Some(0)).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_list_dir_recursive_default_ignores() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("node_modules").join("pkg")).unwrap();
+        fs::write(dir.path().join("node_modules").join("pkg").join("index.md"), "x").unwrap();
+        fs::create_dir_all(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join("note.md"), "x").unwrap();
+
+        let entries = list_dir_recursive(dir.path().to_str().unwrap(), None, SortOrder::default()).unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["note.md"]);
+    }
+
+    #[test]
+    fn test_list_dir_recursive_custom_ignores() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("notes").join("build")).unwrap();
+        fs::write(dir.path().join("notes").join("build").join("out.md"), "x").unwrap();
+        fs::write(dir.path().join("notes").join("keep.md"), "x").unwrap();
+        fs::write(dir.path().join("notes").join("debug.log"), "x").unwrap();
+        fs::write(dir.path().join("build"), "a file, not a directory").unwrap();
+
+        let ignore = vec!["build/".to_string(), "*.log".to_string()];
+        let entries = list_dir_recursive(dir.path().to_str().unwrap(), Some(&ignore), SortOrder::default()).unwrap();
+
+        // `build/` only matches directories, so the top-level file survives
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["notes", "build"]);
+
+        let children: Vec<_> = entries[0].children.as_ref().unwrap()
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(children, vec!["keep.md"]);
+    }
+
+    #[test]
+    fn test_matches_ignore_pattern() {
+        assert!(matches_ignore_pattern("node_modules", "node_modules", true));
+        assert!(matches_ignore_pattern("*.log", "debug.log", false));
+        assert!(matches_ignore_pattern("**/tmp?", "tmp1", true));
+        assert!(matches_ignore_pattern("/dist", "dist", true));
+        assert!(!matches_ignore_pattern("*.log", "debug.md", false));
+        assert!(!matches_ignore_pattern("dist/", "dist", false));
+        assert!(!matches_ignore_pattern("# comment", "# comment", false));
+    }
+
+    #[test]
+    fn test_create_new_file_with_content() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("notes").join("new.md");
+
+        create_new_file_with_content(path.to_str().unwrap(), "# Template\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Template\n");
+    }
+
+    #[test]
+    fn test_create_new_file_with_content_exists() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("existing.md");
+        fs::write(&path, "keep me").unwrap();
+
+        let result = create_new_file_with_content(path.to_str().unwrap(), "overwrite");
+        assert!(matches!(result, Err(AppError::AlreadyExists(_))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "keep me");
+    }
+
+    fn thousand_line_file(dir: &Path) -> PathBuf {
+        let path = dir.join("big.log");
+        let content: String = (0..1000).map(|i| format!("line {}\n", i)).collect();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_file_range_middle() {
+        let dir = tempdir().unwrap();
+        let path = thousand_line_file(dir.path());
+
+        let range = read_file_range(path.to_str().unwrap(), 500, 503).unwrap();
+        assert_eq!(range, "line 500\nline 501\nline 502\n");
+
+        let lines = read_file_lines(path.to_str().unwrap(), 998, 5000).unwrap();
+        assert_eq!(lines.content, "line 998\nline 999\n");
+        assert_eq!(lines.total_lines, 1000);
+    }
+
+    #[test]
+    fn test_read_file_range_start_past_end() {
+        let dir = tempdir().unwrap();
+        let path = thousand_line_file(dir.path());
+
+        let lines = read_file_lines(path.to_str().unwrap(), 2000, 2010).unwrap();
+        assert_eq!(lines.content, "");
+        assert_eq!(lines.total_lines, 1000);
+    }
+
+    #[test]
+    fn test_append_to_file_without_trailing_newline() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("daily.md");
+        fs::write(&path, "- first").unwrap();
+
+        append_to_file(path.to_str().unwrap(), "- second\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- first\n- second\n");
+    }
+
+    #[test]
+    fn test_append_to_file_with_trailing_newline() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("daily.md");
+        fs::write(&path, "- first\n").unwrap();
+
+        append_to_file(path.to_str().unwrap(), "- second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- first\n- second");
+    }
+
+    #[test]
+    fn test_append_to_missing_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("journal").join("new.md");
+
+        append_to_file(path.to_str().unwrap(), "- captured").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "- captured");
+    }
+
+    #[test]
+    fn test_file_checksum() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        let b = dir.path().join("b.md");
+        let c = dir.path().join("c.md");
+        fs::write(&a, "# Same content\n").unwrap();
+        fs::write(&b, "# Same content\n").unwrap();
+        fs::write(&c, "# Same content!").unwrap();
+
+        let hash_a = file_checksum(a.to_str().unwrap()).unwrap();
+        assert_eq!(hash_a.len(), 64);
+        assert_eq!(hash_a, file_checksum(b.to_str().unwrap()).unwrap());
+        assert_ne!(hash_a, file_checksum(c.to_str().unwrap()).unwrap());
+
+        let empty = dir.path().join("empty.md");
+        fs::write(&empty, "").unwrap();
+        assert_eq!(
+            file_checksum(empty.to_str().unwrap()).unwrap(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_delete_entry_missing() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing.md");
+
+        let result = delete_entry(missing.to_str().unwrap(), true);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let err = delete_entry(missing.to_str().unwrap(), false).unwrap_err();
+        assert_eq!(err.code(), "not_found");
+    }
+
+    #[test]
+    fn test_read_missing_file_not_found() {
+        let dir = tempdir().unwrap();
+        let missing = dir.path().join("missing.md");
+
+        let err = read_file_content(missing.to_str().unwrap()).unwrap_err();
+        assert_eq!(err.code(), "not_found");
+    }
+
+    #[test]
+    fn test_dir_size() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.md"), "12345").unwrap();
+        fs::create_dir_all(dir.path().join("sub/deeper")).unwrap();
+        fs::write(dir.path().join("sub/b.md"), "1234567890").unwrap();
+        fs::write(dir.path().join("sub/deeper/c.bin"), [0u8; 100]).unwrap();
+
+        assert_eq!(dir_size(dir.path().to_str().unwrap()).unwrap(), 115);
+        assert_eq!(dir_size(dir.path().join("a.md").to_str().unwrap()).unwrap(), 5);
+
+        let missing = dir.path().join("missing");
+        assert!(matches!(dir_size(missing.to_str().unwrap()), Err(AppError::NotFound(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dir_size_skips_unreadable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.md"), "12345").unwrap();
+        let locked = dir.path().join("locked");
+        fs::create_dir(&locked).unwrap();
+        fs::write(locked.join("secret.md"), "1234567").unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+
+        // Privileged users (e.g. root in CI containers) can still read the directory
+        let expected = if fs::read_dir(&locked).is_ok() { 12 } else { 5 };
+        let total = dir_size(dir.path().to_str().unwrap());
+
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(total.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_rename_entry_refuses_clobber() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        let b = dir.path().join("b.md");
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+
+        let result = rename_entry(a.to_str().unwrap(), b.to_str().unwrap(), false);
+        assert!(matches!(result, Err(AppError::AlreadyExists(_))));
+        assert_eq!(fs::read_to_string(&b).unwrap(), "b");
+
+        rename_entry(a.to_str().unwrap(), b.to_str().unwrap(), true).unwrap();
+        assert!(!a.exists());
+        assert_eq!(fs::read_to_string(&b).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_rename_entry_failed_overwrite_keeps_destination() {
+        let dir = tempdir().unwrap();
+        let failing = |_: &Path, _: &Path| Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+
+        let a = dir.path().join("a.md");
+        let b = dir.path().join("b.md");
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+        assert!(rename_entry_with(a.to_str().unwrap(), b.to_str().unwrap(), true, failing).is_err());
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "b");
+
+        let src = dir.path().join("src");
+        let dst = dir.path().join("dst");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(&dst).unwrap();
+        fs::write(dst.join("keep.md"), "keep").unwrap();
+        assert!(rename_entry_with(src.to_str().unwrap(), dst.to_str().unwrap(), true, failing).is_err());
+        assert!(src.is_dir());
+        assert_eq!(fs::read_to_string(dst.join("keep.md")).unwrap(), "keep");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 4);
+
+        rename_entry(src.to_str().unwrap(), dst.to_str().unwrap(), true).unwrap();
+        assert!(!src.exists());
+        assert!(!dst.join("keep.md").exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn test_move_falls_back_to_copy_across_devices() {
+        let dir = tempdir().unwrap();
+        let cross_device = |_: &Path, _: &Path| Err(std::io::Error::from_raw_os_error(if cfg!(windows) { 17 } else { 18 }));
+
+        let file = dir.path().join("note.md");
+        fs::write(&file, "note").unwrap();
+        let moved_file = dir.path().join("mnt").join("note.md");
+        fs::create_dir_all(moved_file.parent().unwrap()).unwrap();
+        move_with_fallback(&file, &moved_file, cross_device).unwrap();
+        assert!(!file.exists());
+        assert_eq!(fs::read_to_string(&moved_file).unwrap(), "note");
+
+        let folder = dir.path().join("folder");
+        fs::create_dir_all(folder.join("sub")).unwrap();
+        fs::write(folder.join("sub").join("a.md"), "a").unwrap();
+        let moved_folder = dir.path().join("mnt").join("folder");
+        move_with_fallback(&folder, &moved_folder, cross_device).unwrap();
+        assert!(!folder.exists());
+        assert_eq!(fs::read_to_string(moved_folder.join("sub").join("a.md")).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_move_keeps_source_on_other_rename_errors() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("note.md");
+        fs::write(&file, "note").unwrap();
+        let target = dir.path().join("other.md");
+
+        let denied = |_: &Path, _: &Path| Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+        assert!(move_with_fallback(&file, &target, denied).is_err());
+        assert!(file.exists());
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn test_list_deletable_counts_tree_without_deleting() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("folder");
+        fs::create_dir_all(root.join("a").join("b")).unwrap();
+        fs::write(root.join("top.md"), "12345").unwrap();
+        fs::write(root.join("a").join("one.md"), "123").unwrap();
+        fs::write(root.join("a").join("b").join("two.md"), "12").unwrap();
+        for i in 0..60 {
+            fs::write(root.join("a").join("b").join(format!("bulk-{:02}.txt", i)), "x").unwrap();
+        }
+
+        let preview = list_deletable(root.to_str().unwrap()).unwrap();
+        assert_eq!(preview.file_count, 63);
+        assert_eq!(preview.dir_count, 3);
+        assert_eq!(preview.total_bytes, 5 + 3 + 2 + 60);
+        assert_eq!(preview.sample_paths.len(), DELETE_PREVIEW_SAMPLE_LEN);
+        assert!(preview.sample_paths[0].ends_with("a"));
+
+        assert!(root.join("a").join("b").join("two.md").exists());
+        assert!(root.join("top.md").exists());
+
+        let single = list_deletable(root.join("top.md").to_str().unwrap()).unwrap();
+        assert_eq!((single.file_count, single.dir_count, single.total_bytes), (1, 0, 5));
+        assert!(matches!(list_deletable(dir.path().join("missing").to_str().unwrap()), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_rename_entry_same_path_noop() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        fs::write(&a, "a").unwrap();
+
+        rename_entry(a.to_str().unwrap(), a.to_str().unwrap(), false).unwrap();
+        let dotted = dir.path().join(".").join("a.md");
+        rename_entry(a.to_str().unwrap(), dotted.to_str().unwrap(), false).unwrap();
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_read_text_file() {
+        let dir = tempdir().unwrap();
+        let note = dir.path().join("note.md");
+        fs::write(&note, "# Title\nnaïve café ☕").unwrap();
+
+        assert_eq!(read_file_content(note.to_str().unwrap()).unwrap(), "# Title\nnaïve café ☕");
+    }
+
+    #[test]
+    fn test_read_rejects_binary_file() {
+        let dir = tempdir().unwrap();
+        let image = dir.path().join("image.png");
+        fs::write(&image, b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").unwrap();
+        let invalid = dir.path().join("latin1.txt");
+        fs::write(&invalid, b"caf\xe9").unwrap();
+
+        let err = read_file_content(image.to_str().unwrap()).unwrap_err();
+        assert!(matches!(err, AppError::BinaryFile { size: 16, .. }));
+        assert!(matches!(read_file_content(invalid.to_str().unwrap()), Err(AppError::BinaryFile { .. })));
+    }
+
+    #[test]
+    fn test_read_file_lossy_override() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("data.bin");
+        fs::write(&file, b"ab\0c\xff").unwrap();
+        let path = file.to_str().unwrap();
+
+        assert!(matches!(read_file_lossy(path, false), Err(AppError::BinaryFile { .. })));
+        assert_eq!(read_file_lossy(path, true).unwrap(), "ab\0c\u{FFFD}");
+    }
+
+    #[test]
+    fn test_list_dir_directories_first() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.md"), "a much longer file").unwrap();
+        fs::write(dir.path().join("b.md"), "b").unwrap();
+        fs::create_dir_all(dir.path().join("zeta")).unwrap();
+        fs::create_dir_all(dir.path().join("Alpha")).unwrap();
+
+        let names = |sort| -> Vec<String> {
+            list_dir_recursive(dir.path().to_str().unwrap(), None, sort).unwrap()
+                .into_iter()
+                .map(|e| e.name)
+                .collect()
+        };
+
+        assert_eq!(names(SortOrder::NameAsc), vec!["Alpha", "zeta", "a.md", "b.md"]);
+        assert_eq!(names(SortOrder::NameDesc), vec!["zeta", "Alpha", "b.md", "a.md"]);
+        assert_eq!(names(SortOrder::SizeAsc), vec!["Alpha", "zeta", "b.md", "a.md"]);
+        assert_eq!(names(SortOrder::SizeDesc), vec!["Alpha", "zeta", "a.md", "b.md"]);
+    }
+
+    #[test]
+    fn test_natural_name_order() {
+        let dir = tempdir().unwrap();
+        for name in ["file10.md", "File2.md", "file1.md", "file02.md"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let entries = list_dir_recursive(dir.path().to_str().unwrap(), None, SortOrder::NameAsc).unwrap();
+        let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["file1.md", "File2.md", "file02.md", "file10.md"]);
+        assert_eq!(natural_cmp("chapter 9", "Chapter 12"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_create_dir_existing_dir_is_ok() {
+        let dir = tempdir().unwrap();
+        let notes = dir.path().join("notes");
+        fs::create_dir(&notes).unwrap();
+        fs::write(notes.join("keep.md"), "x").unwrap();
+
+        create_new_dir(notes.to_str().unwrap(), false).unwrap();
+        assert!(notes.join("keep.md").exists());
+    }
+
+    #[test]
+    fn test_create_dir_rejects_existing_file() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("notes");
+        fs::write(&file, "a file").unwrap();
+
+        let result = create_new_dir(file.to_str().unwrap(), true);
+        assert!(matches!(result, Err(AppError::AlreadyExists(_))));
+        assert_eq!(fs::read_to_string(&file).unwrap(), "a file");
+    }
+
+    #[test]
+    fn test_create_dir_nested_needs_flag() {
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("a").join("b").join("c");
+        let path = nested.to_str().unwrap();
+
+        assert!(matches!(create_new_dir(path, false), Err(AppError::NotFound(_))));
+        assert!(!dir.path().join("a").exists());
+
+        create_new_dir(path, true).unwrap();
+        assert!(nested.is_dir());
+    }
+
+    #[test]
+    fn test_delete_many_reports_each_path() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("old.md");
+        fs::write(&existing, "x").unwrap();
+        let missing = dir.path().join("missing.md");
+        let paths = vec![
+            missing.to_string_lossy().to_string(),
+            existing.to_string_lossy().to_string(),
+        ];
+
+        let results = delete_many(&paths, true);
+
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0].1, Err(AppError::NotFound(_))));
+        assert!(results[1].1.is_ok());
+        assert!(!existing.exists());
+
+        let report = DeleteResult::new(results[0].0.clone(), &results[0].1);
+        assert!(!report.success);
+        assert!(report.error.unwrap().starts_with("Not found"));
+    }
+}
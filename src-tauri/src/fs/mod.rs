@@ -1,4 +1,28 @@
+mod backup;
+mod breadcrumbs;
+mod encoding;
+mod export;
+mod frontmatter;
+mod guard;
+mod links;
 mod manager;
+mod recent;
+mod search;
+mod stats;
+mod tags;
+mod template;
 pub mod watcher;
 
+pub use backup::*;
+pub use breadcrumbs::*;
+pub use encoding::*;
+pub use export::*;
+pub use frontmatter::*;
+pub use guard::*;
+pub use links::*;
 pub use manager::*;
+pub use recent::*;
+pub use search::*;
+pub use stats::*;
+pub use tags::*;
+pub use template::*;
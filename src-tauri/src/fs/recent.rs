@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+/// Maximum number of recently opened files that are remembered
+pub const RECENT_FILES_CAP: usize = 20;
+
+/// A recently opened file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentEntry {
+    pub path: String,
+    pub opened_at_ms: u64,
+}
+
+/// Recently opened files, most recent first
+pub struct RecentFiles {
+    entries: Mutex<VecDeque<RecentEntry>>,
+}
+
+impl RecentFiles {
+    pub fn new() -> Self {
+        RecentFiles {
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Load the list from a JSON file
+    /// A missing or corrupt file yields an empty list
+    pub fn load(path: &Path) -> Self {
+        let mut entries = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<VecDeque<RecentEntry>>(&json).ok())
+            .unwrap_or_default();
+        entries.truncate(RECENT_FILES_CAP);
+
+        RecentFiles {
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Save the list to a JSON file
+    pub fn save(&self, path: &Path) -> Result<(), AppError> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        let json = serde_json::to_string_pretty(&*entries)
+            .map_err(|e| AppError::InvalidPath(format!("Failed to serialize recent files: {}", e)))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, json)?;
+
+        Ok(())
+    }
+
+    /// Record that a file was opened
+    /// An already listed path moves to the front; the oldest entry is evicted past the cap
+    pub fn push(&self, path: String) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        entries.retain(|entry| entry.path != path);
+        entries.push_front(RecentEntry {
+            path,
+            opened_at_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        });
+        entries.truncate(RECENT_FILES_CAP);
+    }
+
+    /// List recent files, most recent first
+    pub fn list(&self) -> Vec<RecentEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().cloned().collect()
+    }
+
+    /// Forget all recent files
+    pub fn clear(&self) {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+impl Default for RecentFiles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(recent: &RecentFiles) -> Vec<String> {
+        recent.list().into_iter().map(|e| e.path).collect()
+    }
+
+    #[test]
+    fn test_reopen_moves_to_front() {
+        let recent = RecentFiles::new();
+        recent.push("/notes/a.md".to_string());
+        recent.push("/notes/b.md".to_string());
+        recent.push("/notes/a.md".to_string());
+
+        assert_eq!(paths(&recent), vec!["/notes/a.md", "/notes/b.md"]);
+    }
+
+    #[test]
+    fn test_cap_evicts_oldest() {
+        let recent = RecentFiles::new();
+        for i in 0..RECENT_FILES_CAP + 5 {
+            recent.push(format!("/notes/{}.md", i));
+        }
+
+        let list = paths(&recent);
+        assert_eq!(list.len(), RECENT_FILES_CAP);
+        assert_eq!(list[0], format!("/notes/{}.md", RECENT_FILES_CAP + 4));
+        assert!(!list.contains(&"/notes/4.md".to_string()));
+        assert!(list.contains(&"/notes/5.md".to_string()));
+
+        recent.clear();
+        assert!(recent.list().is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recent-files.json");
+
+        let recent = RecentFiles::new();
+        recent.push("/notes/a.md".to_string());
+        recent.push("/notes/b.md".to_string());
+        recent.save(&path).unwrap();
+
+        assert_eq!(RecentFiles::load(&path).list(), recent.list());
+        assert!(RecentFiles::load(&dir.path().join("missing.json")).list().is_empty());
+    }
+}
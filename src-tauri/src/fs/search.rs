@@ -0,0 +1,272 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use serde::Serialize;
+
+use crate::error::AppError;
+use super::manager::is_skipped_name;
+
+/// Maximum number of hits returned by a single search
+pub const MAX_SEARCH_HITS: usize = 1000;
+
+/// Number of leading bytes inspected when sniffing for binary content
+pub(crate) const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+/// A single match of a full-text search
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SearchHit {
+    pub path: String,
+    /// 1-based line number
+    pub line_number: usize,
+    pub line_text: String,
+    /// Match start offset within the line, in characters
+    pub match_start: usize,
+    /// Match end offset within the line (exclusive), in characters
+    pub match_end: usize,
+}
+
+/// Check whether a file looks binary (null byte in the first 8KB)
+pub(crate) fn looks_binary(path: &Path) -> Result<bool, AppError> {
+    let mut file = fs::File::open(path)?;
+    let mut buf = vec![0u8; BINARY_SNIFF_LEN];
+    let mut filled = 0;
+    while filled < buf.len() {
+        let n = file.read(&mut buf[filled..])?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(buf[..filled].contains(&0))
+}
+
+/// Search file contents under a directory
+/// Uses the same recursion rules as `list_dir_recursive`, skips binary files
+/// and stops after `MAX_SEARCH_HITS` matches.
+pub fn search_in_dir(root: &str, query: &str, case_sensitive: bool) -> Result<Vec<SearchHit>, AppError> {
+    let root_path = Path::new(root);
+    if !root_path.exists() {
+        return Err(AppError::NotFound(root.to_string()));
+    }
+    if !root_path.is_dir() {
+        return Err(AppError::InvalidPath("Path is not a directory".to_string()));
+    }
+
+    let mut hits = Vec::new();
+    if query.is_empty() {
+        return Ok(hits);
+    }
+
+    let matcher = regex::RegexBuilder::new(&regex::escape(query))
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|e| AppError::InvalidPath(format!("Invalid query: {}", e)))?;
+
+    search_dir(root_path, &matcher, &mut hits)?;
+    Ok(hits)
+}
+
+fn search_dir(dir: &Path, matcher: &regex::Regex, hits: &mut Vec<SearchHit>) -> Result<(), AppError> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        if hits.len() >= MAX_SEARCH_HITS {
+            return Ok(());
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_skipped_name(&name) {
+            continue;
+        }
+
+        let path = entry.path();
+        if path.is_dir() {
+            search_dir(&path, matcher, hits)?;
+        } else {
+            search_file(&path, matcher, hits);
+        }
+    }
+
+    Ok(())
+}
+
+/// Search a single file; unreadable and binary files are skipped
+fn search_file(path: &Path, matcher: &regex::Regex, hits: &mut Vec<SearchHit>) {
+    if looks_binary(path).unwrap_or(true) {
+        return;
+    }
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return,
+    };
+
+    for (index, line) in content.lines().enumerate() {
+        for m in matcher.find_iter(line) {
+            if hits.len() >= MAX_SEARCH_HITS {
+                return;
+            }
+            let match_start = line[..m.start()].chars().count();
+            hits.push(SearchHit {
+                path: path.to_string_lossy().to_string(),
+                line_number: index + 1,
+                line_text: line.to_string(),
+                match_start,
+                match_end: match_start + m.as_str().chars().count(),
+            });
+        }
+    }
+}
+
+/// List files under `root` matching a glob pattern (`**`, `*`, `?`, `[...]`)
+/// The pattern is matched relative to `root`; results are absolute paths, sorted.
+pub fn glob_files(root: &str, pattern: &str) -> Result<Vec<String>, AppError> {
+    let relative = Path::new(pattern);
+    if pattern.is_empty()
+        || relative.is_absolute()
+        || relative.components().any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err(AppError::InvalidPath(format!("Pattern must be relative to the root: {}", pattern)));
+    }
+
+    let root_path = Path::new(root);
+    if !root_path.exists() {
+        return Err(AppError::NotFound(root.to_string()));
+    }
+    if !root_path.is_dir() {
+        return Err(AppError::InvalidPath("Path is not a directory".to_string()));
+    }
+
+    let root_path = if root_path.is_absolute() {
+        root_path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(root_path)
+    };
+
+    // Escape the root so characters like `[` in folder names aren't treated as wildcards
+    let root_pattern = glob::Pattern::escape(&root_path.to_string_lossy());
+    let full_pattern = format!("{}/{}", root_pattern.trim_end_matches(['/', '\\']), pattern);
+    let options = glob::MatchOptions {
+        require_literal_leading_dot: true,
+        ..Default::default()
+    };
+    let paths = glob::glob_with(&full_pattern, options)
+        .map_err(|e| AppError::InvalidPath(format!("Invalid pattern: {}", e)))?;
+
+    // Entries that can't be read are skipped
+    let mut files: Vec<String> = paths
+        .filter_map(|p| p.ok())
+        .filter(|p| p.is_file())
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
+    files.sort();
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn sample_tree() -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.md"), "# Rust notes\nLearning rust today\n").unwrap();
+        fs::create_dir_all(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub").join("b.md"), "nothing here\nRUST again\n").unwrap();
+        fs::write(dir.path().join("image.bin"), b"rust\0\x01\x02rust").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_search_case_insensitive() {
+        let dir = sample_tree();
+        let hits = search_in_dir(dir.path().to_str().unwrap(), "rust", false).unwrap();
+
+        assert_eq!(hits.len(), 3);
+        assert!(hits.iter().all(|h| !h.path.ends_with("image.bin")));
+
+        let first = &hits[0];
+        assert!(first.path.ends_with("a.md"));
+        assert_eq!(first.line_number, 1);
+        assert_eq!(first.line_text, "# Rust notes");
+        assert_eq!((first.match_start, first.match_end), (2, 6));
+
+        let nested = hits.iter().find(|h| h.path.ends_with("b.md")).unwrap();
+        assert_eq!(nested.line_number, 2);
+    }
+
+    #[test]
+    fn test_search_case_sensitive() {
+        let dir = sample_tree();
+        let hits = search_in_dir(dir.path().to_str().unwrap(), "rust", true).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line_text, "Learning rust today");
+        assert_eq!((hits[0].match_start, hits[0].match_end), (9, 13));
+    }
+
+    #[test]
+    fn test_search_skips_binary_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("data.bin"), b"needle\0needle").unwrap();
+
+        let hits = search_in_dir(dir.path().to_str().unwrap(), "needle", true).unwrap();
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn test_search_caps_results() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("big.md"), "x\n".repeat(MAX_SEARCH_HITS + 50)).unwrap();
+
+        let hits = search_in_dir(dir.path().to_str().unwrap(), "x", true).unwrap();
+        assert_eq!(hits.len(), MAX_SEARCH_HITS);
+    }
+
+    fn glob_tree() -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("notes/daily")).unwrap();
+        fs::write(dir.path().join("readme.md"), "").unwrap();
+        fs::write(dir.path().join("todo.txt"), "").unwrap();
+        fs::write(dir.path().join("notes/ideas.md"), "").unwrap();
+        fs::write(dir.path().join("notes/list.txt"), "").unwrap();
+        fs::write(dir.path().join("notes/daily/2024-01-01.md"), "").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_glob_files_recursive() {
+        let dir = glob_tree();
+        let root = dir.path().to_str().unwrap();
+
+        let files = glob_files(root, "**/*.md").unwrap();
+        let expected: Vec<String> = ["notes/daily/2024-01-01.md", "notes/ideas.md", "readme.md"]
+            .iter()
+            .map(|p| dir.path().join(p).to_string_lossy().to_string())
+            .collect();
+        assert_eq!(files, expected);
+        assert!(files.iter().all(|f| Path::new(f).is_absolute()));
+    }
+
+    #[test]
+    fn test_glob_files_single_level() {
+        let dir = glob_tree();
+        let root = dir.path().to_str().unwrap();
+
+        let files = glob_files(root, "*.txt").unwrap();
+        assert_eq!(files, vec![dir.path().join("todo.txt").to_string_lossy().to_string()]);
+
+        let files = glob_files(root, "notes/????.txt").unwrap();
+        assert_eq!(files, vec![dir.path().join("notes/list.txt").to_string_lossy().to_string()]);
+    }
+
+    #[test]
+    fn test_glob_files_invalid_pattern() {
+        let dir = glob_tree();
+        let root = dir.path().to_str().unwrap();
+
+        assert!(matches!(glob_files(root, "notes/[a-"), Err(AppError::InvalidPath(_))));
+        assert!(matches!(glob_files(root, "../*.md"), Err(AppError::InvalidPath(_))));
+    }
+}
@@ -0,0 +1,114 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+/// Reading speed used for `reading_minutes`
+pub const WORDS_PER_MINUTE: f64 = 200.0;
+
+/// `[text](url)` and `![alt](url)`; group 1 is the text
+static LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"!?\[([^\]]*)\]\([^)]*\)").unwrap());
+
+/// Word/character statistics for a note
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct NoteStats {
+    pub words: usize,
+    pub chars: usize,
+    pub chars_no_spaces: usize,
+    pub lines: usize,
+    pub reading_minutes: f64,
+}
+
+/// CJK ideographs and kana are counted as one word each
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF |   // Hiragana, Katakana
+        0x3400..=0x4DBF |   // CJK Extension A
+        0x4E00..=0x9FFF |   // CJK Unified Ideographs
+        0xF900..=0xFAFF |   // CJK Compatibility Ideographs
+        0xAC00..=0xD7AF     // Hangul Syllables
+    )
+}
+
+/// Count words, ignoring Markdown syntax characters such as `#`, `*` and brackets
+fn count_words(text: &str) -> usize {
+    let mut words = 0;
+    let mut in_word = false;
+
+    for c in text.chars() {
+        if is_cjk(c) {
+            words += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                words += 1;
+                in_word = true;
+            }
+        } else if in_word && matches!(c, '\'' | '’' | '-' | '_') {
+            // Apostrophes, hyphens and underscores inside a word don't split it
+        } else {
+            in_word = false;
+        }
+    }
+
+    words
+}
+
+/// Compute statistics for Markdown content
+pub fn compute_stats(content: &str) -> NoteStats {
+    // Keep link/image text, drop the target: [text](url) -> text
+    let text = LINK.replace_all(content, "$1");
+
+    let words = count_words(&text);
+
+    NoteStats {
+        words,
+        chars: content.chars().count(),
+        chars_no_spaces: content.chars().filter(|c| !c.is_whitespace()).count(),
+        lines: content.lines().count(),
+        reading_minutes: words as f64 / WORDS_PER_MINUTE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_stats_empty() {
+        let stats = compute_stats("");
+        assert_eq!(stats, NoteStats {
+            words: 0,
+            chars: 0,
+            chars_no_spaces: 0,
+            lines: 0,
+            reading_minutes: 0.0,
+        });
+    }
+
+    #[test]
+    fn test_compute_stats_paragraphs() {
+        let content = "First paragraph with five words.\n\nSecond one has **bold** and a [link](https://example.com/page).\n";
+        let stats = compute_stats(content);
+
+        assert_eq!(stats.words, 12);
+        assert_eq!(stats.lines, 3);
+        assert_eq!(stats.chars, content.chars().count());
+        assert_eq!(stats.chars_no_spaces, content.chars().filter(|c| !c.is_whitespace()).count());
+        assert!((stats.reading_minutes - 12.0 / WORDS_PER_MINUTE).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_compute_stats_headings() {
+        let content = "# Heading\n## Second Heading\n### Don't-stop here\n- * list item";
+        let stats = compute_stats(content);
+
+        assert_eq!(stats.words, 7);
+        assert_eq!(stats.lines, 4);
+    }
+
+    #[test]
+    fn test_compute_stats_cjk() {
+        let stats = compute_stats("# 笔记\n今天学习 Rust");
+        assert_eq!(stats.words, 7);
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::error::AppError;
+use super::frontmatter::parse_front_matter;
+use super::manager::is_skipped_name;
+use super::search::looks_binary;
+
+/// `#tag` after whitespace or at the start of a line
+static TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:^|\s)#([\p{L}\p{N}_/-]+)").unwrap());
+
+/// Inline code spans, stripped before tags are matched
+static INLINE_CODE: Lazy<Regex> = Lazy::new(|| Regex::new(r"`[^`]*`").unwrap());
+
+/// Inline `#tag` tokens in a note body, lowercased
+/// Tags must start after whitespace (so headings and URL fragments don't count) and contain
+/// at least one non-digit; fenced and inline code is ignored.
+fn inline_tags(body: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut in_fence = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let line = INLINE_CODE.replace_all(line, "");
+        for caps in TAG.captures_iter(&line) {
+            let name = caps[1].trim_end_matches(['/', '-']);
+            if name.chars().any(|c| !c.is_ascii_digit()) {
+                tags.push(name.to_lowercase());
+            }
+        }
+    }
+    tags
+}
+
+/// Tags declared by a note, from front matter and inline `#tags`, lowercased
+fn note_tags(content: &str) -> Vec<String> {
+    let (front_matter, body) = parse_front_matter(content);
+
+    let mut tags: Vec<String> = front_matter
+        .map(|fm| fm.tags)
+        .unwrap_or_default()
+        .iter()
+        .map(|t| t.trim().trim_start_matches('#').to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.extend(inline_tags(&body));
+    tags
+}
+
+/// Map each tag (case-insensitive, lowercased) to the sorted paths of the notes declaring it
+/// Uses the same recursion rules as `list_dir_recursive`; binary and unreadable files are skipped.
+pub fn build_tag_index(root: &str) -> Result<HashMap<String, Vec<String>>, AppError> {
+    let root_path = Path::new(root);
+    if !root_path.exists() {
+        return Err(AppError::NotFound(root.to_string()));
+    }
+    if !root_path.is_dir() {
+        return Err(AppError::InvalidPath("Path is not a directory".to_string()));
+    }
+
+    let mut index: HashMap<String, Vec<String>> = HashMap::new();
+    index_dir(root_path, &mut index)?;
+
+    for paths in index.values_mut() {
+        paths.sort();
+        paths.dedup();
+    }
+    Ok(index)
+}
+
+fn index_dir(dir: &Path, index: &mut HashMap<String, Vec<String>>) -> Result<(), AppError> {
+    for entry in fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_skipped_name(&name) {
+            continue;
+        }
+
+        let path = entry.path();
+        if path.is_dir() {
+            index_dir(&path, index)?;
+            continue;
+        }
+        if looks_binary(&path).unwrap_or(true) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let path = path.to_string_lossy().to_string();
+        for tag in note_tags(&content) {
+            index.entry(tag).or_default().push(path.clone());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_inline_tags() {
+        let body = "# Heading\nSome #Rust and #tauri/plugins here, not #123.\nSee https://x.com/#anchor\n`#code` and\n```\n#fenced\n```\n";
+        assert_eq!(inline_tags(body), vec!["rust", "tauri/plugins"]);
+    }
+
+    #[test]
+    fn test_build_tag_index() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        let b = dir.path().join("sub").join("b.md");
+        fs::create_dir_all(b.parent().unwrap()).unwrap();
+        fs::write(&a, "---\ntags: [Rust, notes]\n---\nBody").unwrap();
+        fs::write(&b, "Learning #rust today #rust").unwrap();
+        fs::write(dir.path().join("blob.bin"), b"#rust\0\x01").unwrap();
+
+        let index = build_tag_index(dir.path().to_str().unwrap()).unwrap();
+
+        let expected = vec![a.to_string_lossy().to_string(), b.to_string_lossy().to_string()];
+        assert_eq!(index.get("rust"), Some(&expected));
+        assert_eq!(index.get("notes"), Some(&vec![a.to_string_lossy().to_string()]));
+        assert_eq!(index.len(), 2);
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::error::AppError;
+use super::manager::create_new_file_with_content;
+
+/// `{{key}}`, allowing whitespace inside the braces
+static TOKEN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.-]+)\s*\}\}").unwrap());
+
+/// Replace `{{key}}` tokens in `template`
+/// Built-ins: `{{date}}` (YYYY-MM-DD), `{{time}}` (HH:MM), `{{datetime}}` and `{{title}}` (target file stem).
+/// Entries in `vars` take precedence over built-ins; unknown tokens are left verbatim.
+pub(crate) fn render_template(
+    template: &str,
+    vars: &HashMap<String, String>,
+    title: &str,
+    now: DateTime<Local>,
+) -> String {
+    TOKEN
+        .replace_all(template, |caps: &regex::Captures| {
+            let key = &caps[1];
+            if let Some(value) = vars.get(key) {
+                return value.clone();
+            }
+            match key {
+                "date" => now.format("%Y-%m-%d").to_string(),
+                "time" => now.format("%H:%M").to_string(),
+                "datetime" => now.format("%Y-%m-%d %H:%M").to_string(),
+                "title" => title.to_string(),
+                _ => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Create a new note at `target_path` from the template at `template_path`
+/// Returns `AppError::NotFound` if the template is missing and `AppError::AlreadyExists`
+/// if the target exists; the rendered note is written atomically.
+pub fn create_from_template(
+    target_path: &str,
+    template_path: &str,
+    vars: HashMap<String, String>,
+) -> Result<(), AppError> {
+    let template = Path::new(template_path);
+    if !template.is_file() {
+        return Err(AppError::NotFound(template.display().to_string()));
+    }
+    let template = std::fs::read_to_string(template)?;
+
+    let title = Path::new(target_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let content = render_template(&template, &vars, &title, Local::now());
+
+    create_new_file_with_content(target_path, &content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn fixed_now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 3, 9, 7, 5, 0).unwrap()
+    }
+
+    #[test]
+    fn test_render_builtin_date() {
+        let rendered = render_template("# {{date}} {{ time }}\n", &HashMap::new(), "journal", fixed_now());
+        assert_eq!(rendered, "# 2024-03-09 07:05\n");
+    }
+
+    #[test]
+    fn test_render_vars_and_leftover_tokens() {
+        let mut vars = HashMap::new();
+        vars.insert("project".to_string(), "Lumina".to_string());
+        vars.insert("date".to_string(), "someday".to_string());
+
+        let rendered = render_template("{{project}} {{date}} {{title}} {{unknown}} {{ }}", &vars, "Plan", fixed_now());
+        assert_eq!(rendered, "Lumina someday Plan {{unknown}} {{ }}");
+    }
+
+    #[test]
+    fn test_create_from_template() {
+        let dir = tempdir().unwrap();
+        let template = dir.path().join("daily.md");
+        std::fs::write(&template, "# {{title}}\n\n{{mood}}\n").unwrap();
+        let target = dir.path().join("journal").join("today.md");
+
+        let mut vars = HashMap::new();
+        vars.insert("mood".to_string(), "calm".to_string());
+        create_from_template(target.to_str().unwrap(), template.to_str().unwrap(), vars).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "# today\n\ncalm\n");
+    }
+
+    #[test]
+    fn test_create_from_missing_template() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("note.md");
+        let missing = dir.path().join("missing.md");
+
+        let result = create_from_template(target.to_str().unwrap(), missing.to_str().unwrap(), HashMap::new());
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+        assert!(!target.exists());
+    }
+}
@@ -1,8 +1,16 @@
-use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::event::ModifyKind;
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
 use std::path::Path;
-use std::sync::mpsc::channel;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
+use crate::error::AppError;
+use super::manager::is_skipped_name;
+
 /// File system event types
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(tag = "type")]
@@ -80,3 +88,290 @@ pub fn start_watcher(app: AppHandle, watch_path: String) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Debounce window for `fs:changed` events
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Kind of a file system change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FsChangeKind {
+    Create,
+    Modify,
+    Delete,
+    Rename,
+}
+
+/// Payload of the "fs:changed" event
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct FsChangedPayload {
+    pub kind: FsChangeKind,
+    pub path: String,
+}
+
+/// Coalesces bursts of changes per path until they have been quiet for the debounce window
+pub struct Debouncer {
+    window: Duration,
+    pending: HashMap<String, (FsChangeKind, Instant)>,
+}
+
+impl Debouncer {
+    pub fn new(window: Duration) -> Self {
+        Debouncer {
+            window,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Record a change; a create followed by modifications is still reported as a create
+    pub fn push(&mut self, kind: FsChangeKind, path: String, now: Instant) {
+        let kind = match self.pending.get(&path) {
+            Some((FsChangeKind::Create, _)) if kind == FsChangeKind::Modify => FsChangeKind::Create,
+            _ => kind,
+        };
+        self.pending.insert(path, (kind, now));
+    }
+
+    /// Take all changes that have been quiet for at least the debounce window
+    pub fn drain_ready(&mut self, now: Instant) -> Vec<FsChangedPayload> {
+        let window = self.window;
+        let ready: Vec<String> = self.pending.iter()
+            .filter(|(_, (_, at))| now.duration_since(*at) >= window)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        let mut events: Vec<FsChangedPayload> = ready.into_iter()
+            .filter_map(|path| self.pending.remove(&path).map(|(kind, _)| FsChangedPayload { kind, path }))
+            .collect();
+        events.sort_by(|a, b| a.path.cmp(&b.path));
+        events
+    }
+
+    /// Take all pending changes regardless of age
+    pub fn drain_all(&mut self) -> Vec<FsChangedPayload> {
+        let mut events: Vec<FsChangedPayload> = self.pending.drain()
+            .map(|(path, (kind, _))| FsChangedPayload { kind, path })
+            .collect();
+        events.sort_by(|a, b| a.path.cmp(&b.path));
+        events
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// Map a notify event kind to the change kind reported to the frontend
+fn change_kind(kind: &EventKind) -> Option<FsChangeKind> {
+    match kind {
+        EventKind::Create(_) => Some(FsChangeKind::Create),
+        EventKind::Modify(ModifyKind::Name(_)) => Some(FsChangeKind::Rename),
+        EventKind::Modify(_) => Some(FsChangeKind::Modify),
+        EventKind::Remove(_) => Some(FsChangeKind::Delete),
+        _ => None,
+    }
+}
+
+/// A running directory watcher; dropping it stops the watcher and joins its thread
+pub struct WatcherHandle {
+    watcher: Option<RecommendedWatcher>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WatcherHandle {
+    /// Stop watching and wait for the event thread to finish
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        // Dropping the watcher drops the event sender, which ends the thread's loop
+        self.watcher.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for WatcherHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Watch a directory recursively and emit debounced "fs:changed" events
+pub fn watch_directory(path: &str, app: AppHandle) -> Result<WatcherHandle, AppError> {
+    watch_directory_with(path, move |payload| {
+        let _ = app.emit("fs:changed", payload);
+    })
+}
+
+/// Watch a directory recursively, passing debounced changes to `emit`
+pub fn watch_directory_with<F>(path: &str, emit: F) -> Result<WatcherHandle, AppError>
+where
+    F: Fn(FsChangedPayload) + Send + 'static,
+{
+    let root = Path::new(path);
+    if !root.is_dir() {
+        return Err(AppError::NotFound(path.to_string()));
+    }
+
+    let (tx, rx) = channel::<Event>();
+    let mut watcher = RecommendedWatcher::new(
+        move |res: Result<Event, notify::Error>| {
+            if let Ok(event) = res {
+                let _ = tx.send(event);
+            }
+        },
+        Config::default(),
+    )
+    .map_err(|e| AppError::InvalidPath(e.to_string()))?;
+
+    watcher
+        .watch(root, RecursiveMode::Recursive)
+        .map_err(|e| AppError::InvalidPath(e.to_string()))?;
+
+    let root = root.to_path_buf();
+    let thread = std::thread::spawn(move || {
+        let mut debouncer = Debouncer::new(WATCH_DEBOUNCE);
+
+        loop {
+            let timeout = if debouncer.is_empty() { Duration::from_secs(3600) } else { WATCH_DEBOUNCE };
+            match rx.recv_timeout(timeout) {
+                Ok(event) => {
+                    let Some(kind) = change_kind(&event.kind) else { continue };
+                    let now = Instant::now();
+                    for p in &event.paths {
+                        // Ignore noise from hidden/vendored directories (e.g. .git)
+                        let relative = p.strip_prefix(&root).unwrap_or(p);
+                        if relative.components().any(|c| is_skipped_name(&c.as_os_str().to_string_lossy())) {
+                            continue;
+                        }
+                        debouncer.push(kind, p.to_string_lossy().to_string(), now);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    for payload in debouncer.drain_all() {
+                        emit(payload);
+                    }
+                    break;
+                }
+            }
+
+            for payload in debouncer.drain_ready(Instant::now()) {
+                emit(payload);
+            }
+        }
+    });
+
+    Ok(WatcherHandle {
+        watcher: Some(watcher),
+        thread: Some(thread),
+    })
+}
+
+/// Active watchers keyed by watched root
+pub struct WatcherState {
+    watchers: Mutex<HashMap<String, WatcherHandle>>,
+}
+
+impl WatcherState {
+    pub fn new() -> Self {
+        WatcherState {
+            watchers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start watching `root`, tearing down the watchers of any previous workspace
+    pub fn start(&self, root: &str, app: AppHandle) -> Result<(), AppError> {
+        let mut watchers = self.watchers.lock().unwrap_or_else(|e| e.into_inner());
+
+        for (_, handle) in watchers.drain() {
+            handle.stop();
+        }
+
+        let handle = watch_directory(root, app)?;
+        watchers.insert(root.to_string(), handle);
+        Ok(())
+    }
+
+    /// Stop watching `root`; returns false if it wasn't being watched
+    pub fn stop(&self, root: &str) -> Result<bool, AppError> {
+        let handle = {
+            let mut watchers = self.watchers.lock().unwrap_or_else(|e| e.into_inner());
+            watchers.remove(root)
+        };
+
+        match handle {
+            Some(handle) => {
+                handle.stop();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+impl Default for WatcherState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debouncer_coalesces_bursts() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(WATCH_DEBOUNCE);
+
+        debouncer.push(FsChangeKind::Create, "/notes/a.md".into(), start);
+        debouncer.push(FsChangeKind::Modify, "/notes/a.md".into(), start + Duration::from_millis(50));
+        debouncer.push(FsChangeKind::Modify, "/notes/a.md".into(), start + Duration::from_millis(100));
+
+        // Still within the window of the last change
+        assert!(debouncer.drain_ready(start + Duration::from_millis(250)).is_empty());
+
+        let events = debouncer.drain_ready(start + Duration::from_millis(300));
+        assert_eq!(events, vec![FsChangedPayload {
+            kind: FsChangeKind::Create,
+            path: "/notes/a.md".into(),
+        }]);
+        assert!(debouncer.is_empty());
+    }
+
+    #[test]
+    fn test_debouncer_latest_kind_wins() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(WATCH_DEBOUNCE);
+
+        debouncer.push(FsChangeKind::Modify, "/notes/a.md".into(), start);
+        debouncer.push(FsChangeKind::Delete, "/notes/a.md".into(), start);
+        debouncer.push(FsChangeKind::Modify, "/notes/b.md".into(), start);
+
+        let events = debouncer.drain_all();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, FsChangeKind::Delete);
+        assert_eq!(events[1].kind, FsChangeKind::Modify);
+    }
+
+    #[test]
+    fn test_watch_directory_stops_cleanly() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, rx) = channel();
+
+        let handle = watch_directory_with(dir.path().to_str().unwrap(), move |payload| {
+            let _ = tx.send(payload);
+        }).unwrap();
+        std::fs::write(dir.path().join("note.md"), "hello").unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+
+        // Stopping joins the thread and flushes anything still pending
+        handle.stop();
+        let events: Vec<_> = rx.try_iter().collect();
+        assert!(events.iter().all(|e| e.path.ends_with("note.md")));
+    }
+}
@@ -0,0 +1,66 @@
+/// Application logging
+///
+/// Records from the `log` macros are written to stderr. Everything is passed to the
+/// logger; the level filter is the global `log::max_level`, adjustable at runtime.
+
+use crate::error::AppError;
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Level used until the frontend changes it
+pub const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Info;
+
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{:<5} {}", record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Install the stderr logger at `DEFAULT_LOG_LEVEL`; later calls are ignored
+pub fn init() {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(DEFAULT_LOG_LEVEL);
+    }
+}
+
+/// Parse a level name (`off`, `error`, `warn`, `info`, `debug`, `trace`; case-insensitive)
+pub fn parse_level(level: &str) -> Result<LevelFilter, AppError> {
+    level.trim().parse::<LevelFilter>()
+        .map_err(|_| AppError::InvalidArgument(format!("Unknown log level: {}", level)))
+}
+
+/// Change the log level at runtime
+/// 
+/// # Arguments
+/// * `level` - One of `off`, `error`, `warn`, `info`, `debug`, `trace`
+#[tauri::command]
+pub async fn set_log_level(level: String) -> Result<(), AppError> {
+    let filter = parse_level(&level)?;
+    log::set_max_level(filter);
+    log::info!("[LOG] level set to {}", filter);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level("debug").unwrap(), LevelFilter::Debug);
+        assert_eq!(parse_level(" WARN ").unwrap(), LevelFilter::Warn);
+        assert_eq!(parse_level("off").unwrap(), LevelFilter::Off);
+        assert!(matches!(parse_level("loud"), Err(AppError::InvalidArgument(_))));
+    }
+}
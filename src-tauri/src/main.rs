@@ -13,29 +13,58 @@ mod cef;
 mod webdav;
 mod langgraph;
 mod agent;
+mod logging;
 
 use tauri::Manager;
 
 fn main() {
+    logging::init();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_os::init())
         .invoke_handler(tauri::generate_handler![
+            logging::set_log_level,
+            commands::set_workspace_root,
             commands::read_file,
+            commands::read_file_lossy,
+            commands::read_file_encoded,
+            commands::detect_file_encoding,
+            commands::read_file_lines,
+            commands::get_recent_files,
+            commands::clear_recent_files,
             commands::save_file,
+            commands::save_file_with_backup,
+            commands::append_note,
+            commands::get_note_stats,
+            commands::get_file_checksum,
+            commands::get_dir_size,
+            commands::get_breadcrumbs,
+            commands::get_front_matter,
             commands::write_binary_file,
             commands::read_binary_file_base64,
             commands::list_directory,
             commands::list_directory_tree,
+            commands::search_notes,
+            commands::glob_notes,
+            commands::get_tag_index,
+            commands::get_link_graph,
             commands::create_file,
+            commands::create_file_with_content,
+            commands::create_note_from_template,
+            commands::export_note_html,
             commands::create_dir,
             commands::delete_file,
+            commands::preview_delete,
+            commands::delete_files,
             commands::rename_file,
+            commands::copy_file,
             commands::move_file,
             commands::move_folder,
             commands::show_in_explorer,
+            commands::open_external_url,
             commands::open_video_window,
             commands::close_video_window,
             commands::get_video_time,
@@ -50,6 +79,8 @@ fn main() {
             commands::fill_danmaku_prefix,
             commands::setup_danmaku_autofill,
             commands::start_file_watcher,
+            commands::start_watching,
+            commands::stop_watching,
             // Browser WebView commands
             commands::create_browser_webview,
             commands::update_browser_webview_bounds,
@@ -64,6 +95,14 @@ fn main() {
             commands::browser_webview_exists,
             // CEF Browser commands
             cef::commands::create_cef_browser,
+            cef::commands::cef_create_private_tab,
+            cef::commands::get_home_url,
+            cef::commands::set_home_url,
+            cef::commands::get_default_bounds,
+            cef::commands::set_default_bounds,
+            cef::commands::get_allow_local_files,
+            cef::commands::set_allow_local_files,
+            cef::commands::cef_go_home,
             cef::commands::navigate_cef,
             cef::commands::close_cef_browser,
             cef::commands::cef_go_back,
@@ -71,13 +110,75 @@ fn main() {
             cef::commands::cef_reload,
             cef::commands::cef_stop,
             cef::commands::cef_execute_js,
+            cef::commands::cef_report_js_result,
             cef::commands::cef_get_page_content,
+            cef::commands::cef_clip_page_to_note,
+            cef::commands::cef_bookmark_to_note,
             cef::commands::cef_get_selection,
+            cef::commands::cef_report_selection,
+            cef::commands::cef_selection_to_note,
+            cef::commands::cef_find_in_page,
+            cef::commands::cef_on_find_result,
             cef::commands::cef_on_url_change,
             cef::commands::cef_on_title_change,
             cef::commands::cef_on_loading_state_change,
+            cef::commands::cef_on_load_progress,
+            cef::commands::cef_on_favicon_change,
+            cef::commands::cef_clear_favicon_cache,
             cef::commands::cef_switch_tab,
+            cef::commands::cef_activate_tab,
+            cef::commands::cef_rename_tab,
             cef::commands::cef_update_bounds,
+            cef::commands::cef_flush_bounds,
+            cef::commands::cef_update_bounds_batch,
+            cef::commands::cef_instance_at_point,
+            cef::commands::cef_set_instance_visible,
+            cef::commands::cef_save_session,
+            cef::commands::cef_restore_session,
+            cef::commands::cef_export_session,
+            cef::commands::cef_import_session,
+            cef::commands::cef_get_history,
+            cef::commands::cef_search_history,
+            cef::commands::cef_clear_history,
+            cef::commands::cef_top_sites,
+            cef::commands::cef_frecent_urls,
+            cef::commands::cef_go_to_history_index,
+            cef::commands::cef_navigate_offset,
+            cef::commands::cef_set_zoom,
+            cef::commands::cef_save_scroll,
+            cef::commands::cef_restore_closed_tab,
+            cef::commands::cef_set_user_agent,
+            cef::commands::cef_print_to_pdf,
+            cef::commands::cef_capture_screenshot,
+            cef::commands::cef_report_screenshot,
+            cef::commands::cef_get_cookies,
+            cef::commands::cef_report_cookies,
+            cef::commands::cef_clear_cookies,
+            cef::commands::cef_add_bookmark,
+            cef::commands::cef_remove_bookmark,
+            cef::commands::cef_list_bookmarks,
+            cef::commands::cef_list_bookmarks_tree,
+            cef::commands::cef_move_bookmark,
+            cef::commands::cef_rename_bookmark_folder,
+            cef::commands::cef_list_tabs,
+            cef::commands::cef_broadcast_state,
+            cef::commands::cef_diagnostics,
+            cef::commands::cef_find_tab_by_url,
+            cef::commands::cef_set_muted,
+            cef::commands::cef_on_audio_state,
+            cef::commands::cef_list_audible_tabs,
+            cef::commands::cef_get_load_duration,
+            cef::commands::cef_on_load_error,
+            cef::commands::cef_get_load_error,
+            cef::commands::cef_poll_timeouts,
+            cef::commands::cef_block_host,
+            cef::commands::cef_unblock_host,
+            cef::commands::cef_list_blocked_hosts,
+            cef::commands::cef_on_download_started,
+            cef::commands::cef_on_download_progress,
+            cef::commands::cef_on_download_finished,
+            cef::commands::cef_list_downloads,
+            cef::commands::cef_cancel_download,
             // Vector DB commands
             vector_db::init_vector_db,
             vector_db::upsert_vector_chunks,
@@ -127,7 +228,24 @@ fn main() {
         .manage(webdav::commands::WebDAVState::new())
         .manage(agent::AgentState::new())
         .manage(agent::DeepResearchStateManager::new())
+        .manage(cef::CefBrowserManager::new())
+        .manage(cef::CefInstancePool::new())
+        .manage(cef::BrowserConfig::new())
+        .manage(cef::commands::ScreenshotResults::new())
+        .manage(cef::commands::JsResults::new())
+        .manage(cef::commands::CookieResults::new())
+        .manage(cef::commands::SelectionResults::new())
+        .manage(cef::blocklist::NavigationBlocklist::new())
+        .manage(cef::debounce::BoundsDebouncer::new())
+        .manage(cef::downloads::DownloadManager::new())
+        .manage(fs::watcher::WatcherState::new())
+        .manage(fs::WorkspaceGuard::new())
         .setup(|app| {
+            let bookmarks_path = cef::commands::bookmarks_file_path(app.handle())?;
+            app.manage(cef::bookmarks::BookmarkStore::load(&bookmarks_path));
+            let recent_path = commands::recent_files_path(app.handle())?;
+            app.manage(fs::RecentFiles::load(&recent_path));
+
             let window = app.get_webview_window("main").unwrap();
             
             // Mac 上启用 decorations 并使用透明标题栏，避免无边框窗口的兼容性问题